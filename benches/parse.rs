@@ -16,9 +16,17 @@ const INPUTS: &[(&str, &str)] = &[
     ("09: Lyrics", "Van Gogh my earlobe\nI can't hear, I'm here though\nI may be a weirdo, but this is my year, yo\nMy life may be crazy\nMy lack of the lazy has let me write code that I love on the daily.\nVan Gogh my earlobe\nI can't hear, I'm here though\nI may be a weirdo, but this is my year, yo\nMy life may be crazy\nMy lack of the lazy has let me write code that I love on the daily.\nVan Gogh my earlobe\nI can't hear, I'm here though\nI may be a weirdo, but this is my year, yo\nMy life may be crazy\nMy lack of the lazy has let me write code that I love on the daily."),
 ];
 
-fn parse(c: &mut Criterion) {
+fn load_font(kerning_lookup: KerningLookup) -> BMFont {
     let file = File::open("font.fnt").unwrap();
-    let font = BMFont::new(file, OrdinateOrientation::TopToBottom).unwrap();
+    let options = LoadOptions {
+        kerning_lookup,
+        ..Default::default()
+    };
+    BMFont::with_options(file, OrdinateOrientation::TopToBottom, &options).unwrap()
+}
+
+fn parse(c: &mut Criterion) {
+    let font = load_font(KerningLookup::BinarySearch);
     let mut group = c.benchmark_group("Parse");
     for (desc, input) in INPUTS.iter() {
         group.bench_with_input(BenchmarkId::new("Input", desc), input, |b, input| {
@@ -27,5 +35,80 @@ fn parse(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, parse);
+fn kerning(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Kerning");
+    for &(name, kerning_lookup) in &[
+        ("BinarySearch", KerningLookup::BinarySearch),
+        ("HashMap", KerningLookup::HashMap),
+    ] {
+        let font = load_font(kerning_lookup);
+        for (desc, input) in INPUTS.iter() {
+            group.bench_with_input(BenchmarkId::new(name, desc), input, |b, input| {
+                b.iter(|| {
+                    let char_positions = font.parse(black_box(input));
+
+                    #[cfg(feature = "parse-error")]
+                    let char_positions = char_positions.unwrap();
+
+                    char_positions.count()
+                })
+            });
+        }
+    }
+}
+
+fn utf16(c: &mut Criterion) {
+    let font = load_font(KerningLookup::BinarySearch);
+    let mut group = c.benchmark_group("Utf16");
+    for (desc, input) in INPUTS.iter() {
+        group.bench_with_input(BenchmarkId::new("Str", desc), input, |b, input| {
+            b.iter(|| {
+                let units = black_box(input).encode_utf16().collect::<Vec<u16>>();
+                let text = String::from_utf16(&units).unwrap();
+                let char_positions = font.parse(&text);
+
+                #[cfg(feature = "parse-error")]
+                let char_positions = char_positions.unwrap();
+
+                char_positions.count()
+            })
+        });
+        let units = input.encode_utf16().collect::<Vec<u16>>();
+        group.bench_with_input(BenchmarkId::new("Units", desc), &units, |b, units| {
+            b.iter(|| {
+                let char_positions = font.parse_utf16(black_box(units));
+
+                #[cfg(feature = "parse-error")]
+                let char_positions = char_positions.unwrap();
+
+                char_positions.count()
+            })
+        });
+    }
+}
+
+fn parallel(c: &mut Criterion) {
+    let font = load_font(KerningLookup::BinarySearch);
+    let mut group = c.benchmark_group("Parallel");
+    let (desc, lyrics) = INPUTS[INPUTS.len() - 1];
+    for &repeat in &[1, 100, 1000] {
+        let input = lyrics.repeat(repeat);
+        let desc = format!("{} x{}", desc, repeat);
+        group.bench_with_input(BenchmarkId::new("Parse", &desc), &input, |b, input| {
+            b.iter(|| {
+                let char_positions = font.parse(black_box(input));
+
+                #[cfg(feature = "parse-error")]
+                let char_positions = char_positions.unwrap();
+
+                char_positions.collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("ParParse", &desc), &input, |b, input| {
+            b.iter(|| font.par_parse_with(black_box(input), &LayoutOptions::default()))
+        });
+    }
+}
+
+criterion_group!(benches, parse, kerning, utf16, parallel);
 criterion_main!(benches);
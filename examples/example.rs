@@ -3,8 +3,8 @@ extern crate bmfont;
 extern crate glium;
 extern crate image;
 
-use bmfont::{BMFont, OrdinateOrientation};
-use glium::{Display, Program, VertexBuffer};
+use bmfont::{BMFont, GlyphMesh, OrdinateOrientation};
+use glium::{Display, IndexBuffer, Program, VertexBuffer};
 use std::io::Cursor;
 
 fn create_program(display: &Display) -> Program {
@@ -65,48 +65,28 @@ fn main() {
     #[cfg(feature = "parse-error")]
     let char_positions = char_positions.unwrap();
 
-    let shapes = char_positions.into_iter().map(|char_position| {
-        let left_page_x = char_position.page_rect.x as f32 / image_dimensions.0 as f32;
-        let right_page_x = char_position.page_rect.max_x() as f32 / image_dimensions.0 as f32;
-        let top_page_y = char_position.page_rect.y as f32 / image_dimensions.1 as f32;
-        let bottom_page_y = char_position.page_rect.max_y() as f32 / image_dimensions.1 as f32;
-
-        let left_screen_x = char_position.screen_rect.x as f32 / design_size.0 as f32;
-        let right_screen_x = char_position.screen_rect.max_x() as f32 / design_size.0 as f32;
-        let bottom_screen_y = char_position.screen_rect.y as f32 / design_size.1 as f32;
-        let top_screen_y = char_position.screen_rect.max_y() as f32 / design_size.1 as f32;
-        vec![
-            Vertex {
-                position: [left_screen_x, bottom_screen_y],
-                tex_coords: [left_page_x, bottom_page_y],
-            },
-            Vertex {
-                position: [left_screen_x, top_screen_y],
-                tex_coords: [left_page_x, top_page_y],
-            },
-            Vertex {
-                position: [right_screen_x, top_screen_y],
-                tex_coords: [right_page_x, top_page_y],
-            },
-            Vertex {
-                position: [left_screen_x, bottom_screen_y],
-                tex_coords: [left_page_x, bottom_page_y],
-            },
-            Vertex {
-                position: [right_screen_x, top_screen_y],
-                tex_coords: [right_page_x, top_page_y],
-            },
-            Vertex {
-                position: [right_screen_x, bottom_screen_y],
-                tex_coords: [right_page_x, bottom_page_y],
-            },
-        ]
-    });
-    let mut vertex_buffers = Vec::new();
-    for shape in shapes {
-        vertex_buffers.push(VertexBuffer::new(&display, &shape).unwrap());
+    let mut mesh = GlyphMesh::new(image_dimensions, bmfont.ordinate_orientation());
+    for char_position in &char_positions {
+        mesh.push_glyph(char_position);
     }
-    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+    let (glyph_vertices, glyph_indices) = mesh.into_buffers();
+    let shape: Vec<Vertex> = glyph_vertices
+        .into_iter()
+        .map(|vertex| Vertex {
+            position: [
+                vertex.position[0] / design_size.0 as f32,
+                vertex.position[1] / design_size.1 as f32,
+            ],
+            tex_coords: vertex.tex_coords,
+        })
+        .collect();
+    let vertex_buffer = VertexBuffer::new(&display, &shape).unwrap();
+    let indices = IndexBuffer::new(
+        &display,
+        glium::index::PrimitiveType::TrianglesList,
+        &glyph_indices,
+    )
+    .unwrap();
     let program = create_program(&display);
     let uniforms = uniform! { tex: &texture };
     let draw_parameters = DrawParameters {
@@ -118,17 +98,15 @@ fn main() {
         let mut target = display.draw();
         target.clear_color(0.0, 0.0, 1.0, 1.0);
 
-        for vertex_buffer in &vertex_buffers {
-            target
-                .draw(
-                    vertex_buffer,
-                    &indices,
-                    &program,
-                    &uniforms,
-                    &draw_parameters,
-                )
-                .unwrap();
-        }
+        target
+            .draw(
+                &vertex_buffer,
+                &indices,
+                &program,
+                &uniforms,
+                &draw_parameters,
+            )
+            .unwrap();
         target.finish().unwrap();
 
         for ev in display.poll_events() {
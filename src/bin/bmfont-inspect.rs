@@ -0,0 +1,140 @@
+//! Prints a summary of a bitmap font and, optionally, the metrics of some of its glyphs.
+//!
+//! Usage: `bmfont-inspect <font.fnt> [characters]`
+
+extern crate bmfont;
+
+use bmfont::{BMFont, CharPosition, OrdinateOrientation};
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (path, characters) = match args.as_slice() {
+        [path] => (path, None),
+        [path, characters] => (path, Some(characters)),
+        _ => {
+            eprintln!("Usage: bmfont-inspect <font.fnt> [characters]");
+            process::exit(2);
+        }
+    };
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", path, error);
+            process::exit(1);
+        }
+    };
+    let font = match BMFont::from_bytes(content.as_bytes(), OrdinateOrientation::TopToBottom) {
+        Ok(font) => font,
+        Err(error) => {
+            eprintln!("Cannot load {}: {}", path, error);
+            process::exit(1);
+        }
+    };
+
+    print_summary(&font, &content);
+    if let Some(characters) = characters {
+        println!();
+        for c in characters.chars() {
+            print_glyph(&font, c);
+        }
+    }
+}
+
+fn print_summary(font: &BMFont, content: &str) {
+    println!("Face: {}", face(content).unwrap_or("<unknown>"));
+    println!("Line height: {}", font.line_height());
+    println!("Base: {}", font.base_height());
+    println!("Page size: {}x{}", font.page_width(), font.page_height());
+    println!("Pages:");
+    for (index, file) in font.pages().enumerate() {
+        println!("  {}: {}", index, file);
+    }
+    println!("Glyphs: {}", font.char_ids().count());
+    let coverage = coverage_ranges(font)
+        .iter()
+        .map(|&(first, last)| {
+            if first == last {
+                format!("U+{:04X}", first)
+            } else {
+                format!("U+{:04X}-U+{:04X}", first, last)
+            }
+        })
+        .collect::<Vec<_>>();
+    println!("Coverage: {}", coverage.join(", "));
+    println!("Kerning pairs: {}", font.kerning_count());
+}
+
+/// Returns the `face` of the "info" section, which the font itself does not keep.
+fn face(content: &str) -> Option<&str> {
+    let info = content
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}'))
+        .find(|line| line.starts_with("info "))?;
+    let face = &info[info.find(" face=")? + " face=".len()..];
+    if let Some(quoted) = face.strip_prefix('"') {
+        quoted.split('"').next()
+    } else {
+        face.split_whitespace().next()
+    }
+}
+
+/// Returns the inclusive ranges of consecutive char ids.
+fn coverage_ranges(font: &BMFont) -> Vec<(u32, u32)> {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for id in font.char_ids() {
+        match ranges.last_mut() {
+            Some(range) if range.1 + 1 == id => range.1 = id,
+            _ => ranges.push((id, id)),
+        }
+    }
+    ranges
+}
+
+fn print_glyph(font: &BMFont, c: char) {
+    let text = c.to_string();
+    if !font.char_ids().any(|id| Some(id) == char_id(c)) {
+        println!("{:?}: missing", c);
+        return;
+    }
+
+    let parse = font.parse(&text);
+
+    #[cfg(feature = "parse-error")]
+    let parse = match parse {
+        Ok(parse) => parse,
+        Err(error) => {
+            println!("{:?}: {:?}", c, error);
+            return;
+        }
+    };
+
+    let char_positions = parse.collect::<Vec<CharPosition>>();
+    match char_positions.first() {
+        Some(char_position) => println!(
+            "{:?} (U+{:04X}): page {} at {}x{} size {}x{} offset {}x{} xadvance {}",
+            c,
+            c as u32,
+            char_position.page_index,
+            char_position.page_rect.x,
+            char_position.page_rect.y,
+            char_position.page_rect.width,
+            char_position.page_rect.height,
+            char_position.screen_rect.x,
+            char_position.screen_rect.y,
+            char_position.xadvance
+        ),
+        None => println!("{:?}: empty", c),
+    }
+}
+
+fn char_id(c: char) -> Option<u32> {
+    let mut buffer = [0; 2];
+    match c.encode_utf16(&mut buffer) {
+        [unit] => Some(u32::from(*unit)),
+        _ => None,
+    }
+}
@@ -0,0 +1,74 @@
+//! Converts bitmap fonts between formats, picked by the file extensions.
+//!
+//! Usage: `bmfont convert <input> <output>`
+//!
+//! Supported formats are text (`.fnt`, `.txt`) and JSON (`.json`), the serialized form of
+//! [BMFont]. The binary and XML formats are not supported yet.
+
+extern crate bmfont;
+extern crate serde_json;
+
+use bmfont::{BMFont, OrdinateOrientation};
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::process;
+
+enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    fn of(path: &str) -> Result<Format, String> {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("fnt") | Some("txt") => Ok(Format::Text),
+            Some("json") => Ok(Format::Json),
+            _ => Err(format!("Unsupported format of {}", path)),
+        }
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.as_slice() {
+        [command, input, output] if command == "convert" => convert(input, output),
+        _ => {
+            eprintln!("Usage: bmfont convert <input> <output>");
+            process::exit(2);
+        }
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn convert(input: &str, output: &str) -> Result<(), String> {
+    let input_format = Format::of(input)?;
+    let output_format = Format::of(output)?;
+
+    let content =
+        fs::read_to_string(input).map_err(|error| format!("Cannot read {}: {}", input, error))?;
+    let font = match input_format {
+        Format::Text => BMFont::from_bytes(content.as_bytes(), OrdinateOrientation::TopToBottom)
+            .map_err(|error| error.to_string()),
+        Format::Json => serde_json::from_str(&content).map_err(|error| error.to_string()),
+    }
+    .map_err(|error| format!("Cannot load {}: {}", input, error))?;
+
+    let file =
+        File::create(output).map_err(|error| format!("Cannot create {}: {}", output, error))?;
+    let writer = BufWriter::new(file);
+    match output_format {
+        Format::Text => font.write_text(writer).map_err(|error| error.to_string()),
+        Format::Json => {
+            serde_json::to_writer_pretty(writer, &font).map_err(|error| error.to_string())
+        }
+    }
+    .map_err(|error| format!("Cannot write {}: {}", output, error))
+}
@@ -0,0 +1,133 @@
+use encoding_rs::{
+    Encoding, BIG5, EUC_KR, GBK, MACINTOSH, SHIFT_JIS, WINDOWS_1250, WINDOWS_1251, WINDOWS_1252,
+    WINDOWS_1253, WINDOWS_1254, WINDOWS_1255, WINDOWS_1256, WINDOWS_1257, WINDOWS_1258,
+    WINDOWS_874,
+};
+
+use super::glyph::Glyph;
+use super::kerning_value::KerningValue;
+use super::{BMFont, CharIds, LayoutOptions, Parse};
+use std::borrow::Cow;
+
+/// Returns the encoding of the code page named by the `charset` of the "info" section, as written
+/// by BMFont for fonts exported with `unicode=0`. `SYMBOL`, `OEM` and `JOHAB` have no encoding.
+pub fn charset_encoding(charset: &str) -> Option<&'static Encoding> {
+    let encoding = match charset.to_ascii_uppercase().as_str() {
+        "ANSI" | "DEFAULT" => WINDOWS_1252,
+        "SHIFTJIS" => SHIFT_JIS,
+        "HANGUL" => EUC_KR,
+        "GB2312" => GBK,
+        "CHINESEBIG5" => BIG5,
+        "HEBREW" => WINDOWS_1255,
+        "ARABIC" => WINDOWS_1256,
+        "GREEK" => WINDOWS_1253,
+        "TURKISH" => WINDOWS_1254,
+        "VIETNAMESE" => WINDOWS_1258,
+        "THAI" => WINDOWS_874,
+        "EASTEUROPE" => WINDOWS_1250,
+        "RUSSIAN" => WINDOWS_1251,
+        "MAC" => MACINTOSH,
+        "BALTIC" => WINDOWS_1257,
+        _ => return None,
+    };
+    Some(encoding)
+}
+
+/// Returns the Unicode char id of the code page char id `char_id`, whose lead byte is in the high
+/// byte for double-byte code pages, or `None` if it does not decode to a single character of the
+/// Basic Multilingual Plane.
+pub fn decode_char_id(encoding: &'static Encoding, char_id: u32) -> Option<u32> {
+    let bytes = match char_id {
+        0..=0xFF => vec![char_id as u8],
+        0x100..=0xFFFF => vec![(char_id >> 8) as u8, char_id as u8],
+        _ => return None,
+    };
+    let decoded = encoding.decode_without_bom_handling_and_without_replacement(&bytes)?;
+    let mut chars = decoded.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => super::char_id(c),
+        _ => None,
+    }
+}
+
+/// Replaces the code page char ids of `characters` and `kerning_values` by Unicode char ids,
+/// keeping those which do not decode, and sorts them again.
+pub fn decode_char_ids(
+    encoding: &'static Encoding,
+    characters: &mut [Glyph],
+    kerning_values: &mut [KerningValue],
+) {
+    let decode = |char_id| decode_char_id(encoding, char_id).unwrap_or(char_id);
+    for char in characters.iter_mut() {
+        char.id = decode(char.id);
+    }
+    for kerning_value in kerning_values.iter_mut() {
+        kerning_value.first_char_id = decode(kerning_value.first_char_id);
+        kerning_value.second_char_id = decode(kerning_value.second_char_id);
+    }
+    characters.sort_by_key(|char| char.id);
+    kerning_values.sort_by_key(|kerning_value| kerning_value.first_char_id);
+}
+
+/// Iterator over the characters of text decoded from bytes, used by
+/// [`BMFont::parse_bytes()`](crate::BMFont::parse_bytes). It borrows the bytes when they decode to
+/// themselves, such as ASCII text.
+#[derive(Clone, Debug)]
+pub struct DecodedChars<'a> {
+    text: Cow<'a, str>,
+    idx: usize,
+}
+
+impl<'a> Iterator for DecodedChars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.text[self.idx..].chars().next()?;
+        self.idx += c.len_utf8();
+        Some(c)
+    }
+}
+
+impl BMFont {
+    /// Lays out `bytes` decoded with `encoding`, such as `encoding_rs::SHIFT_JIS` or
+    /// `encoding_rs::WINDOWS_1252` for Latin-1, with the default [LayoutOptions]. Malformed byte
+    /// sequences decode to U+FFFD REPLACEMENT CHARACTER, which is missing unless the font has it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let char_positions = font.parse_bytes(b"Rust", encoding_rs::SHIFT_JIS);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_bytes<'s>(
+        &'s self,
+        bytes: &'s [u8],
+        encoding: &'static Encoding,
+    ) -> Parse<'s, CharIds<DecodedChars<'s>>> {
+        self.parse_bytes_with(bytes, encoding, &LayoutOptions::default())
+    }
+
+    /// Lays out `bytes` decoded with `encoding` with the given [LayoutOptions], like
+    /// [BMFont::parse_with()].
+    pub fn parse_bytes_with<'s>(
+        &'s self,
+        bytes: &'s [u8],
+        encoding: &'static Encoding,
+        options: &LayoutOptions,
+    ) -> Parse<'s, CharIds<DecodedChars<'s>>> {
+        let text = encoding.decode_without_bom_handling(bytes).0;
+
+        #[cfg(feature = "parse-error")]
+        self.check_characters(text.chars(), options)?;
+
+        self.parse_char_ids(CharIds::new(DecodedChars { text, idx: 0 }), options)
+    }
+}
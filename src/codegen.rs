@@ -0,0 +1,108 @@
+//! Generation of Rust source for [StaticFont](crate::StaticFont) tables, meant to be called from
+//! build scripts.
+
+use super::{BMFont, Error, OrdinateOrientation};
+use std::fmt::Write;
+use std::io::Read;
+
+/// Loads a font from `source` and returns Rust source defining a `pub static` [StaticFont] named
+/// `name`, which [BMFont::from_static()] turns into a font without parsing or allocating.
+///
+/// # Examples
+///
+/// In `build.rs`:
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), bmfont::Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let code = bmfont::codegen::generate(file, "FONT")?;
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// std::fs::write(std::path::Path::new(&out_dir).join("font.rs"), code)?;
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// In the crate:
+///
+/// ```rust,ignore
+/// include!(concat!(env!("OUT_DIR"), "/font.rs"));
+///
+/// let font = bmfont::BMFont::from_static(&FONT, bmfont::OrdinateOrientation::TopToBottom);
+/// ```
+///
+/// [StaticFont]: crate::StaticFont
+pub fn generate<R>(source: R, name: &str) -> Result<String, Error>
+where
+    R: Read,
+{
+    let font = BMFont::new(source, OrdinateOrientation::TopToBottom)?;
+    let mut code = String::new();
+    // Writing to a `String` cannot fail.
+    let _ = write_font(&mut code, &font, name);
+    Ok(code)
+}
+
+fn write_font(code: &mut String, font: &BMFont, name: &str) -> std::fmt::Result {
+    writeln!(
+        code,
+        "pub static {}: ::bmfont::StaticFont = ::bmfont::StaticFont {{",
+        name
+    )?;
+    writeln!(code, "    base_height: {},", font.base_height)?;
+    writeln!(code, "    line_height: {},", font.line_height)?;
+    writeln!(code, "    page_width: {},", font.page_width)?;
+    writeln!(code, "    page_height: {},", font.page_height)?;
+    writeln!(code, "    size: {:?},", font.size)?;
+    match font.distance_field {
+        Some(distance_field) => writeln!(
+            code,
+            "    distance_field: Some(::bmfont::DistanceField {{ field_type: \
+             ::bmfont::DistanceFieldType::{:?}, distance_range: {} }}),",
+            distance_field.field_type, distance_field.distance_range
+        )?,
+        None => writeln!(code, "    distance_field: None,")?,
+    }
+    writeln!(code, "    characters: &[")?;
+    for char in font.characters.iter() {
+        writeln!(
+            code,
+            "        ::bmfont::Glyph {{ id: {}, x: {}, y: {}, width: {}, height: {}, \
+             xoffset: {}, yoffset: {}, xadvance: {}, page_index: {}, chnl: {}, letter: {} }},",
+            char.id,
+            char.x,
+            char.y,
+            char.width,
+            char.height,
+            char.xoffset,
+            char.yoffset,
+            char.xadvance,
+            char.page_index,
+            char.chnl,
+            match char.letter {
+                Some(ref letter) => format!("Some(::std::borrow::Cow::Borrowed({:?}))", letter),
+                None => "None".to_string(),
+            }
+        )?;
+    }
+    writeln!(code, "    ],")?;
+    writeln!(code, "    kerning_values: &[")?;
+    for kerning in font.kerning_values.iter() {
+        writeln!(
+            code,
+            "        ::bmfont::__private::KerningValue {{ first_char_id: {}, second_char_id: {}, \
+             value: {} }},",
+            kerning.first_char_id, kerning.second_char_id, kerning.value
+        )?;
+    }
+    writeln!(code, "    ],")?;
+    writeln!(code, "    pages: &[")?;
+    for page in font.pages.iter() {
+        writeln!(
+            code,
+            "        ::bmfont::__private::Page {{ id: {}, file: ::std::borrow::Cow::Borrowed({:?}) }},",
+            page.id, page.file
+        )?;
+    }
+    writeln!(code, "    ],")?;
+    writeln!(code, "}};")
+}
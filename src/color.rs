@@ -0,0 +1,35 @@
+/// Color of a laid out glyph, with straight alpha.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+
+    /// Returns an opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Returns `[r, g, b, a]` in the range from `0.0` to `1.0`, such as for vertex colors.
+    pub fn to_f32(&self) -> [f32; 4] {
+        [
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+            self.a as f32 / 255.0,
+        ]
+    }
+}
+
+impl Default for Color {
+    /// Returns [Color::WHITE], which leaves the glyphs of the page as they are when multiplied.
+    fn default() -> Self {
+        Color::WHITE
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::{Display, Error, Formatter};
+use std::str::Utf8Error;
 
 #[derive(Debug)]
 pub enum ConfigParseError {
@@ -17,6 +18,12 @@ pub enum ConfigParseError {
         component: String,
         value: String,
     },
+    DuplicateChar(u32),
+    /// The font passed to [BMFont::from_bytes()](crate::BMFont::from_bytes) is not valid UTF-8.
+    InvalidUtf8(Utf8Error),
+    /// The gzip-compressed font passed to [BMFont::from_bytes()](crate::BMFont::from_bytes) does
+    /// not decompress to UTF-8 text.
+    InvalidGzip(std::io::Error),
 }
 
 impl Display for ConfigParseError {
@@ -52,6 +59,13 @@ impl Display for ConfigParseError {
                 "Invalid component value = {} for component = {} in section = {}",
                 value, component, section
             ),
+            ConfigParseError::DuplicateChar(id) => write!(formatter, "Duplicate char = {}", id),
+            ConfigParseError::InvalidUtf8(ref error) => {
+                write!(formatter, "Invalid UTF-8. {}", error)
+            }
+            ConfigParseError::InvalidGzip(ref error) => {
+                write!(formatter, "Invalid gzip. {}", error)
+            }
         }
     }
 }
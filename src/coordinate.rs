@@ -0,0 +1,33 @@
+/// Number type coordinates of laid out glyphs are returned in by
+/// [`CharPosition::screen_bounds()`](crate::CharPosition::screen_bounds), so that code drawing
+/// glyphs can be written once for both pixel fonts and scaled or distance field fonts.
+///
+/// `i32` keeps the exact whole pixels of [`CharPosition::screen_rect`](crate::CharPosition::screen_rect),
+/// while `f32` adds the [`CharPosition::subpixel`](crate::CharPosition::subpixel) offset of
+/// [`Positioning::Fractional`](crate::Positioning::Fractional).
+///
+/// Only the conversion of the output is generic. Every layout function still returns
+/// [`CharPosition`](crate::CharPosition)s in whole pixels with the fraction of a pixel kept in
+/// 256ths, so positions are exact to 1/256 of a pixel whatever `T` they are read as.
+pub trait Coordinate: Copy {
+    /// Returns the coordinate `whole` pixels and `subpixel` 256ths of a pixel from the origin.
+    fn from_pixels(whole: i32, subpixel: u8) -> Self;
+}
+
+impl Coordinate for i32 {
+    fn from_pixels(whole: i32, _: u8) -> Self {
+        whole
+    }
+}
+
+impl Coordinate for f32 {
+    fn from_pixels(whole: i32, subpixel: u8) -> Self {
+        whole as f32 + subpixel as f32 / 256.0
+    }
+}
+
+impl Coordinate for f64 {
+    fn from_pixels(whole: i32, subpixel: u8) -> Self {
+        whole as f64 + subpixel as f64 / 256.0
+    }
+}
@@ -0,0 +1,161 @@
+use super::OrdinateOrientation;
+
+/// Direction in which x positions grow.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AbscissaOrientation {
+    #[default]
+    LeftToRight,
+    /// x positions grow to the left, so the rect of a glyph extends from its `x` to the left.
+    RightToLeft,
+}
+
+/// Point of a line which the y positions of its glyphs are measured from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineOrigin {
+    /// The top of the line box.
+    Top,
+    /// The baseline, [`BMFont::base_height()`](crate::BMFont::base_height) below the top.
+    Baseline,
+    /// The bottom of the line box,
+    /// [`BMFont::line_height()`](crate::BMFont::line_height) below the top.
+    Bottom,
+}
+
+/// Coordinate system glyphs are laid out in, see
+/// [`LayoutOptions::coordinate_system`](crate::LayoutOptions::coordinate_system).
+///
+/// The pen, and so [`Layout::pen()`](crate::Layout::pen) and
+/// [`LineMetrics`](crate::LineMetrics), always measures the advance along the line from the
+/// origin of the first line, so only the rects of the glyphs depend on `abscissa_orientation` and
+/// `line_origin`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let options = LayoutOptions {
+///     coordinate_system: Some(CoordinateSystem {
+///         ordinate_orientation: OrdinateOrientation::BottomToTop,
+///         abscissa_orientation: AbscissaOrientation::LeftToRight,
+///         line_origin: LineOrigin::Top,
+///     }),
+///     ..Default::default()
+/// };
+/// let char_positions = font.parse_with("Rust", &options);
+/// # #[cfg(feature = "parse-error")]
+/// # let char_positions = char_positions.unwrap();
+/// // The glyphs hang below the top of the line.
+/// assert!(char_positions.into_iter().all(|p| p.screen_rect.max_y() <= 0));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoordinateSystem {
+    pub ordinate_orientation: OrdinateOrientation,
+    pub abscissa_orientation: AbscissaOrientation,
+    pub line_origin: LineOrigin,
+}
+
+impl CoordinateSystem {
+    /// Returns the system measuring the y positions of glyphs from the baseline of their line, as
+    /// most typographic code and shaders of font atlases expect. Glyphs of fonts with different
+    /// line heights laid out this way line up on the same baseline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let options = LayoutOptions {
+    ///     coordinate_system: Some(CoordinateSystem::baseline(OrdinateOrientation::TopToBottom)),
+    ///     ..Default::default()
+    /// };
+    /// let char_positions = font.parse_with("_", &options);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// // The underscore lies below the baseline.
+    /// assert!(char_positions.into_iter().all(|p| p.screen_rect.y > 0));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn baseline(ordinate_orientation: OrdinateOrientation) -> Self {
+        CoordinateSystem {
+            ordinate_orientation,
+            abscissa_orientation: AbscissaOrientation::LeftToRight,
+            line_origin: LineOrigin::Baseline,
+        }
+    }
+
+    /// Returns how far the rects of glyphs laid out with the lines measured from their top if
+    /// y grows downwards, or from their baseline if y grows upwards, move in this system.
+    pub(crate) fn glyph_dy(&self, base_height: i32, line_height: i32) -> i32 {
+        match (self.ordinate_orientation, self.line_origin) {
+            (OrdinateOrientation::TopToBottom, LineOrigin::Top) => 0,
+            (OrdinateOrientation::TopToBottom, LineOrigin::Baseline) => -base_height,
+            (OrdinateOrientation::TopToBottom, LineOrigin::Bottom) => -line_height,
+            (OrdinateOrientation::BottomToTop, LineOrigin::Top) => -base_height,
+            (OrdinateOrientation::BottomToTop, LineOrigin::Baseline) => 0,
+            (OrdinateOrientation::BottomToTop, LineOrigin::Bottom) => line_height - base_height,
+        }
+    }
+
+    /// Returns the minimum x of a glyph spanning from `start` to `end` along the line, given as
+    /// whole pixels and the fraction of a pixel above them in 256ths, saturating at the bounds of
+    /// `i32`.
+    pub(crate) fn glyph_subpixel_x(&self, start: (i32, u8), end: (i32, u8)) -> (i32, u8) {
+        match (self.abscissa_orientation, end) {
+            (AbscissaOrientation::LeftToRight, _) => start,
+            (AbscissaOrientation::RightToLeft, (x, 0)) => (x.saturating_neg(), 0),
+            (AbscissaOrientation::RightToLeft, (x, fraction)) => (
+                x.saturating_neg().saturating_sub(1),
+                (256 - fraction as u16) as u8,
+            ),
+        }
+    }
+
+    /// Returns the minimum x of a glyph spanning from `x` to `x + width` pixels along the line,
+    /// saturating at the bounds of `i32`.
+    pub(crate) fn glyph_pixel_x(&self, x: i32, width: u32) -> i32 {
+        match self.abscissa_orientation {
+            AbscissaOrientation::LeftToRight => x,
+            AbscissaOrientation::RightToLeft => x.saturating_add(width as i32).saturating_neg(),
+        }
+    }
+
+    /// Returns `dx` along the line in this system.
+    pub(crate) fn dx(&self, dx: i32) -> i32 {
+        match self.abscissa_orientation {
+            AbscissaOrientation::LeftToRight => dx,
+            AbscissaOrientation::RightToLeft => dx.saturating_neg(),
+        }
+    }
+}
+
+impl From<OrdinateOrientation> for CoordinateSystem {
+    /// Returns the system fonts loaded with `ordinate_orientation` lay out text in, which measures
+    /// from the top of the lines if y grows downwards and from their baseline if y grows upwards.
+    fn from(ordinate_orientation: OrdinateOrientation) -> Self {
+        CoordinateSystem {
+            ordinate_orientation,
+            abscissa_orientation: AbscissaOrientation::LeftToRight,
+            line_origin: match ordinate_orientation {
+                OrdinateOrientation::TopToBottom => LineOrigin::Top,
+                OrdinateOrientation::BottomToTop => LineOrigin::Baseline,
+            },
+        }
+    }
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        CoordinateSystem::from(OrdinateOrientation::default())
+    }
+}
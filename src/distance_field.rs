@@ -0,0 +1,46 @@
+/// Kind of distance field the glyphs of a font are drawn as.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DistanceFieldType {
+    /// Single-channel signed distance field.
+    Sdf,
+    /// Single-channel pseudo signed distance field.
+    Psdf,
+    /// Multi-channel signed distance field.
+    Msdf,
+    /// Multi-channel signed distance field with a true signed distance field in the alpha channel.
+    Mtsdf,
+}
+
+impl DistanceFieldType {
+    /// Returns the type named `name` as by msdf-atlas-gen and msdf-bmfont-xml, e.g. `"msdf"`.
+    pub(crate) fn from_name(name: &str) -> Option<DistanceFieldType> {
+        match name {
+            "sdf" => Some(DistanceFieldType::Sdf),
+            "psdf" => Some(DistanceFieldType::Psdf),
+            "msdf" => Some(DistanceFieldType::Msdf),
+            "mtsdf" => Some(DistanceFieldType::Mtsdf),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            DistanceFieldType::Sdf => "sdf",
+            DistanceFieldType::Psdf => "psdf",
+            DistanceFieldType::Msdf => "msdf",
+            DistanceFieldType::Mtsdf => "mtsdf",
+        }
+    }
+}
+
+/// Distance field the glyphs of a font are drawn as, given by the "distanceField" section, which
+/// renderers need to turn distances back into coverage.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceField {
+    pub field_type: DistanceFieldType,
+    /// Range of distances encoded in the pages, in pixels, from fully outside to fully inside a
+    /// glyph.
+    pub distance_range: u32,
+}
@@ -0,0 +1,191 @@
+use super::generate::{kerning, rasterize, ShelfPacker};
+use super::{BMFont, GenerateError, GenerateOptions, KerningValue, PageImage, Rect};
+use ab_glyph::FontArc;
+
+/// Area of a page changed by [`DynamicAtlas::insert_missing()`], which has to be uploaded to the
+/// texture of the page again.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DirtyRegion {
+    pub page_index: u32,
+    pub rect: Rect,
+}
+
+/// Bitmap font paired with a vector font, which rasterizes the glyphs the bitmap font lacks into
+/// the spare region of its last page at runtime, e.g. for user-generated text.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let font_data = std::fs::read("font.ttf")?;
+/// let options = GenerateOptions::default();
+/// let generated = BMFont::generate(&font_data, "A", "font", &options)?;
+/// let mut atlas = DynamicAtlas::new(generated.font, generated.pages, font_data, options)?;
+/// assert!(!atlas.font().supports("AV"));
+/// let dirty_region = atlas.insert_missing("AV")?.unwrap();
+/// assert!(atlas.font().supports("AV"));
+/// assert_eq!(dirty_region.page_index, 0);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DynamicAtlas {
+    font: BMFont,
+    pages: Vec<PageImage>,
+    vector_font: FontArc,
+    options: GenerateOptions,
+    packer: ShelfPacker,
+}
+
+impl DynamicAtlas {
+    /// Pairs `font`, whose page images are `pages`, with the TrueType or OpenType font
+    /// `vector_font_data`. Missing glyphs are rasterized with `options` and packed on new shelves
+    /// below the glyphs on the last page, whose size is taken from the font.
+    pub fn new(
+        font: BMFont,
+        pages: Vec<PageImage>,
+        vector_font_data: Vec<u8>,
+        options: GenerateOptions,
+    ) -> Result<DynamicAtlas, GenerateError> {
+        let vector_font =
+            FontArc::try_from_vec(vector_font_data).map_err(|_| GenerateError::InvalidFont)?;
+        let options = GenerateOptions {
+            page_width: font.page_width,
+            page_height: font.page_height,
+            ..options
+        };
+        let last_page_index = pages.len().saturating_sub(1) as u32;
+        let used_height = font
+            .characters
+            .iter()
+            .filter(|c| c.page_index == last_page_index && c.width != 0 && c.height != 0)
+            .map(|c| c.y + c.height)
+            .max()
+            .unwrap_or(0);
+        Ok(DynamicAtlas {
+            font,
+            pages,
+            vector_font,
+            options,
+            packer: ShelfPacker::below(options.padding, used_height),
+        })
+    }
+
+    pub fn font(&self) -> &BMFont {
+        &self.font
+    }
+
+    /// Returns the images of the pages, in the order of their ids.
+    pub fn pages(&self) -> &[PageImage] {
+        &self.pages
+    }
+
+    /// Returns the font and the images of its pages.
+    pub fn into_parts(self) -> (BMFont, Vec<PageImage>) {
+        (self.font, self.pages)
+    }
+
+    /// Rasterizes the glyphs of the characters of `s` which the font lacks but the vector font
+    /// has, draws them on the last page and adds them, with their kerning, to the font.
+    ///
+    /// Returns the region of the last page which changed, or `None` if nothing did. If the glyphs
+    /// do not all fit, returns [`GenerateError::AtlasFull`] and leaves the font and its pages
+    /// unchanged.
+    pub fn insert_missing(&mut self, s: &str) -> Result<Option<DirtyRegion>, GenerateError> {
+        let mut chars: Vec<char> = s
+            .chars()
+            .filter(|&c| c.len_utf16() == 1 && !self.font.has_glyph(c))
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let page_index = self.pages.len().saturating_sub(1) as u32;
+        let mut packer = self.packer;
+        let mut rasters = Vec::with_capacity(chars.len());
+        for c in chars {
+            let mut raster = match rasterize(&self.vector_font, c, &self.options) {
+                Some(raster) => raster,
+                None => continue,
+            };
+            if raster.char.width != 0 && raster.char.height != 0 {
+                let (x, y) = match packer.place(&raster.char, &self.options) {
+                    Some(position) if !self.pages.is_empty() => position,
+                    _ => return Err(GenerateError::AtlasFull(c)),
+                };
+                raster.char.x = x;
+                raster.char.y = y;
+                raster.char.page_index = page_index;
+            }
+            rasters.push(raster);
+        }
+        self.packer = packer;
+
+        let mut dirty_rect = Rect::default();
+        for raster in &rasters {
+            let char = &raster.char;
+            if let Some(page) = self.pages.last_mut() {
+                if char.width != 0 && char.height != 0 {
+                    raster.blit(page);
+                    let rect = Rect {
+                        x: char.x as i32,
+                        y: char.y as i32,
+                        width: char.width,
+                        height: char.height,
+                    };
+                    dirty_rect = dirty_rect.union(&rect);
+                }
+            }
+            self.font.fingerprint.reset();
+            let characters = self.font.characters.to_mut();
+            if let Err(idx) = characters.binary_search_by(|probe| probe.id.cmp(&char.id)) {
+                characters.insert(idx, char.clone());
+            }
+        }
+
+        // Kern the new glyphs with every glyph, and the old glyphs with the new ones.
+        let chars: Vec<char> = self
+            .font
+            .characters
+            .iter()
+            .filter_map(|c| std::char::from_u32(c.id))
+            .collect();
+        for raster in &rasters {
+            for &other in &chars {
+                let is_new = rasters.iter().any(|r| r.c == other);
+                let mut pairs = vec![(raster.c, other)];
+                if !is_new {
+                    pairs.push((other, raster.c));
+                }
+                for (first, second) in pairs {
+                    if let Some(kerning) = kerning(&self.vector_font, first, second, &self.options)
+                    {
+                        self.insert_kerning(kerning);
+                    }
+                }
+            }
+        }
+
+        Ok(if dirty_rect.is_empty() {
+            None
+        } else {
+            Some(DirtyRegion {
+                page_index,
+                rect: dirty_rect,
+            })
+        })
+    }
+
+    fn insert_kerning(&mut self, kerning: KerningValue) {
+        if let Some(kerning_map) = self.font.kerning_map.as_mut() {
+            kerning_map
+                .entry((kerning.first_char_id, kerning.second_char_id))
+                .or_insert(kerning.value);
+        }
+        self.font.fingerprint.reset();
+        let kerning_values = self.font.kerning_values.to_mut();
+        let idx =
+            kerning_values.partition_point(|probe| probe.first_char_id <= kerning.first_char_id);
+        kerning_values.insert(idx, kerning);
+    }
+}
@@ -0,0 +1,12 @@
+/// Where [`BMFont::truncate_to_width()`](crate::BMFont::truncate_to_width) cuts text which is
+/// too wide and puts the ellipsis.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum EllipsisPosition {
+    /// Keep the start of the text, e.g. `"long_na…"`.
+    #[default]
+    End,
+    /// Keep the end of the text, e.g. `"…name.txt"`, for file names.
+    Start,
+    /// Keep both ends of the text, e.g. `"long…name"`, for paths and URLs.
+    Middle,
+}
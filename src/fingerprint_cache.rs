@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// [`BMFont::fingerprint()`](crate::BMFont::fingerprint) of a font, computed once when it is first
+/// asked for and kept until the font changes, so that looking it up doesn't hash every glyph and
+/// kerning pair again. `0` stands for a fingerprint which is not computed yet.
+#[derive(Debug, Default)]
+pub(crate) struct FingerprintCache(AtomicU64);
+
+impl FingerprintCache {
+    /// Returns the kept fingerprint, computing it with `compute` if there is none.
+    pub fn get_or_compute<F>(&self, compute: F) -> u64
+    where
+        F: FnOnce() -> u64,
+    {
+        match self.0.load(Ordering::Relaxed) {
+            0 => {
+                let fingerprint = compute();
+                self.0.store(fingerprint, Ordering::Relaxed);
+                fingerprint
+            }
+            fingerprint => fingerprint,
+        }
+    }
+
+    /// Forgets the kept fingerprint, for a font which changed.
+    pub fn reset(&mut self) {
+        *self.0.get_mut() = 0;
+    }
+}
+
+impl Clone for FingerprintCache {
+    fn clone(&self) -> Self {
+        FingerprintCache(AtomicU64::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+/// Fonts are told apart by their content, of which the fingerprint is only a hash.
+impl PartialEq for FingerprintCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for FingerprintCache {}
@@ -0,0 +1,142 @@
+use super::typesetter::{Cursor, Typesetter};
+use super::{BMFont, CharPosition, LayoutOptions, TextRun};
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<FittedText, StringParseError>`] _or_ [FittedText], returned by
+/// [`BMFont::fit_to_box()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type FitToBox = Result<FittedText, StringParseError>;
+
+/// Alias of either [`Result<FittedText, StringParseError>`] _or_ [FittedText], returned by
+/// [`BMFont::fit_to_box()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type FitToBox = FittedText;
+
+/// Text scaled down to fit a box by [`BMFont::fit_to_box()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FittedText {
+    /// Factor by which the glyphs, their advances and the line height are scaled, at most `1.0`.
+    pub scale: f32,
+    pub char_positions: Vec<CharPosition>,
+}
+
+/// Number of halvings of the range of scales searched by [`BMFont::fit_to_box()`].
+const FIT_ITERATIONS: u32 = 16;
+
+impl BMFont {
+    /// Lays out `s` at the largest scale up to `1.0` at which its widest line and all of its line
+    /// heights fit within `width` and `height`, e.g. for player names over avatars. Lines are only
+    /// broken at line breaks.
+    ///
+    /// The scale is found by a binary search, since rounding the scaled glyphs to whole pixels
+    /// makes the size of the text only roughly proportional to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let fitted_text = font.fit_to_box("You", &LayoutOptions::default(), 200, 40);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let fitted_text = fitted_text.unwrap();
+    /// assert!((0.5..0.51).contains(&fitted_text.scale));
+    /// assert_eq!(fitted_text.char_positions.len(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn fit_to_box(
+        &self,
+        s: &str,
+        options: &LayoutOptions,
+        width: u32,
+        height: u32,
+    ) -> FitToBox {
+        #[cfg(feature = "parse-error")]
+        self.check_characters(s.chars(), options)?;
+
+        let fits =
+            |(text_width, text_height): (u32, u32)| text_width <= width && text_height <= height;
+        let (char_positions, size) = self.lay_out_scaled(s, options, 1.0);
+        let fitted_text = if fits(size) {
+            FittedText {
+                scale: 1.0,
+                char_positions,
+            }
+        } else {
+            let (mut fitting, mut too_large) = (0.0, 1.0);
+            let mut fitting_char_positions = None;
+            for _ in 0..FIT_ITERATIONS {
+                let scale = (fitting + too_large) / 2.0;
+                let (char_positions, size) = self.lay_out_scaled(s, options, scale);
+                if fits(size) {
+                    fitting = scale;
+                    fitting_char_positions = Some(char_positions);
+                } else {
+                    too_large = scale;
+                }
+            }
+            FittedText {
+                scale: fitting,
+                char_positions: fitting_char_positions
+                    .unwrap_or_else(|| self.lay_out_scaled(s, options, fitting).0),
+            }
+        };
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(fitted_text)
+        }
+
+        #[cfg(not(feature = "parse-error"))]
+        {
+            fitted_text
+        }
+    }
+
+    /// Lays out `s` at `scale` and returns its glyphs together with the width of its widest line
+    /// and the sum of its line heights.
+    fn lay_out_scaled(
+        &self,
+        s: &str,
+        options: &LayoutOptions,
+        scale: f32,
+    ) -> (Vec<CharPosition>, (u32, u32)) {
+        let mut char_positions = Vec::new();
+        let mut lines = Vec::new();
+        let mut typesetter = Typesetter::new(
+            Some(self),
+            options,
+            None,
+            &mut char_positions,
+            Cursor::default(),
+        );
+        typesetter.scale_lines(scale);
+        typesetter.record_lines(&mut lines);
+        typesetter.push_run(
+            0,
+            &TextRun {
+                scale,
+                ..TextRun::new(s, self)
+            },
+        );
+        let line_height = typesetter.line_height();
+        let last_line = typesetter.cursor().line(typesetter.glyph_count());
+        lines.push(last_line);
+
+        let width = lines.iter().map(|line| line.width.max(0) as u32).max();
+        let height = lines.len() as u32 * line_height;
+        (char_positions, (width.unwrap_or(0), height))
+    }
+}
@@ -0,0 +1,174 @@
+use super::{BMFont, Error, LoadOptions, OrdinateOrientation};
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Error as FmtError, Formatter};
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::thread;
+
+/// Fonts loaded by name, e.g. one per language of a localized game.
+///
+/// [`FontCollection::load()`] parses the fonts concurrently, so loading many of them at startup
+/// takes about as long as loading the largest one.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # use std::fs::File;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sources = vec![("en", File::open("font.fnt")?), ("de", File::open("font.fnt")?)];
+/// let options = LoadOptions::default();
+/// let fonts = FontCollection::load(sources, OrdinateOrientation::TopToBottom, &options)?;
+/// assert_eq!(fonts.len(), 2);
+/// assert_eq!(fonts.get("de").unwrap().line_height(), 80);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FontCollection {
+    fonts: HashMap<String, BMFont>,
+}
+
+impl FontCollection {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads each of `sources`, a name with the source of a font, like [BMFont::with_options()]
+    /// but on several threads at once.
+    ///
+    /// If names repeat, the font of the last source with the name is kept.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the errors of all sources which could not be loaded, together with the fonts
+    /// which could.
+    pub fn load<I, N, R>(
+        sources: I,
+        ordinate_orientation: OrdinateOrientation,
+        options: &LoadOptions,
+    ) -> Result<FontCollection, FontCollectionError>
+    where
+        I: IntoIterator<Item = (N, R)>,
+        N: Into<String>,
+        R: Read + Send,
+    {
+        let sources: Vec<(String, R)> = sources
+            .into_iter()
+            .map(|(name, source)| (name.into(), source))
+            .collect();
+        let source_count = sources.len();
+        let thread_count = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(source_count);
+        let queue = Mutex::new(sources.into_iter().enumerate());
+        let results = Mutex::new(Vec::with_capacity(source_count));
+        thread::scope(|scope| {
+            for _ in 0..thread_count {
+                scope.spawn(|| loop {
+                    let next = queue.lock().unwrap().next();
+                    let (index, (name, source)) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+                    let result = BMFont::with_options(source, ordinate_orientation, options);
+                    results.lock().unwrap().push((index, name, result));
+                });
+            }
+        });
+
+        // The threads finish in any order, so the sources are put back in theirs.
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|&(index, _, _)| index);
+        let mut fonts = FontCollection::new();
+        let mut errors = Vec::new();
+        for (_, name, result) in results {
+            match result {
+                Ok(font) => {
+                    fonts.insert(name, font);
+                }
+                Err(error) => errors.push((name, error)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(fonts)
+        } else {
+            Err(FontCollectionError {
+                errors,
+                loaded: fonts,
+            })
+        }
+    }
+
+    /// Returns the font named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&BMFont> {
+        self.fonts.get(name)
+    }
+
+    /// Returns `true` if the collection has a font named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fonts.contains_key(name)
+    }
+
+    /// Adds `font` named `name`, returning the font it replaces, if any.
+    pub fn insert<N>(&mut self, name: N, font: BMFont) -> Option<BMFont>
+    where
+        N: Into<String>,
+    {
+        self.fonts.insert(name.into(), font)
+    }
+
+    /// Removes and returns the font named `name`, if any.
+    pub fn remove(&mut self, name: &str) -> Option<BMFont> {
+        self.fonts.remove(name)
+    }
+
+    /// Returns an iterator over the names and fonts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BMFont)> + '_ {
+        self.fonts.iter().map(|(name, font)| (name.as_str(), font))
+    }
+
+    /// Returns the number of fonts.
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Returns `true` if the collection has no fonts.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    /// Converts the collection into a map of name to font.
+    pub fn into_map(self) -> HashMap<String, BMFont> {
+        self.fonts
+    }
+}
+
+/// Error returned by [`FontCollection::load()`] if any of the fonts could not be loaded.
+#[derive(Debug)]
+pub struct FontCollectionError {
+    /// Names of the sources which could not be loaded with their errors, in the order of the
+    /// sources.
+    pub errors: Vec<(String, Error)>,
+    /// The fonts which were loaded.
+    pub loaded: FontCollection,
+}
+
+impl Display for FontCollectionError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), FmtError> {
+        write!(formatter, "Failed to load fonts.")?;
+        for (name, error) in &self.errors {
+            write!(formatter, " {}: {}.", name, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for FontCollectionError {
+    fn description(&self) -> &str {
+        "BMFont collection loading error"
+    }
+}
@@ -0,0 +1,57 @@
+use super::BMFont;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A cheaply clonable, thread safe handle to a shared [BMFont].
+///
+/// It dereferences to the font, so the whole [BMFont] API, including
+/// [`BMFont::parse()`](crate::BMFont::parse), is available on it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = FontHandle::new(BMFont::new(file, OrdinateOrientation::TopToBottom)?);
+/// let thread_font = font.clone();
+/// let count = std::thread::spawn(move || {
+///     let char_positions = thread_font.parse("Rust");
+///     # #[cfg(feature = "parse-error")]
+///     # let char_positions = char_positions.unwrap();
+///     char_positions.count()
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(count, 4);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FontHandle(Arc<BMFont>);
+
+impl FontHandle {
+    pub fn new(font: BMFont) -> Self {
+        FontHandle(Arc::new(font))
+    }
+}
+
+impl Deref for FontHandle {
+    type Target = BMFont;
+
+    fn deref(&self) -> &BMFont {
+        &self.0
+    }
+}
+
+impl From<BMFont> for FontHandle {
+    fn from(font: BMFont) -> Self {
+        FontHandle::new(font)
+    }
+}
+
+impl From<Arc<BMFont>> for FontHandle {
+    fn from(font: Arc<BMFont>) -> Self {
+        FontHandle(font)
+    }
+}
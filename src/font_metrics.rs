@@ -0,0 +1,30 @@
+/// Vertical metrics of a [`BMFont`](crate::BMFont), returned by
+/// [`BMFont::metrics()`](crate::BMFont::metrics), in pixels.
+///
+/// BMFont files only give the line height and the base, so the descent is estimated as the part
+/// of the line below the base, and the line gap, which the line height already includes, as `0`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FontMetrics {
+    /// Distance from the top of a line to the baseline.
+    pub ascent: u32,
+    /// Distance from the baseline to the bottom of a line.
+    pub descent: u32,
+    pub line_gap: u32,
+    /// Distance between the baselines of consecutive lines.
+    pub line_height: u32,
+    /// Size of the font the glyphs were rendered from, if the "info" section gives it.
+    pub size: Option<u32>,
+}
+
+/// Alias of [FontMetrics], as vertical metrics are all that BMFont files give, returned by
+/// [`BMFont::vertical_metrics()`](crate::BMFont::vertical_metrics) with the descent measured from
+/// the glyphs.
+pub type VerticalMetrics = FontMetrics;
+
+impl FontMetrics {
+    /// Returns the height of an em, the size of the font the glyphs were rendered from, or the
+    /// line height, which is usually a little larger, if the size is unknown.
+    pub fn em_height(&self) -> u32 {
+        self.size.unwrap_or(self.line_height)
+    }
+}
@@ -0,0 +1,138 @@
+use super::{FontCollection, FontHandle};
+use std::collections::HashMap;
+
+/// Fonts registered by name, usually a family and a style such as `"ui-bold"`, which layout code
+/// looks up with [`FontRegistry::resolve()`], falling back to other fonts if a name is missing.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let mut registry = FontRegistry::new();
+/// registry.register("ui-regular", BMFont::new(file, OrdinateOrientation::TopToBottom)?);
+/// registry.set_fallback("ui-bold", "ui-regular");
+/// assert!(registry.get("ui-bold").is_none());
+/// assert_eq!(registry.resolve("ui-bold").unwrap().line_height(), 80);
+/// assert!(registry.resolve("title").is_none());
+/// registry.set_default("ui-regular");
+/// assert!(registry.resolve("title").is_some());
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FontRegistry {
+    fonts: HashMap<String, FontHandle>,
+    fallbacks: HashMap<String, String>,
+    default: Option<String>,
+}
+
+impl FontRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `font` as `name`, returning the font it replaces, if any.
+    pub fn register<N, F>(&mut self, name: N, font: F) -> Option<FontHandle>
+    where
+        N: Into<String>,
+        F: Into<FontHandle>,
+    {
+        self.fonts.insert(name.into(), font.into())
+    }
+
+    /// Unregisters and returns the font named `name`, if any. Fallbacks to it are kept.
+    pub fn unregister(&mut self, name: &str) -> Option<FontHandle> {
+        self.fonts.remove(name)
+    }
+
+    /// Makes [`FontRegistry::resolve()`] look up `fallback` if no font is registered as `name`,
+    /// returning the fallback it replaces, if any.
+    pub fn set_fallback<N, F>(&mut self, name: N, fallback: F) -> Option<String>
+    where
+        N: Into<String>,
+        F: Into<String>,
+    {
+        self.fallbacks.insert(name.into(), fallback.into())
+    }
+
+    /// Removes and returns the fallback of `name`, if any.
+    pub fn remove_fallback(&mut self, name: &str) -> Option<String> {
+        self.fallbacks.remove(name)
+    }
+
+    /// Makes [`FontRegistry::resolve()`] look up `name` if neither a requested font nor any of its
+    /// fallbacks is registered.
+    pub fn set_default<N>(&mut self, name: N)
+    where
+        N: Into<String>,
+    {
+        self.default = Some(name.into());
+    }
+
+    /// Returns the name of the default font, if any.
+    pub fn default_name(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Returns the font registered as exactly `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&FontHandle> {
+        self.fonts.get(name)
+    }
+
+    /// Returns the font registered as `name`, otherwise the first registered font of its chain of
+    /// fallbacks, otherwise the default font, if any.
+    ///
+    /// A chain of fallbacks looping back on itself ends where it loops.
+    pub fn resolve(&self, name: &str) -> Option<&FontHandle> {
+        let mut name = name;
+        // Each step follows a different fallback unless the chain loops.
+        for _ in 0..=self.fallbacks.len() {
+            if let Some(font) = self.fonts.get(name) {
+                return Some(font);
+            }
+            match self.fallbacks.get(name) {
+                Some(fallback) => name = fallback,
+                None => break,
+            }
+        }
+        self.default
+            .as_ref()
+            .and_then(|default| self.fonts.get(default))
+    }
+
+    /// Returns `true` if a font is registered as `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fonts.contains_key(name)
+    }
+
+    /// Returns an iterator over the names and fonts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FontHandle)> + '_ {
+        self.fonts.iter().map(|(name, font)| (name.as_str(), font))
+    }
+
+    /// Returns the number of registered fonts.
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Returns `true` if no fonts are registered.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+}
+
+impl From<FontCollection> for FontRegistry {
+    fn from(collection: FontCollection) -> Self {
+        FontRegistry {
+            fonts: collection
+                .into_map()
+                .into_iter()
+                .map(|(name, font)| (name, font.into()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
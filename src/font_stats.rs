@@ -0,0 +1,200 @@
+use super::BMFont;
+use std::ops::RangeInclusive;
+
+/// Common Unicode blocks reported by [`BMFont::stats()`], sorted by their first character.
+const BLOCKS: &[(u32, u32, &str)] = &[
+    (0x0000, 0x007F, "Basic Latin"),
+    (0x0080, 0x00FF, "Latin-1 Supplement"),
+    (0x0100, 0x017F, "Latin Extended-A"),
+    (0x0180, 0x024F, "Latin Extended-B"),
+    (0x0250, 0x02AF, "IPA Extensions"),
+    (0x02B0, 0x02FF, "Spacing Modifier Letters"),
+    (0x0300, 0x036F, "Combining Diacritical Marks"),
+    (0x0370, 0x03FF, "Greek and Coptic"),
+    (0x0400, 0x04FF, "Cyrillic"),
+    (0x0500, 0x052F, "Cyrillic Supplement"),
+    (0x0530, 0x058F, "Armenian"),
+    (0x0590, 0x05FF, "Hebrew"),
+    (0x0600, 0x06FF, "Arabic"),
+    (0x0700, 0x074F, "Syriac"),
+    (0x0750, 0x077F, "Arabic Supplement"),
+    (0x0780, 0x07BF, "Thaana"),
+    (0x0900, 0x097F, "Devanagari"),
+    (0x0980, 0x09FF, "Bengali"),
+    (0x0A00, 0x0A7F, "Gurmukhi"),
+    (0x0A80, 0x0AFF, "Gujarati"),
+    (0x0B00, 0x0B7F, "Oriya"),
+    (0x0B80, 0x0BFF, "Tamil"),
+    (0x0C00, 0x0C7F, "Telugu"),
+    (0x0C80, 0x0CFF, "Kannada"),
+    (0x0D00, 0x0D7F, "Malayalam"),
+    (0x0D80, 0x0DFF, "Sinhala"),
+    (0x0E00, 0x0E7F, "Thai"),
+    (0x0E80, 0x0EFF, "Lao"),
+    (0x0F00, 0x0FFF, "Tibetan"),
+    (0x1000, 0x109F, "Myanmar"),
+    (0x10A0, 0x10FF, "Georgian"),
+    (0x1100, 0x11FF, "Hangul Jamo"),
+    (0x1200, 0x137F, "Ethiopic"),
+    (0x13A0, 0x13FF, "Cherokee"),
+    (0x1780, 0x17FF, "Khmer"),
+    (0x1800, 0x18AF, "Mongolian"),
+    (0x1E00, 0x1EFF, "Latin Extended Additional"),
+    (0x1F00, 0x1FFF, "Greek Extended"),
+    (0x2000, 0x206F, "General Punctuation"),
+    (0x2070, 0x209F, "Superscripts and Subscripts"),
+    (0x20A0, 0x20CF, "Currency Symbols"),
+    (0x2100, 0x214F, "Letterlike Symbols"),
+    (0x2150, 0x218F, "Number Forms"),
+    (0x2190, 0x21FF, "Arrows"),
+    (0x2200, 0x22FF, "Mathematical Operators"),
+    (0x2300, 0x23FF, "Miscellaneous Technical"),
+    (0x2460, 0x24FF, "Enclosed Alphanumerics"),
+    (0x2500, 0x257F, "Box Drawing"),
+    (0x2580, 0x259F, "Block Elements"),
+    (0x25A0, 0x25FF, "Geometric Shapes"),
+    (0x2600, 0x26FF, "Miscellaneous Symbols"),
+    (0x2700, 0x27BF, "Dingbats"),
+    (0x2C60, 0x2C7F, "Latin Extended-C"),
+    (0x2E80, 0x2EFF, "CJK Radicals Supplement"),
+    (0x3000, 0x303F, "CJK Symbols and Punctuation"),
+    (0x3040, 0x309F, "Hiragana"),
+    (0x30A0, 0x30FF, "Katakana"),
+    (0x3100, 0x312F, "Bopomofo"),
+    (0x3130, 0x318F, "Hangul Compatibility Jamo"),
+    (0x31F0, 0x31FF, "Katakana Phonetic Extensions"),
+    (0x3200, 0x32FF, "Enclosed CJK Letters and Months"),
+    (0x3300, 0x33FF, "CJK Compatibility"),
+    (0x3400, 0x4DBF, "CJK Unified Ideographs Extension A"),
+    (0x4E00, 0x9FFF, "CJK Unified Ideographs"),
+    (0xA640, 0xA69F, "Cyrillic Extended-B"),
+    (0xA720, 0xA7FF, "Latin Extended-D"),
+    (0xAC00, 0xD7AF, "Hangul Syllables"),
+    (0xE000, 0xF8FF, "Private Use Area"),
+    (0xF900, 0xFAFF, "CJK Compatibility Ideographs"),
+    (0xFB00, 0xFB4F, "Alphabetic Presentation Forms"),
+    (0xFB50, 0xFDFF, "Arabic Presentation Forms-A"),
+    (0xFE30, 0xFE4F, "CJK Compatibility Forms"),
+    (0xFE70, 0xFEFF, "Arabic Presentation Forms-B"),
+    (0xFF00, 0xFFEF, "Halfwidth and Fullwidth Forms"),
+    (0xFFF0, 0xFFFF, "Specials"),
+    (0x1F300, 0x1F5FF, "Miscellaneous Symbols and Pictographs"),
+    (0x1F600, 0x1F64F, "Emoticons"),
+    (0x1F680, 0x1F6FF, "Transport and Map Symbols"),
+    (0x1F900, 0x1F9FF, "Supplemental Symbols and Pictographs"),
+    (0x20000, 0x2A6DF, "CJK Unified Ideographs Extension B"),
+];
+
+/// A Unicode block with glyphs in a font, see [`FontStats::blocks`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BlockCoverage {
+    pub name: &'static str,
+    pub range: RangeInclusive<char>,
+    /// Number of characters of the block the font has glyphs for.
+    pub glyph_count: usize,
+}
+
+impl BlockCoverage {
+    /// Returns the fraction of the code points of the block the font has glyphs for.
+    pub fn coverage(&self) -> f32 {
+        let len = *self.range.end() as u32 - *self.range.start() as u32 + 1;
+        self.glyph_count as f32 / len as f32
+    }
+}
+
+/// Statistics of a font returned by [`BMFont::stats()`], e.g. for build pipelines flagging
+/// bloated fonts or fonts lacking characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontStats {
+    pub glyph_count: usize,
+    pub kerning_count: usize,
+    pub page_count: usize,
+    /// Runs of consecutive characters the font has glyphs for.
+    pub char_ranges: Vec<RangeInclusive<char>>,
+    /// Common Unicode blocks with at least one glyph, in code point order. Characters outside of
+    /// them only show up in [`FontStats::char_ranges`].
+    pub blocks: Vec<BlockCoverage>,
+    /// Fraction of the area of all pages covered by glyphs.
+    pub fill_ratio: f32,
+    /// Smallest width and height of the glyphs with an area, which need not be the same glyph.
+    pub min_glyph_size: Option<(u32, u32)>,
+    /// Largest width and height of the glyphs with an area, which need not be the same glyph.
+    pub max_glyph_size: Option<(u32, u32)>,
+}
+
+impl BMFont {
+    /// Returns statistics about the glyphs, kerning and pages of the font.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let stats = font.stats();
+    /// assert_eq!(stats.glyph_count, 81);
+    /// assert_eq!(stats.blocks[0].name, "Basic Latin");
+    /// assert!(stats.fill_ratio > 0.0 && stats.fill_ratio < 1.0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn stats(&self) -> FontStats {
+        let mut char_ranges: Vec<RangeInclusive<char>> = Vec::new();
+        let mut blocks: Vec<BlockCoverage> = Vec::new();
+        let mut glyph_area = 0u64;
+        let mut min_glyph_size: Option<(u32, u32)> = None;
+        let mut max_glyph_size: Option<(u32, u32)> = None;
+        for char in self.characters.iter() {
+            glyph_area += char.width as u64 * char.height as u64;
+            if char.width > 0 && char.height > 0 {
+                let (width, height) = min_glyph_size.unwrap_or((char.width, char.height));
+                min_glyph_size = Some((width.min(char.width), height.min(char.height)));
+                let (width, height) = max_glyph_size.unwrap_or((char.width, char.height));
+                max_glyph_size = Some((width.max(char.width), height.max(char.height)));
+            }
+
+            // The characters are sorted by id, so runs and blocks are built in order.
+            let c = match std::char::from_u32(char.id) {
+                Some(c) => c,
+                None => continue,
+            };
+            match char_ranges.last_mut() {
+                Some(range) if *range.end() as u32 + 1 == char.id => {
+                    *range = *range.start()..=c;
+                }
+                _ => char_ranges.push(c..=c),
+            }
+            let block = BLOCKS
+                .iter()
+                .find(|&&(start, end, _)| (start..=end).contains(&char.id));
+            if let Some(&(start, end, name)) = block {
+                match blocks.last_mut() {
+                    Some(coverage) if coverage.name == name => coverage.glyph_count += 1,
+                    _ => blocks.push(BlockCoverage {
+                        name,
+                        // The bounds of all blocks are valid characters.
+                        range: std::char::from_u32(start).unwrap()
+                            ..=std::char::from_u32(end).unwrap(),
+                        glyph_count: 1,
+                    }),
+                }
+            }
+        }
+
+        let page_area = self.page_width as u64 * self.page_height as u64 * self.pages.len() as u64;
+        FontStats {
+            glyph_count: self.characters.len(),
+            kerning_count: self.kerning_count(),
+            page_count: self.pages.len(),
+            char_ranges,
+            blocks,
+            fill_ratio: match page_area {
+                0 => 0.0,
+                _ => (glyph_area as f64 / page_area as f64) as f32,
+            },
+            min_glyph_size,
+            max_glyph_size,
+        }
+    }
+}
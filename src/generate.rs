@@ -0,0 +1,356 @@
+use super::{
+    BMFont, DistanceField, DistanceFieldType, Glyph, KerningValue, OrdinateOrientation, Page,
+};
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::fmt::{Display, Error as FmtError, Formatter};
+
+/// Options controlling how [`BMFont::generate()`] rasterizes a vector font.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GenerateOptions {
+    /// Height of an em in pixels.
+    pub size: u32,
+    pub page_width: u32,
+    pub page_height: u32,
+    /// Pixels left empty around every glyph on the pages, so that filtering does not bleed
+    /// neighbouring glyphs in.
+    pub padding: u32,
+    pub ordinate_orientation: OrdinateOrientation,
+    /// Draw the glyphs as single-channel signed distance fields reaching this many pixels past
+    /// their outlines instead of as coverage, e.g. to scale them smoothly in a shader.
+    pub sdf_spread: Option<u32>,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            size: 32,
+            page_width: 256,
+            page_height: 256,
+            padding: 1,
+            ordinate_orientation: OrdinateOrientation::default(),
+            sdf_spread: None,
+        }
+    }
+}
+
+/// Rasterized page of a generated font, with one byte of coverage, or distance with
+/// [`GenerateOptions::sdf_spread`], per pixel, row by row from the top.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PageImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Font returned by [`BMFont::generate()`] together with the images of its pages.
+#[derive(Clone, Debug)]
+pub struct GeneratedFont {
+    pub font: BMFont,
+    /// Images of the pages, in the order of their ids.
+    pub pages: Vec<PageImage>,
+    /// Characters which were requested but lack a glyph in the vector font.
+    pub missing_chars: Vec<char>,
+}
+
+#[derive(Debug)]
+pub enum GenerateError {
+    /// The data is not a TrueType or OpenType font.
+    InvalidFont,
+    /// The glyph of the character does not fit on a page with its padding.
+    GlyphTooLarge(char),
+    /// The rest of the last page has no room for the glyph of the character, see
+    /// [`DynamicAtlas::insert_missing()`](crate::DynamicAtlas::insert_missing).
+    AtlasFull(char),
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), FmtError> {
+        match *self {
+            GenerateError::InvalidFont => write!(formatter, "Invalid vector font"),
+            GenerateError::GlyphTooLarge(c) => {
+                write!(formatter, "Glyph of char = {:?} does not fit on a page", c)
+            }
+            GenerateError::AtlasFull(c) => {
+                write!(formatter, "No room left on the pages for char = {:?}", c)
+            }
+        }
+    }
+}
+
+impl StdError for GenerateError {}
+
+impl PageImage {
+    /// Returns an empty page.
+    pub(crate) fn new(width: u32, height: u32) -> PageImage {
+        PageImage {
+            width,
+            height,
+            pixels: vec![0; (width * height) as usize],
+        }
+    }
+}
+
+impl BMFont {
+    /// Rasterizes the glyphs of `chars` from the TrueType or OpenType font `font_data` with
+    /// ab_glyph, packs them onto pages named `{page_name}_{id}.png` and returns the resulting
+    /// font together with the page images, e.g. to generate bitmap fonts in build scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let font_data = std::fs::read("font.ttf")?;
+    /// let generated = BMFont::generate(&font_data, "AV", "font", &GenerateOptions::default())?;
+    /// assert_eq!(generated.font.pages().collect::<Vec<_>>(), ["font_0.png"]);
+    /// assert_eq!(generated.pages[0].pixels.len(), 256 * 256);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn generate(
+        font_data: &[u8],
+        chars: &str,
+        page_name: &str,
+        options: &GenerateOptions,
+    ) -> Result<GeneratedFont, GenerateError> {
+        let font = FontRef::try_from_slice(font_data).map_err(|_| GenerateError::InvalidFont)?;
+        let scaled_font = font.as_scaled(PxScale::from(options.size as f32));
+        let base_height = scaled_font.ascent().round() as i32;
+        let line_height = (scaled_font.ascent() - scaled_font.descent() + scaled_font.line_gap())
+            .round()
+            .max(0.0) as u32;
+
+        let mut chars: Vec<char> = chars.chars().filter(|&c| c.len_utf16() == 1).collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut rasters = Vec::with_capacity(chars.len());
+        let mut missing_chars = Vec::new();
+        for &c in &chars {
+            match rasterize(&font, c, options) {
+                Some(raster) => rasters.push(raster),
+                None => missing_chars.push(c),
+            }
+        }
+
+        // Pack the glyphs onto shelves, tallest first, starting a new page when one is full.
+        let mut order: Vec<usize> = (0..rasters.len()).collect();
+        order.sort_by_key(|&idx| std::cmp::Reverse(rasters[idx].char.height));
+        let mut pages: Vec<PageImage> = Vec::new();
+        let mut packer = ShelfPacker::new(options.padding);
+        for idx in order {
+            let raster = &mut rasters[idx];
+            if raster.char.width == 0 || raster.char.height == 0 {
+                continue;
+            }
+            let position = match packer.place(&raster.char, options) {
+                Some(position) if !pages.is_empty() => position,
+                _ => {
+                    pages.push(PageImage::new(options.page_width, options.page_height));
+                    packer = ShelfPacker::new(options.padding);
+                    packer
+                        .place(&raster.char, options)
+                        .ok_or(GenerateError::GlyphTooLarge(raster.c))?
+                }
+            };
+            raster.char.x = position.0;
+            raster.char.y = position.1;
+            raster.char.page_index = (pages.len() - 1) as u32;
+            raster.blit(pages.last_mut().unwrap());
+        }
+
+        let mut kerning_values = Vec::new();
+        for first in &rasters {
+            for second in &rasters {
+                if let Some(kerning) = kerning(&font, first.c, second.c, options) {
+                    kerning_values.push(kerning);
+                }
+            }
+        }
+
+        let page_count = pages.len().max(1);
+        let font = BMFont {
+            base_height: base_height.max(0) as u32,
+            line_height,
+            page_width: options.page_width,
+            page_height: options.page_height,
+            size: Some(options.size),
+            distance_field: options.sdf_spread.map(|spread| DistanceField {
+                field_type: DistanceFieldType::Sdf,
+                distance_range: 2 * spread,
+            }),
+            characters: Cow::Owned(rasters.into_iter().map(|raster| raster.char).collect()),
+            kerning_values: Cow::Owned(kerning_values),
+            kerning_map: None,
+            pages: Cow::Owned(
+                (0..page_count)
+                    .map(|id| Page {
+                        id: id as u32,
+                        file: Cow::Owned(format!("{}_{}.png", page_name, id)),
+                    })
+                    .collect(),
+            ),
+            ordinate_orientation: options.ordinate_orientation,
+            truncated: false,
+            fingerprint: Default::default(),
+        };
+        Ok(GeneratedFont {
+            font,
+            pages,
+            missing_chars,
+        })
+    }
+}
+
+/// Glyph rasterized but not necessarily placed on a page yet.
+pub(crate) struct Raster {
+    pub(crate) c: char,
+    pub(crate) char: Glyph,
+    coverage: Vec<u8>,
+}
+
+impl Raster {
+    /// Copies the glyph onto the page at its position.
+    pub(crate) fn blit(&self, page: &mut PageImage) {
+        let (x, y, width) = (self.char.x, self.char.y, self.char.width);
+        for row in 0..self.char.height {
+            let start = ((y + row) * page.width + x) as usize;
+            let source = (row * width) as usize;
+            page.pixels[start..start + width as usize]
+                .copy_from_slice(&self.coverage[source..source + width as usize]);
+        }
+    }
+}
+
+/// Rasterizes the glyph of `c`, returning `None` if the font lacks it.
+pub(crate) fn rasterize<F: Font>(font: &F, c: char, options: &GenerateOptions) -> Option<Raster> {
+    let scale = PxScale::from(options.size as f32);
+    let scaled_font = font.as_scaled(scale);
+    let base_height = scaled_font.ascent().round() as i32;
+    let glyph_id = font.glyph_id(c);
+    if glyph_id.0 == 0 {
+        return None;
+    }
+    let mut char = Glyph::space(scaled_font.h_advance(glyph_id).round() as i32);
+    char.id = c as u32;
+    let mut coverage = Vec::new();
+    let glyph = glyph_id.with_scale_and_position(scale, point(0.0, 0.0));
+    if let Some(outlined) = font.outline_glyph(glyph) {
+        let bounds = outlined.px_bounds();
+        char.width = bounds.width() as u32;
+        char.height = bounds.height() as u32;
+        char.xoffset = bounds.min.x as i32;
+        char.yoffset = base_height + bounds.min.y as i32;
+        coverage = vec![0; (char.width * char.height) as usize];
+        outlined.draw(|x, y, value| {
+            if x < char.width && y < char.height {
+                coverage[(y * char.width + x) as usize] = (value * 255.0).round() as u8;
+            }
+        });
+        if let Some(spread) = options.sdf_spread {
+            coverage = signed_distance_field(&coverage, char.width, char.height, spread);
+            char.width += 2 * spread;
+            char.height += 2 * spread;
+            char.xoffset -= spread as i32;
+            char.yoffset -= spread as i32;
+        }
+    }
+    Some(Raster { c, char, coverage })
+}
+
+/// Returns the kerning between the glyphs of `first` and `second`, if any.
+pub(crate) fn kerning<F: Font>(
+    font: &F,
+    first: char,
+    second: char,
+    options: &GenerateOptions,
+) -> Option<KerningValue> {
+    let scaled_font = font.as_scaled(PxScale::from(options.size as f32));
+    let value = scaled_font
+        .kern(font.glyph_id(first), font.glyph_id(second))
+        .round() as i32;
+    if value == 0 {
+        return None;
+    }
+    Some(KerningValue {
+        first_char_id: first as u32,
+        second_char_id: second as u32,
+        value,
+    })
+}
+
+/// Places glyphs on a page in rows, or shelves, as high as their tallest glyph.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ShelfPacker {
+    x: u32,
+    y: u32,
+    shelf_height: u32,
+}
+
+impl ShelfPacker {
+    /// Returns a packer starting at the top of an empty page.
+    pub(crate) fn new(padding: u32) -> ShelfPacker {
+        ShelfPacker::below(padding, 0)
+    }
+
+    /// Returns a packer starting on a new shelf below `y`, e.g. below the glyphs already on a page.
+    pub(crate) fn below(padding: u32, y: u32) -> ShelfPacker {
+        ShelfPacker {
+            x: padding,
+            y: y + padding,
+            shelf_height: 0,
+        }
+    }
+
+    /// Returns the position for the glyph, or `None` if the rest of the page has no room for it.
+    pub(crate) fn place(&mut self, char: &Glyph, options: &GenerateOptions) -> Option<(u32, u32)> {
+        let padding = options.padding;
+        if self.x + char.width + padding > options.page_width {
+            self.x = padding;
+            self.y += self.shelf_height + padding;
+            self.shelf_height = 0;
+        }
+        if self.x + char.width + padding > options.page_width
+            || self.y + char.height + padding > options.page_height
+        {
+            return None;
+        }
+        let position = (self.x, self.y);
+        self.x += char.width + padding;
+        self.shelf_height = self.shelf_height.max(char.height);
+        Some(position)
+    }
+}
+
+/// Converts the coverage of a `width` by `height` glyph into a signed distance field grown by
+/// `spread` pixels on every side. Distances from the outline of up to `spread` pixels map to
+/// `0..=255`, with `128` on the outline and larger values inside.
+fn signed_distance_field(coverage: &[u8], width: u32, height: u32, spread: u32) -> Vec<u8> {
+    let spread = spread as i32;
+    let (width, height) = (width as i32, height as i32);
+    let is_inside = |x: i32, y: i32| {
+        x >= 0 && y >= 0 && x < width && y < height && coverage[(y * width + x) as usize] >= 128
+    };
+    let mut field = Vec::with_capacity(((width + 2 * spread) * (height + 2 * spread)) as usize);
+    for y in -spread..height + spread {
+        for x in -spread..width + spread {
+            let inside = is_inside(x, y);
+            // Find the nearest pixel on the other side of the outline, which lies half way.
+            let mut distance = spread.max(1) as f32;
+            for dy in -spread..=spread {
+                for dx in -spread..=spread {
+                    if is_inside(x + dx, y + dy) != inside {
+                        let to_outline = ((dx * dx + dy * dy) as f32).sqrt() - 0.5;
+                        distance = distance.min(to_outline);
+                    }
+                }
+            }
+            let signed_distance = if inside { distance } else { -distance };
+            let value = 128.0 + signed_distance * 127.0 / spread.max(1) as f32;
+            field.push(value.round().clamp(0.0, 255.0) as u8);
+        }
+    }
+    field
+}
@@ -0,0 +1,126 @@
+use super::load_options::Quirks;
+use super::{utils, ConfigParseError};
+use std::borrow::Cow;
+
+const SECTION_NAME: &str = "char";
+
+/// Value of `chnl` for glyphs drawn in all channels of the page.
+const ALL_CHANNELS: u32 = 15;
+
+/// A glyph of a font as described by its `char` line, returned by [`BMFont::glyph()`] and
+/// [`BMFont::glyphs()`] for code doing its own layout or processing the font.
+///
+/// [`BMFont::glyph()`]: crate::BMFont::glyph
+/// [`BMFont::glyphs()`]: crate::BMFont::glyphs
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Glyph {
+    /// Unicode code point of the character, unless the font was exported with a `charset`.
+    pub id: u32,
+    /// Left of the glyph on its page, in pixels.
+    pub x: u32,
+    /// Top of the glyph on its page, in pixels.
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Distance from the pen to the left of the glyph on the screen.
+    pub xoffset: i32,
+    /// Distance from the top of the line to the top of the glyph on the screen.
+    pub yoffset: i32,
+    /// Distance the pen advances by after the glyph, before kerning.
+    pub xadvance: i32,
+    /// Index of the page the glyph is drawn on.
+    pub page_index: u32,
+    /// Bitmask of the channels of the page the glyph is drawn in: 1 for blue, 2 for green, 4 for
+    /// red, 8 for alpha and 15 for all of them.
+    #[cfg_attr(feature = "serde", serde(default = "all_channels"))]
+    pub chnl: u32,
+    /// The `letter` field some exporters write, kept as a sanity check of `id`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub letter: Option<Cow<'static, str>>,
+}
+
+impl Glyph {
+    /// Parses a `char` line of the text format.
+    pub fn new(s: &str) -> Result<Glyph, ConfigParseError> {
+        Glyph::with_quirks(s, Quirks::default())
+    }
+
+    pub(crate) fn with_quirks(s: &str, quirks: Quirks) -> Result<Glyph, ConfigParseError> {
+        let components = utils::section_components(s, SECTION_NAME, quirks)?;
+        let id: u32 = components.value("id")?;
+        let x: u32 = components.value("x")?;
+        let y: u32 = components.value("y")?;
+        let width: u32 = components.value("width")?;
+        let height: u32 = components.value("height")?;
+        let xoffset: i32 = components.value("xoffset")?;
+        let yoffset: i32 = components.value("yoffset")?;
+        let xadvance: i32 = components.value("xadvance")?;
+        // Minimal exporters omit the page of single page fonts.
+        let page_index: u32 = components.value_or("page", 0)?;
+        let chnl: u32 = components.value_or("chnl", ALL_CHANNELS)?;
+        let letter: Option<String> = components.optional_value("letter")?;
+        let letter = letter.map(|letter| Cow::Owned(utils::unquote(&letter).to_string()));
+        Ok(Glyph {
+            id,
+            x,
+            y,
+            width,
+            height,
+            xoffset,
+            yoffset,
+            xadvance,
+            page_index,
+            chnl,
+            letter,
+        })
+    }
+
+    /// Returns an empty glyph of the space advancing by `xadvance`, for fonts which lack it.
+    pub fn space(xadvance: i32) -> Glyph {
+        Glyph {
+            id: 32,
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            xoffset: 0,
+            yoffset: 0,
+            xadvance,
+            page_index: 0,
+            chnl: ALL_CHANNELS,
+            letter: None,
+        }
+    }
+
+    /// Returns the bitmask of the channels the glyph is drawn in. Some exporters write 0 for
+    /// glyphs drawn in all channels.
+    pub(crate) fn channels(&self) -> u32 {
+        match self.chnl {
+            0 => ALL_CHANNELS,
+            chnl => chnl,
+        }
+    }
+
+    /// Returns `false` if the `letter` field names a different character than `id`.
+    pub fn letter_matches_id(&self) -> bool {
+        let letter = match self.letter.as_deref() {
+            Some("space") => " ",
+            Some(letter) => letter,
+            None => return true,
+        };
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c as u32 == self.id,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn all_channels() -> u32 {
+    ALL_CHANNELS
+}
@@ -0,0 +1,176 @@
+use super::{BMFont, CharPosition, GlyphSink, OrdinateOrientation, PageOrigin};
+
+/// A corner of a glyph quad built by [GlyphMesh].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlyphVertex {
+    /// Screen position in pixels, including the [`CharPosition::subpixel`] offset.
+    pub position: [f32; 2],
+    /// Texture coordinates in the page, normalized to `0.0..=1.0` and measured from the corner
+    /// the page rectangles are measured from, see [`GlyphMesh::with_page_origin()`].
+    pub tex_coords: [f32; 2],
+}
+
+/// Indices of the two triangles of a quad into its four vertices, as returned by
+/// [`GlyphMesh::quad()`].
+pub const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+/// Builds an indexed triangle list from laid out glyphs, four vertices and six indices per glyph,
+/// rather than six duplicated vertices.
+///
+/// It is a [GlyphSink], so glyphs can be laid out straight into it with
+/// [`BMFont::parse_into()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let mut mesh = GlyphMesh::for_font(&font);
+/// let result = font.parse_into("Rust", &LayoutOptions::default(), &mut mesh);
+/// # #[cfg(feature = "parse-error")]
+/// # result.unwrap();
+/// assert_eq!(mesh.vertices().len(), 4 * 4);
+/// assert_eq!(mesh.indices().len(), 4 * 6);
+/// assert_eq!(mesh.indices()[6..12], [4, 5, 6, 4, 6, 7]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphMesh {
+    page_size: (u32, u32),
+    ordinate_orientation: OrdinateOrientation,
+    page_origin: PageOrigin,
+    vertices: Vec<GlyphVertex>,
+    indices: Vec<u32>,
+}
+
+impl GlyphMesh {
+    /// Creates an empty mesh for glyphs on pages of `page_size` pixels, laid out with
+    /// `ordinate_orientation`, which decides which screen edge the top of the page maps to.
+    pub fn new(page_size: (u32, u32), ordinate_orientation: OrdinateOrientation) -> Self {
+        GlyphMesh {
+            page_size,
+            ordinate_orientation,
+            page_origin: PageOrigin::TopLeft,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Creates an empty mesh for glyphs laid out by `font` with its own ordinate orientation.
+    pub fn for_font(font: &BMFont) -> Self {
+        GlyphMesh::new(
+            (font.page_width(), font.page_height()),
+            font.ordinate_orientation(),
+        )
+    }
+
+    /// Makes the mesh read the page rectangles of the glyphs as measured from `page_origin`, which
+    /// must be the [`LayoutOptions::page_origin`](crate::LayoutOptions::page_origin) they were laid
+    /// out with. The texture coordinates are then measured from the same corner.
+    pub fn with_page_origin(mut self, page_origin: PageOrigin) -> Self {
+        self.page_origin = page_origin;
+        self
+    }
+
+    /// Returns the four vertices of the quad of `char_position`, going from the minimum screen
+    /// corner along the x axis first like [`Rect::corners()`](crate::Rect::corners), to be drawn
+    /// with [QUAD_INDICES].
+    pub fn quad(&self, char_position: &CharPosition) -> [GlyphVertex; 4] {
+        let [x, y, width, height] = char_position.screen_bounds::<f32>();
+        let page_width = self.page_size.0.max(1) as f32;
+        let page_height = self.page_size.1.max(1) as f32;
+        let page_rect = char_position.page_rect;
+        let left = page_rect.x as f32 / page_width;
+        let right = page_rect.max_x() as f32 / page_width;
+        let (min_y, max_y) = (
+            page_rect.y as f32 / page_height,
+            page_rect.max_y() as f32 / page_height,
+        );
+        // The top row of the glyph lies at the maximum y only if the page is measured upwards.
+        let (top, bottom) = match self.page_origin {
+            PageOrigin::TopLeft => (min_y, max_y),
+            PageOrigin::BottomLeft => (max_y, min_y),
+        };
+        // The top of the page lies at the minimum screen y only if y grows downwards.
+        let (min_v, max_v) = match self.ordinate_orientation {
+            OrdinateOrientation::TopToBottom => (top, bottom),
+            OrdinateOrientation::BottomToTop => (bottom, top),
+        };
+        [
+            GlyphVertex {
+                position: [x, y],
+                tex_coords: [left, min_v],
+            },
+            GlyphVertex {
+                position: [x + width, y],
+                tex_coords: [right, min_v],
+            },
+            GlyphVertex {
+                position: [x + width, y + height],
+                tex_coords: [right, max_v],
+            },
+            GlyphVertex {
+                position: [x, y + height],
+                tex_coords: [left, max_v],
+            },
+        ]
+    }
+
+    /// Appends the quad of `char_position`.
+    pub fn push_glyph(&mut self, char_position: &CharPosition) {
+        let base = self.vertices.len() as u32;
+        let quad = self.quad(char_position);
+        self.vertices.extend_from_slice(&quad);
+        self.indices
+            .extend(QUAD_INDICES.iter().map(|index| base + index));
+    }
+
+    /// Returns the vertices, four per glyph.
+    pub fn vertices(&self) -> &[GlyphVertex] {
+        &self.vertices
+    }
+
+    /// Returns the indices into [`GlyphMesh::vertices()`], six per glyph.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the number of glyphs in the mesh.
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    /// Returns `true` if the mesh has no glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Removes all glyphs, keeping the allocated buffers.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Returns the vertices with the indices resolved, six per glyph, for renderers without index
+    /// buffers.
+    pub fn to_triangle_list(&self) -> Vec<GlyphVertex> {
+        self.indices
+            .iter()
+            .map(|&index| self.vertices[index as usize])
+            .collect()
+    }
+
+    /// Splits the mesh into its vertices and indices.
+    pub fn into_buffers(self) -> (Vec<GlyphVertex>, Vec<u32>) {
+        (self.vertices, self.indices)
+    }
+}
+
+impl GlyphSink for GlyphMesh {
+    fn push(&mut self, char_position: CharPosition) {
+        self.push_glyph(&char_position);
+    }
+}
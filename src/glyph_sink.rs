@@ -0,0 +1,45 @@
+use super::CharPosition;
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`BMFont::parse_into()`](crate::BMFont::parse_into).
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type ParseInto = Result<(), StringParseError>;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`BMFont::parse_into()`](crate::BMFont::parse_into).
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type ParseInto = ();
+
+/// Receives the glyphs laid out by [`BMFont::parse_into()`](crate::BMFont::parse_into), e.g. to
+/// write vertices straight into a vertex buffer without collecting the glyphs first.
+///
+/// It is implemented for `Vec<CharPosition>` and for closures taking a [CharPosition].
+pub trait GlyphSink {
+    fn push(&mut self, char_position: CharPosition);
+}
+
+impl GlyphSink for Vec<CharPosition> {
+    fn push(&mut self, char_position: CharPosition) {
+        Vec::push(self, char_position);
+    }
+}
+
+impl<F> GlyphSink for F
+where
+    F: FnMut(CharPosition),
+{
+    fn push(&mut self, char_position: CharPosition) {
+        self(char_position);
+    }
+}
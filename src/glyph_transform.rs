@@ -0,0 +1,51 @@
+use super::Rect;
+
+/// Offset and scale applied to the screen rectangle of a glyph by
+/// [`ParseIter::transformed()`](crate::ParseIter::transformed), e.g. for wavy or shaky text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphTransform {
+    /// Distance the glyph moves along the x and y axes.
+    pub offset: (i32, i32),
+    /// Factor the glyph is scaled by around its center.
+    pub scale: f32,
+}
+
+impl GlyphTransform {
+    /// Returns the transform moving glyphs by `dx` and `dy`.
+    pub fn offset(dx: i32, dy: i32) -> GlyphTransform {
+        GlyphTransform {
+            offset: (dx, dy),
+            ..GlyphTransform::default()
+        }
+    }
+
+    /// Returns `rect` scaled around its center and then moved by the offset. The edges are
+    /// rounded to the nearest pixel.
+    pub fn apply(&self, rect: Rect) -> Rect {
+        let (center_x, center_y) = (
+            rect.x as f32 + rect.width as f32 / 2.0,
+            rect.y as f32 + rect.height as f32 / 2.0,
+        );
+        let (half_width, half_height) = (
+            rect.width as f32 * self.scale / 2.0,
+            rect.height as f32 * self.scale / 2.0,
+        );
+        Rect::from_edges(
+            center_x - half_width,
+            center_y - half_height,
+            center_x + half_width,
+            center_y + half_height,
+        )
+        .translate(self.offset.0, self.offset.1)
+    }
+}
+
+impl Default for GlyphTransform {
+    /// Returns the transform leaving glyphs unchanged.
+    fn default() -> Self {
+        GlyphTransform {
+            offset: (0, 0),
+            scale: 1.0,
+        }
+    }
+}
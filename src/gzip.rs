@@ -0,0 +1,31 @@
+use flate2::read::GzDecoder;
+use std::io::{Error, ErrorKind, Read, Result};
+
+/// First bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Reads `source` into a string, decompressing it first if it is gzip-compressed.
+pub fn read_to_string<R>(mut source: R) -> Result<String>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    source.read_to_end(&mut bytes)?;
+    match decompress(&bytes) {
+        Some(content) => content,
+        None => String::from_utf8(bytes).map_err(|error| Error::new(ErrorKind::InvalidData, error)),
+    }
+}
+
+/// Decompresses `bytes` into a string if they are gzip-compressed, returning `None` otherwise.
+pub fn decompress(bytes: &[u8]) -> Option<Result<String>> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return None;
+    }
+    let mut content = String::new();
+    Some(
+        GzDecoder::new(bytes)
+            .read_to_string(&mut content)
+            .map(|_| content),
+    )
+}
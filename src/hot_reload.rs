@@ -0,0 +1,139 @@
+use super::{BMFont, Error, FontHandle, LoadOptions, OrdinateOrientation};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs::File;
+use std::io::Error as IOError;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+/// A font reloaded by [`FontWatcher::poll()`].
+#[derive(Clone, Debug)]
+pub struct FontReload {
+    /// [`BMFont::fingerprint()`] of the replaced font, e.g. for
+    /// [`LayoutCache::invalidate()`](crate::LayoutCache::invalidate).
+    pub previous_fingerprint: u64,
+    pub font: FontHandle,
+}
+
+/// Keeps a font loaded from a file up to date with it, reloading it whenever the file changes,
+/// e.g. while tweaking fonts during development.
+///
+/// The font is only swapped by [`FontWatcher::poll()`], usually called once a frame, so a frame
+/// lays out all of its text with the same font.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let options = LoadOptions::default();
+/// let mut watcher = FontWatcher::new("font.fnt", OrdinateOrientation::TopToBottom, &options)?;
+/// let mut cache = LayoutCache::new(64);
+/// if let Some(Ok(reload)) = watcher.poll() {
+///     cache.invalidate(reload.previous_fingerprint);
+/// }
+/// assert_eq!(watcher.font().line_height(), 80);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FontWatcher {
+    path: PathBuf,
+    ordinate_orientation: OrdinateOrientation,
+    options: LoadOptions,
+    font: FontHandle,
+    events: Receiver<notify::Result<Event>>,
+    // Watching stops once the watcher is dropped.
+    _watcher: RecommendedWatcher,
+}
+
+impl FontWatcher {
+    /// Loads the font at `path` like [BMFont::with_options()] and starts watching the file.
+    pub fn new<P>(
+        path: P,
+        ordinate_orientation: OrdinateOrientation,
+        options: &LoadOptions,
+    ) -> Result<FontWatcher, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let font = BMFont::with_options(File::open(&path)?, ordinate_orientation, options)?;
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+        // Editors often replace a file instead of writing to it, which ends watching the file
+        // itself, so its directory is watched instead.
+        let directory = match path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+        watcher
+            .watch(directory, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+        Ok(FontWatcher {
+            path,
+            ordinate_orientation,
+            options: *options,
+            font: FontHandle::new(font),
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the current font.
+    pub fn font(&self) -> FontHandle {
+        self.font.clone()
+    }
+
+    /// Returns the path of the watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reloads the font if its file changed since the last call, returning the new font, or
+    /// `None` if the file did not change.
+    ///
+    /// If the file cannot be loaded, e.g. because it is still being written, the error is
+    /// returned and the current font is kept until the file changes again.
+    pub fn poll(&mut self) -> Option<Result<FontReload, Error>> {
+        let file_name = self.path.file_name();
+        let mut changed = false;
+        for event in self.events.try_iter() {
+            let event = match event {
+                Ok(event) => event,
+                Err(error) => return Some(Err(watch_error(error))),
+            };
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                && event.paths.iter().any(|path| path.file_name() == file_name)
+            {
+                changed = true;
+            }
+        }
+        if changed {
+            Some(self.reload())
+        } else {
+            None
+        }
+    }
+
+    /// Reloads the font from its file regardless of whether it changed.
+    pub fn reload(&mut self) -> Result<FontReload, Error> {
+        let font = BMFont::with_options(
+            File::open(&self.path)?,
+            self.ordinate_orientation,
+            &self.options,
+        )?;
+        let previous_fingerprint = self.font.fingerprint();
+        self.font = FontHandle::new(font);
+        Ok(FontReload {
+            previous_fingerprint,
+            font: self.font.clone(),
+        })
+    }
+}
+
+fn watch_error(error: notify::Error) -> Error {
+    match error.kind {
+        notify::ErrorKind::Io(error) => Error::IOError(error),
+        _ => Error::IOError(IOError::other(error)),
+    }
+}
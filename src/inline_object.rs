@@ -0,0 +1,10 @@
+/// Object, such as an icon, laid out inside text by [`TextRun::object()`](crate::TextRun::object).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct InlineObject {
+    pub width: u32,
+    pub height: u32,
+    /// Distance the pen advances past the object, usually a little more than its width.
+    pub advance: i32,
+    /// Value chosen by the caller to tell which object to draw, e.g. an index into a sprite sheet.
+    pub tag: u64,
+}
@@ -1,6 +1,5 @@
+use super::load_options::Quirks;
 use super::{utils, ConfigParseError};
-use std::fmt::Debug;
-use std::str::FromStr;
 
 const SECTION_NAME: &str = "kerning";
 
@@ -15,15 +14,14 @@ pub struct KerningValue {
 
 impl KerningValue {
     pub fn new(s: &str) -> Result<KerningValue, ConfigParseError> {
-        let mut components = s.split_whitespace();
-        let section_name = components.next();
-        assert_eq!(
-            section_name.expect("Kerning value cannot be created from empty string"),
-            SECTION_NAME
-        );
-        let first_char_id: u32 = extract_component_value(components.next(), "first")?;
-        let second_char_id: u32 = extract_component_value(components.next(), "second")?;
-        let value: i32 = extract_component_value(components.next(), "amount")?;
+        KerningValue::with_quirks(s, Quirks::default())
+    }
+
+    pub(crate) fn with_quirks(s: &str, quirks: Quirks) -> Result<KerningValue, ConfigParseError> {
+        let components = utils::section_components(s, SECTION_NAME, quirks)?;
+        let first_char_id: u32 = components.value("first")?;
+        let second_char_id: u32 = components.value("second")?;
+        let value: i32 = components.value("amount")?;
         Ok(KerningValue {
             first_char_id,
             second_char_id,
@@ -31,11 +29,3 @@ impl KerningValue {
         })
     }
 }
-
-fn extract_component_value<T>(s: Option<&str>, component: &str) -> Result<T, ConfigParseError>
-where
-    T: FromStr,
-    T::Err: Debug,
-{
-    utils::extract_component_value(s, SECTION_NAME, component)
-}
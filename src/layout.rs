@@ -0,0 +1,673 @@
+use super::line_break::{LineBreak, LineBreaks};
+use super::typesetter::{Cursor, Typesetter};
+use super::{
+    char_id, BMFont, CharPosition, EmptyGlyphPolicy, LayoutLine, LayoutOptions, LayoutParagraph,
+    LineMetrics, Overflow,
+};
+use std::ops::Range;
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`Layout::set_text()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type SetText = Result<(), StringParseError>;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`Layout::set_text()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type SetText = ();
+
+/// Alias of either [`Result<LayoutDiff, StringParseError>`] _or_ [LayoutDiff], returned by
+/// [`Layout::replace_range()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type ReplaceRange = Result<LayoutDiff, StringParseError>;
+
+/// Alias of either [`Result<LayoutDiff, StringParseError>`] _or_ [LayoutDiff], returned by
+/// [`Layout::replace_range()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type ReplaceRange = LayoutDiff;
+
+/// Glyphs changed by [`Layout::replace_range()`].
+///
+/// The glyphs `removed` of the old layout were replaced by the glyphs `inserted` of the new one.
+/// The glyphs after them are unchanged, except that they moved vertically by `dy` when the edit
+/// changed the number of lines.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LayoutDiff {
+    pub removed: Range<usize>,
+    pub inserted: Range<usize>,
+    pub dy: i32,
+}
+
+/// A part of the text between hard line breaks.
+#[derive(Clone, Copy, Debug, Default)]
+struct Paragraph {
+    text_start: usize,
+    glyph_start: usize,
+    /// Index of the first line of the paragraph in [`Layout::lines`].
+    line_start: usize,
+    y: i32,
+}
+
+/// Laid out text which keeps its buffers between calls to [`Layout::set_text()`], so laying out
+/// text of a similar length every frame does not allocate.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let mut layout = Layout::new(LayoutOptions::default());
+/// for label in &["Score", "Lives", "Level"] {
+///     let result = layout.set_text(&font, label);
+///     # #[cfg(feature = "parse-error")]
+///     # result.unwrap();
+///     assert_eq!(layout.char_positions().len(), 5);
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    options: LayoutOptions,
+    max_width: Option<u32>,
+    max_lines: Option<u32>,
+    /// Line height of the font the text was laid out with.
+    line_height: u32,
+    text: String,
+    paragraphs: Vec<Paragraph>,
+    char_positions: Vec<CharPosition>,
+    /// Lines which have ended, the current line being kept by the cursor.
+    lines: Vec<LineMetrics>,
+    /// Glyphs following an edited range while it is laid out again.
+    scratch: Vec<CharPosition>,
+    /// Lines following an edited range while it is laid out again.
+    line_scratch: Vec<LineMetrics>,
+    cursor: Cursor,
+}
+
+impl Layout {
+    pub fn new(options: LayoutOptions) -> Self {
+        Layout {
+            options,
+            ..Layout::default()
+        }
+    }
+
+    pub fn options(&self) -> &LayoutOptions {
+        &self.options
+    }
+
+    /// Replaces the options used by the next call to [`Layout::set_text()`].
+    pub fn set_options(&mut self, options: LayoutOptions) {
+        self.options = options;
+    }
+
+    pub fn max_width(&self) -> Option<u32> {
+        self.max_width
+    }
+
+    /// Sets the width after which lines are broken at whitespace by the next call to
+    /// [`Layout::set_text()`]. Words wider than `max_width` are not broken.
+    pub fn set_max_width(&mut self, max_width: Option<u32>) {
+        self.max_width = max_width;
+    }
+
+    pub fn max_lines(&self) -> Option<u32> {
+        self.max_lines
+    }
+
+    /// Sets the number of visual lines after which the text is reported as cut off by
+    /// [`Layout::overflow()`]. The lines past it are still laid out, so that edits stay
+    /// incremental.
+    pub fn set_max_lines(&mut self, max_lines: Option<u32>) {
+        self.max_lines = max_lines;
+    }
+
+    /// Replaces the laid out text with `text`, reusing the existing buffers.
+    pub fn set_text(&mut self, font: &BMFont, text: &str) -> SetText {
+        self.clear();
+        self.append_text(font, text)
+    }
+
+    /// Lays out `text` after the already laid out text, continuing from the pen position and line
+    /// where it ended, e.g. for streaming console output.
+    ///
+    /// The text must be laid out with the same font as the previous text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// let result = layout.append_text(&font, "Rust\n");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let result = layout.append_text(&font, "You");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// assert_eq!(layout.pen(), (121, 80));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn append_text(&mut self, font: &BMFont, text: &str) -> SetText {
+        #[cfg(feature = "parse-error")]
+        font.check_characters(text.chars(), &self.options)?;
+
+        if self.paragraphs.is_empty() {
+            self.paragraphs.push(Paragraph {
+                text_start: 0,
+                glyph_start: 0,
+                line_start: 0,
+                y: 0,
+            });
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        self.cursor = self.typeset(font, start..self.text.len(), self.cursor);
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(())
+        }
+    }
+
+    /// Replaces the `range` of bytes of the text with `replace_with`, e.g. for a keystroke in a
+    /// text editor, and lays out only the lines between the hard line breaks around the edit.
+    ///
+    /// The text must be laid out with the same font as the previous text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not lie on char boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// let result = layout.set_text(&font, "Rust\nYou\nRust");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let diff = layout.replace_range(&font, 5..8, "Me\nand you");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let diff = diff.unwrap();
+    /// assert_eq!(diff.removed, 4..7);
+    /// assert_eq!(diff.inserted, 4..13);
+    /// assert_eq!(diff.dy, 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn replace_range(
+        &mut self,
+        font: &BMFont,
+        range: Range<usize>,
+        replace_with: &str,
+    ) -> ReplaceRange {
+        #[cfg(feature = "parse-error")]
+        font.check_characters(replace_with.chars(), &self.options)?;
+
+        if self.paragraphs.is_empty() {
+            self.paragraphs.push(Paragraph {
+                text_start: 0,
+                glyph_start: 0,
+                line_start: 0,
+                y: 0,
+            });
+        }
+
+        // The edit affects the paragraphs containing both ends of the range.
+        let first = self.paragraph_index(range.start);
+        // A '\r' ending up right before a '\n' which started a line joins it into its line break,
+        // which makes the paragraph after the '\n' part of the edit too.
+        let previous_char = replace_with
+            .chars()
+            .next_back()
+            .or_else(|| self.text[..range.start].chars().next_back());
+        let last = if previous_char == Some('\r') && self.text[range.end..].starts_with('\n') {
+            self.paragraph_index(range.end + 1)
+        } else {
+            self.paragraph_index(range.end)
+        };
+        let first_paragraph = self.paragraphs[first];
+        let next_paragraph = self.paragraphs.get(last + 1).copied();
+        let old_end = next_paragraph.map(|next| {
+            let line_break = self.text[..next.text_start].chars().next_back();
+            next.text_start - line_break.map_or(0, char::len_utf8)
+        });
+        let old_glyph_end = next_paragraph
+            .map(|next| next.glyph_start)
+            .unwrap_or_else(|| self.char_positions.len());
+        let old_line_end = next_paragraph
+            .map(|next| next.line_start)
+            .unwrap_or_else(|| self.lines.len());
+
+        self.text.replace_range(range.clone(), replace_with);
+        let text_delta = replace_with.len() as isize - range.len() as isize;
+        let end = old_end
+            .map(|old_end| (old_end as isize + text_delta) as usize)
+            .unwrap_or_else(|| self.text.len());
+
+        // Set the following glyphs and paragraphs aside and lay out the affected paragraphs.
+        self.scratch.clear();
+        self.scratch
+            .extend(self.char_positions.drain(old_glyph_end..));
+        self.char_positions.truncate(first_paragraph.glyph_start);
+        self.line_scratch.clear();
+        self.line_scratch.extend(self.lines.drain(old_line_end..));
+        self.lines.truncate(first_paragraph.line_start);
+        let following_paragraphs = self.paragraphs.split_off(last + 1);
+        self.paragraphs.truncate(first + 1);
+
+        let cursor = self.typeset(
+            font,
+            first_paragraph.text_start..end,
+            Cursor::line_start(first_paragraph.y, first_paragraph.glyph_start),
+        );
+        let inserted = first_paragraph.glyph_start..self.char_positions.len();
+        let glyph_delta = inserted.end as isize - old_glyph_end as isize;
+
+        let dy = match next_paragraph {
+            Some(next_paragraph) => {
+                let mut typesetter = Typesetter::new(
+                    Some(font),
+                    &self.options,
+                    self.max_width,
+                    &mut self.char_positions,
+                    cursor,
+                );
+                typesetter.record_lines(&mut self.lines);
+                typesetter.new_line();
+                let dy = typesetter.cursor().y.saturating_sub(next_paragraph.y);
+                self.cursor.shift(dy, glyph_delta);
+                dy
+            }
+            None => {
+                self.cursor = cursor;
+                0
+            }
+        };
+
+        // Put the following glyphs, lines and paragraphs back, moved by the edit.
+        for mut char_position in self.scratch.drain(..) {
+            char_position.screen_rect.y = char_position.screen_rect.y.saturating_add(dy);
+            self.char_positions.push(char_position);
+        }
+        let line_delta = self.lines.len() as isize - old_line_end as isize;
+        for mut line in self.line_scratch.drain(..) {
+            line.glyphs.start = (line.glyphs.start as isize + glyph_delta) as usize;
+            line.glyphs.end = (line.glyphs.end as isize + glyph_delta) as usize;
+            line.y = line.y.saturating_add(dy);
+            self.lines.push(line);
+        }
+        for paragraph in following_paragraphs {
+            self.paragraphs.push(Paragraph {
+                text_start: (paragraph.text_start as isize + text_delta) as usize,
+                glyph_start: (paragraph.glyph_start as isize + glyph_delta) as usize,
+                line_start: (paragraph.line_start as isize + line_delta) as usize,
+                y: paragraph.y.saturating_add(dy),
+            });
+        }
+
+        let diff = LayoutDiff {
+            removed: first_paragraph.glyph_start..old_glyph_end,
+            inserted,
+            dy,
+        };
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(diff)
+        }
+
+        #[cfg(not(feature = "parse-error"))]
+        {
+            diff
+        }
+    }
+
+    /// Inserts `text` at the byte `idx` of the text, e.g. for a typed character in a text field,
+    /// laying out only the lines after the edit point. See [`Layout::replace_range()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds or does not lie on a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// let result = layout.set_text(&font, "Rust\nRust");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let diff = layout.insert(&font, 4, " You");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let diff = diff.unwrap();
+    /// assert_eq!(layout.text(), "Rust You\nRust");
+    /// assert_eq!(diff.inserted, 0..8);
+    /// assert_eq!(diff.dy, 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn insert(&mut self, font: &BMFont, idx: usize, text: &str) -> ReplaceRange {
+        self.replace_range(font, idx..idx, text)
+    }
+
+    /// Deletes the `range` of bytes of the text, e.g. for a backspace in a text field, laying out
+    /// only the lines after the edit point. See [`Layout::replace_range()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not lie on char boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// let result = layout.set_text(&font, "Rust\nYou\nRust");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let diff = layout.delete(&font, 4..8);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let diff = diff.unwrap();
+    /// assert_eq!(layout.text(), "Rust\nRust");
+    /// assert_eq!(diff.dy, -80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn delete(&mut self, font: &BMFont, range: Range<usize>) -> ReplaceRange {
+        self.replace_range(font, range, "")
+    }
+
+    /// Returns the width of the widest line, including trailing whitespace, and the distance from
+    /// the top of the first line to the bottom of the last one, e.g. to size the background of a
+    /// wrapped text without measuring it again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// layout.set_max_width(Some(100));
+    /// let result = layout.set_text(&font, "Rust Rust\nYou");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// assert_eq!(layout.size(), (font.width_of("Rust ") as u32, 240));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn size(&self) -> (u32, u32) {
+        let mut lines = self.lines();
+        let first = match lines.next() {
+            Some(first) => first,
+            None => return (0, 0),
+        };
+        let (width, last_y) = lines.fold((first.width, first.y), |(width, _), line| {
+            (width.max(line.width), line.y)
+        });
+        let height = (last_y as i64 - first.y as i64).unsigned_abs() + self.line_height as u64;
+        (width.max(0) as u32, height.min(u32::MAX as u64) as u32)
+    }
+
+    /// Returns the position of the pen, where the next glyph would be placed.
+    pub fn pen(&self) -> (i32, i32) {
+        (self.cursor.x, self.cursor.y)
+    }
+
+    /// Removes the laid out text, keeping the buffers.
+    pub fn clear(&mut self) {
+        self.line_height = 0;
+        self.text.clear();
+        self.paragraphs.clear();
+        self.char_positions.clear();
+        self.lines.clear();
+        self.cursor = Cursor::default();
+    }
+
+    /// Returns the laid out text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn char_positions(&self) -> &[CharPosition] {
+        &self.char_positions
+    }
+
+    /// Returns the visual lines of the text, telling apart lines ended by line breaks in the text
+    /// from lines ended by wrapping, e.g. to map visual lines back to logical lines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// layout.set_max_width(Some(100));
+    /// let result = layout.set_text(&font, "Rust Rust\nYou");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let endings: Vec<_> = layout.lines().map(|line| line.ending).collect();
+    /// assert_eq!(endings, [LineEnding::Soft, LineEnding::Hard, LineEnding::End]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn lines(&self) -> impl Iterator<Item = LineMetrics> + '_ {
+        let current = self.cursor.line(self.char_positions.len());
+        self.lines.iter().cloned().chain(std::iter::once(current))
+    }
+
+    /// Returns the text grouped into paragraphs, the paragraphs into visual lines and the lines
+    /// into glyphs, e.g. to draw every line separately or to select whole paragraphs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// layout.set_max_width(Some(100));
+    /// let result = layout.set_text(&font, "Rust Rust\nYou");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let paragraphs = layout.paragraphs();
+    /// assert_eq!(paragraphs.len(), 2);
+    /// assert_eq!(paragraphs[0].text, 0..9);
+    /// assert_eq!(paragraphs[0].lines.len(), 2);
+    /// assert_eq!(paragraphs[1].lines[0].glyphs.len(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn paragraphs(&self) -> Vec<LayoutParagraph<'_>> {
+        let mut lines: Vec<_> = self
+            .lines()
+            .map(|metrics| LayoutLine {
+                glyphs: &self.char_positions[metrics.glyphs.clone()],
+                metrics,
+            })
+            .collect();
+
+        // Paragraphs end where the line breaks starting the next ones begin.
+        let mut text_ends = Vec::with_capacity(self.paragraphs.len());
+        let mut line_breaks = LineBreaks::default();
+        for (idx, c) in self.text.char_indices() {
+            if line_breaks.classify(c as u32) == LineBreak::NewLine {
+                text_ends.push(idx);
+            }
+        }
+        text_ends.push(self.text.len());
+
+        let mut paragraphs = Vec::with_capacity(text_ends.len());
+        for (idx, &text_end) in text_ends.iter().enumerate().rev() {
+            let paragraph = self.paragraphs.get(idx).copied().unwrap_or_default();
+            paragraphs.push(LayoutParagraph {
+                text: self.paragraph_text_start(&paragraph)..text_end,
+                lines: lines.split_off(paragraph.line_start.min(lines.len())),
+            });
+        }
+        paragraphs.reverse();
+        paragraphs
+    }
+
+    /// Returns where the text is cut off by [`Layout::set_max_lines()`], if it has more lines,
+    /// e.g. to show a "more…" button or to switch to a scrollable view. Only the glyphs before
+    /// [Overflow::glyph] are meant to be drawn.
+    ///
+    /// `font` must be the font the text was laid out with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut layout = Layout::new(LayoutOptions::default());
+    /// layout.set_max_width(Some(100));
+    /// layout.set_max_lines(Some(2));
+    /// let result = layout.set_text(&font, "Rust Rust You");
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// let overflow = layout.overflow(&font).unwrap();
+    /// assert_eq!(&layout.text()[overflow.text..], "You");
+    /// assert_eq!(overflow.glyph, 10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn overflow(&self, font: &BMFont) -> Option<Overflow> {
+        let line = self.max_lines? as usize;
+        let glyph = self.lines().nth(line)?.glyphs.start;
+
+        // Walk the paragraph of the line up to its first glyph.
+        let paragraph = self
+            .paragraphs
+            .iter()
+            .rev()
+            .find(|paragraph| paragraph.line_start <= line)
+            .copied()
+            .unwrap_or_default();
+        let text_start = self.paragraph_text_start(&paragraph);
+        let mut glyphs_before = glyph - paragraph.glyph_start;
+        // A wrapped line starts after the whitespace ending the previous one, even if the
+        // whitespace has no glyphs.
+        let wrapped = glyphs_before > 0;
+        let mut text = self.text.len();
+        for (idx, c) in self.text[text_start..].char_indices() {
+            let has_glyph = char_id(c)
+                .and_then(|char_id| font.find_glyph(char_id, &self.options))
+                .is_some_and(|char| {
+                    self.options.empty_glyph_policy == EmptyGlyphPolicy::Emit
+                        || char.width != 0 && char.height != 0
+                });
+            if glyphs_before == 0 && (has_glyph || !wrapped || !c.is_whitespace()) {
+                text = text_start + idx;
+                break;
+            }
+            if has_glyph {
+                glyphs_before -= 1;
+            }
+        }
+
+        Some(Overflow { line, glyph, text })
+    }
+
+    /// Returns the byte index of the first character of `paragraph`, which follows the '\n' of a
+    /// "\r\n" pair that began it.
+    fn paragraph_text_start(&self, paragraph: &Paragraph) -> usize {
+        let text_start = paragraph.text_start;
+        if self.text[..text_start].ends_with('\r') && self.text[text_start..].starts_with('\n') {
+            text_start + 1
+        } else {
+            text_start
+        }
+    }
+
+    fn paragraph_index(&self, text_idx: usize) -> usize {
+        self.paragraphs
+            .iter()
+            .rposition(|paragraph| paragraph.text_start <= text_idx)
+            .unwrap_or(0)
+    }
+
+    /// Lays out the `range` of the text starting at `cursor`, recording the paragraphs which begin
+    /// in it, and returns the cursor at its end.
+    fn typeset(&mut self, font: &BMFont, range: Range<usize>, cursor: Cursor) -> Cursor {
+        self.line_height = font.line_height;
+        let Layout {
+            options,
+            max_width,
+            text,
+            paragraphs,
+            char_positions,
+            lines,
+            ..
+        } = self;
+        let mut typesetter =
+            Typesetter::new(Some(font), options, *max_width, char_positions, cursor);
+        typesetter.record_lines(lines);
+        // Paragraphs begin right after the character starting a line break, so the '\n' of a
+        // "\r\n" pair begins the paragraph and is skipped when it is laid out.
+        let mut line_breaks = LineBreaks::after(text[..range.start].chars().next_back());
+        let mut segment_start = range.start;
+        for (idx, c) in text[range.clone()].char_indices() {
+            let idx = range.start + idx;
+            match line_breaks.classify(c as u32) {
+                LineBreak::NewLine => {
+                    typesetter.push_str(0, font, &text[segment_start..idx]);
+                    typesetter.new_line();
+                    segment_start = idx + c.len_utf8();
+                    paragraphs.push(Paragraph {
+                        text_start: segment_start,
+                        glyph_start: typesetter.glyph_count(),
+                        line_start: typesetter.line_count(),
+                        y: typesetter.cursor().y,
+                    });
+                }
+                LineBreak::Joined => segment_start = idx + c.len_utf8(),
+                LineBreak::None => (),
+            }
+        }
+        typesetter.push_str(0, font, &text[segment_start..range.end]);
+        typesetter.cursor()
+    }
+}
@@ -0,0 +1,133 @@
+use super::{BMFont, CharPosition, LayoutOptions};
+use std::ops::Range;
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`BMFont::parse_batch()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type ParseBatch = Result<(), StringParseError>;
+
+/// Alias of either [`Result<(), StringParseError>`] _or_ `()`, returned by
+/// [`BMFont::parse_batch()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type ParseBatch = ();
+
+/// Glyphs of many strings laid out by [`BMFont::parse_batch()`] into one vector, together with
+/// the range of each string's glyphs in it.
+///
+/// Keeping a batch around and passing it again reuses its allocations, e.g. when laying out the
+/// labels of a HUD every frame.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LayoutBatch {
+    char_positions: Vec<CharPosition>,
+    ranges: Vec<Range<usize>>,
+}
+
+impl LayoutBatch {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the glyphs of all strings, one string after another.
+    pub fn char_positions(&self) -> &[CharPosition] {
+        &self.char_positions
+    }
+
+    /// Returns the range of [`LayoutBatch::char_positions()`] holding the glyphs of each string.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Returns the glyphs of the string at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&[CharPosition]> {
+        self.ranges
+            .get(index)
+            .map(|range| &self.char_positions[range.clone()])
+    }
+
+    /// Returns an iterator over the glyphs of each string.
+    pub fn iter(&self) -> impl Iterator<Item = &[CharPosition]> + '_ {
+        self.ranges
+            .iter()
+            .map(move |range| &self.char_positions[range.clone()])
+    }
+
+    /// Returns the number of strings in the batch.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the batch holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Removes all strings, keeping the allocated buffers.
+    pub fn clear(&mut self) {
+        self.char_positions.clear();
+        self.ranges.clear();
+    }
+}
+
+impl BMFont {
+    /// Lays out each of `items`, a string with its [LayoutOptions], into `batch`, replacing its
+    /// contents. All glyphs go into one vector, reusing the buffers of `batch`, and the glyphs of
+    /// each string are found through [`LayoutBatch::ranges()`], in the order of `items`.
+    ///
+    /// # Errors
+    ///
+    /// With the `parse-error` feature, fails on the first string with missing or unsupported
+    /// characters, leaving `batch` with the strings before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let options = LayoutOptions::default();
+    /// let mut batch = LayoutBatch::new();
+    /// let result = font.parse_batch(&[("You", options), ("Rust", options)], &mut batch);
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// assert_eq!(batch.ranges(), [0..3, 3..7]);
+    /// assert_eq!(batch.get(1).unwrap().len(), 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_batch(
+        &self,
+        items: &[(&str, LayoutOptions)],
+        batch: &mut LayoutBatch,
+    ) -> ParseBatch {
+        batch.clear();
+        batch.ranges.reserve(items.len());
+        for (s, options) in items {
+            let char_positions = self.parse_with(s, options);
+
+            #[cfg(feature = "parse-error")]
+            let char_positions = char_positions?;
+
+            let start = batch.char_positions.len();
+            batch.char_positions.extend(char_positions);
+            batch.ranges.push(start..batch.char_positions.len());
+        }
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(())
+        }
+    }
+}
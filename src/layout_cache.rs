@@ -0,0 +1,176 @@
+use super::{BMFont, CharPosition, LayoutOptions};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<Arc<[CharPosition]>, StringParseError>`] _or_
+/// [`Arc<[CharPosition]>`], returned by [`LayoutCache::parse_with()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type CachedParse = Result<Arc<[CharPosition]>, StringParseError>;
+
+/// Alias of either [`Result<Arc<[CharPosition]>, StringParseError>`] _or_
+/// [`Arc<[CharPosition]>`], returned by [`LayoutCache::parse_with()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type CachedParse = Arc<[CharPosition]>;
+
+#[derive(Debug)]
+struct Entry {
+    fingerprint: u64,
+    text: String,
+    options: LayoutOptions,
+    char_positions: Arc<[CharPosition]>,
+    /// Value of [`LayoutCache::clock`] when the entry was last used.
+    last_used: u64,
+}
+
+/// Laid out texts kept by font, text and [LayoutOptions], e.g. for UI labels laid out again every
+/// frame. Once `capacity` layouts are kept, the least recently used one is dropped.
+///
+/// Fonts are told apart by [`BMFont::fingerprint()`], so a font changed after a text was laid out
+/// with it does not get the stale layout.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # use std::sync::Arc;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let mut cache = LayoutCache::new(64);
+/// let options = LayoutOptions::default();
+/// let first = cache.parse_with(&font, "Score", &options);
+/// let second = cache.parse_with(&font, "Score", &options);
+/// # #[cfg(feature = "parse-error")]
+/// # let (first, second) = (first.unwrap(), second.unwrap());
+/// assert!(Arc::ptr_eq(&first, &second));
+/// assert_eq!(cache.len(), 1);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    capacity: usize,
+    entries: HashMap<u64, Entry>,
+    clock: u64,
+}
+
+impl LayoutCache {
+    pub fn new(capacity: usize) -> Self {
+        LayoutCache {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            clock: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of kept layouts.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops all kept layouts.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Drops the layouts of the font with the given [`BMFont::fingerprint()`], e.g. once it was
+    /// reloaded, returning how many were dropped.
+    pub fn invalidate(&mut self, fingerprint: u64) -> usize {
+        let len = self.entries.len();
+        self.entries
+            .retain(|_, entry| entry.fingerprint != fingerprint);
+        len - self.entries.len()
+    }
+
+    /// Returns the glyphs of `s` laid out with `font` as [`BMFont::parse_with()`] does, laying it
+    /// out only if it is not kept yet. Texts which fail to lay out are not kept.
+    pub fn parse_with(&mut self, font: &BMFont, s: &str, options: &LayoutOptions) -> CachedParse {
+        self.clock += 1;
+        let fingerprint = font.fingerprint();
+        let mut hasher = DefaultHasher::new();
+        (fingerprint, s, options).hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            if entry.fingerprint == fingerprint && entry.text == s && entry.options == *options {
+                entry.last_used = self.clock;
+                let char_positions = entry.char_positions.clone();
+
+                #[cfg(feature = "parse-error")]
+                {
+                    return Ok(char_positions);
+                }
+
+                #[cfg(not(feature = "parse-error"))]
+                {
+                    return char_positions;
+                }
+            }
+        }
+
+        let char_positions = font.parse_with(s, options);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions?;
+
+        let char_positions: Arc<[CharPosition]> = char_positions.collect();
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+                self.evict();
+            }
+            self.entries.insert(
+                key,
+                Entry {
+                    fingerprint,
+                    text: s.to_owned(),
+                    options: *options,
+                    char_positions: char_positions.clone(),
+                    last_used: self.clock,
+                },
+            );
+        }
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(char_positions)
+        }
+
+        #[cfg(not(feature = "parse-error"))]
+        {
+            char_positions
+        }
+    }
+
+    /// Drops the least recently used layout.
+    fn evict(&mut self) {
+        let least_recently_used = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(&key, _)| key);
+        if let Some(key) = least_recently_used {
+            self.entries.remove(&key);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use super::line_break::is_line_break;
+use super::{CharPosition, CoordinateSystem, Glyph, OrdinateOrientation, Rect};
+
+/// What [`BMFont::parse_with()`](crate::BMFont::parse_with) does with glyphs without area, such
+/// as the space. The pen advances past them in either case.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum EmptyGlyphPolicy {
+    /// Emit a [`CharPosition`](crate::CharPosition) with an empty rect, see
+    /// [`Rect::is_empty()`](crate::Rect::is_empty).
+    #[default]
+    Emit,
+    /// Emit nothing.
+    Skip,
+}
+
+/// What [`BMFont::parse_with()`](crate::BMFont::parse_with) does with invisible control
+/// characters: the C0 and C1 controls besides the line breaks, the zero-width characters such as
+/// U+200B ZERO WIDTH SPACE and U+FEFF ZERO WIDTH NO-BREAK SPACE, and the BiDi controls such as
+/// U+200F RIGHT-TO-LEFT MARK. `'\r'` is a line break, so it is never one of them.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ControlCharPolicy {
+    /// Lay them out as any other character, so that they are missing unless the font has glyphs
+    /// for them.
+    #[default]
+    Glyph,
+    /// Lay out nothing for them, without reporting them.
+    Skip,
+    /// Lay out the given character instead, e.g. `'\u{FFFD}'`. They are missing if the font lacks
+    /// it.
+    Substitute(char),
+    /// Report them as unsupported characters, even if the font has glyphs for them. They are
+    /// skipped where characters are not reported.
+    Error,
+}
+
+impl ControlCharPolicy {
+    /// Returns the char id to look up in place of `char_id`, if any.
+    pub(crate) fn replace(self, char_id: u32) -> Option<u32> {
+        match std::char::from_u32(char_id) {
+            Some(c) if is_control_char(c) => match self {
+                ControlCharPolicy::Glyph => Some(char_id),
+                ControlCharPolicy::Skip | ControlCharPolicy::Error => None,
+                ControlCharPolicy::Substitute(substitute) => crate::char_id(substitute),
+            },
+            _ => Some(char_id),
+        }
+    }
+}
+
+/// Returns `true` for the characters [ControlCharPolicy] applies to.
+pub(crate) fn is_control_char(c: char) -> bool {
+    (c.is_control() && !is_line_break(c))
+        || matches!(
+            c,
+            '\u{061C}'
+                | '\u{180E}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{206F}'
+                | '\u{FEFF}'
+        )
+}
+
+/// How far an empty line, one without any characters, advances the pen to the next line, e.g. for
+/// consecutive `'\n'`s. Lines with characters always advance by the line height.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum EmptyLineAdvance {
+    /// Advance by the full line height, so that blank lines take space as in a text editor.
+    #[default]
+    Full,
+    /// Advance by the given percentage of the line height.
+    Percent(u32),
+    /// Do not advance, so that consecutive line breaks act as one.
+    None,
+}
+
+impl EmptyLineAdvance {
+    /// Returns the advance of an empty line for the given line height.
+    pub(crate) fn advance(self, line_height: i32) -> i32 {
+        match self {
+            EmptyLineAdvance::Full => line_height,
+            EmptyLineAdvance::Percent(percent) => (line_height as i64 * percent as i64 / 100)
+                .clamp(i32::MIN as i64, i32::MAX as i64)
+                as i32,
+            EmptyLineAdvance::None => 0,
+        }
+    }
+}
+
+/// How far a space advances the pen if the font lacks its glyph, e.g. for icon fonts.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MissingSpaceAdvance {
+    /// Treat the space as any other missing character.
+    #[default]
+    Skip,
+    /// Advance by the given number of pixels.
+    Fixed(i32),
+    /// Advance by the given percentage of the base height.
+    Percent(u32),
+}
+
+impl MissingSpaceAdvance {
+    /// Returns the advance of a missing space for the given base height, if any.
+    pub(crate) fn advance(self, base_height: u32) -> Option<i32> {
+        match self {
+            MissingSpaceAdvance::Skip => None,
+            MissingSpaceAdvance::Fixed(advance) => Some(advance),
+            MissingSpaceAdvance::Percent(percent) => {
+                Some((base_height as i64 * percent as i64 / 100).min(i32::MAX as i64) as i32)
+            }
+        }
+    }
+}
+
+/// Whether glyphs scaled by [`TextRun::scale`](crate::TextRun::scale) are placed at whole
+/// pixels by [`layout_runs()`](crate::layout_runs).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Positioning {
+    /// Place every glyph at its exact scaled position rounded to the nearest whole pixel, halves
+    /// upwards, for crisp bitmap glyphs. Advances, kerning and offsets are not rounded one by one,
+    /// so spacing doesn't drift along a line at small scales.
+    #[default]
+    Snapped,
+    /// Advance the pen by the exact scaled advances and keep the fraction of a pixel at which
+    /// every glyph starts in [`CharPosition::subpixel`](crate::CharPosition::subpixel), e.g. for
+    /// distance field fonts. The sizes of the glyphs are still rounded.
+    Fractional,
+}
+
+/// Corner of the page [`CharPosition::page_rect`](crate::CharPosition::page_rect) is measured
+/// from.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum PageOrigin {
+    /// Measure from the top left corner with y growing downwards, as the font file does.
+    #[default]
+    TopLeft,
+    /// Measure from the bottom left corner with y growing upwards, for textures uploaded without
+    /// flipping their rows, as with OpenGL and some KTX and DDS loaders.
+    BottomLeft,
+}
+
+/// Point of a laid out text block placed at [`LayoutOptions::anchor_point`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the position of the anchor across the block, in halves of its width and height
+    /// from the top left corner.
+    pub(crate) fn halves(self) -> (i32, i32) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Center => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+/// Options controlling how [`BMFont::parse_with()`](crate::BMFont::parse_with) lays out text.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LayoutOptions {
+    pub empty_glyph_policy: EmptyGlyphPolicy,
+    pub empty_line_advance: EmptyLineAdvance,
+    /// Advance every glyph by this instead of its own advance, centering it in its cell and
+    /// ignoring kerning, e.g. `font.char_advance('0')` for tabular numbers.
+    pub fixed_advance: Option<i32>,
+    pub positioning: Positioning,
+    /// Place the point of the whole text block, spanning its widest line and all of its line
+    /// heights, at `anchor_point` instead of starting the first line at the origin. This takes a
+    /// measurement pass over the text. Only used by
+    /// [`BMFont::parse_with()`](crate::BMFont::parse_with) and its variants.
+    pub anchor: Option<Anchor>,
+    pub anchor_point: (i32, i32),
+    /// Lay out a character the font lacks with the glyph of its uppercase variant if it is
+    /// lowercase, or of its lowercase variant otherwise, e.g. for fonts with capitals only.
+    pub case_fallback: bool,
+    pub missing_space_advance: MissingSpaceAdvance,
+    /// Lay out text in this coordinate system instead of the one of the orientation the font was
+    /// loaded with, e.g. to use the same font in a y-down UI pass and a y-up world space pass.
+    pub coordinate_system: Option<CoordinateSystem>,
+    /// Lay out text with this orientation instead of the one the font was loaded with. Ignored if
+    /// [`coordinate_system`](Self::coordinate_system) is set.
+    #[deprecated(note = "Use `coordinate_system` instead.")]
+    pub ordinate_orientation: Option<OrdinateOrientation>,
+    pub control_char_policy: ControlCharPolicy,
+    pub page_origin: PageOrigin,
+    /// Leave out the glyphs lying entirely outside of this screen rectangle, and flag those lying
+    /// partly outside of it with [`CharPosition::clipped`](crate::CharPosition::clipped), e.g. for
+    /// scrolling text panels. The pen advances past left out glyphs as usual. Only used by
+    /// [`BMFont::parse_with()`](crate::BMFont::parse_with) and its variants and by
+    /// [`layout_runs()`](crate::layout_runs).
+    pub clip_rect: Option<Rect>,
+}
+
+impl LayoutOptions {
+    /// Returns the coordinate system to lay out text in, the one of `font_orientation` or of the
+    /// deprecated orientation override unless overridden.
+    pub(crate) fn coordinate_system(
+        &self,
+        font_orientation: OrdinateOrientation,
+    ) -> CoordinateSystem {
+        #[allow(deprecated)]
+        let ordinate_orientation = self.ordinate_orientation.unwrap_or(font_orientation);
+        self.coordinate_system
+            .unwrap_or_else(|| CoordinateSystem::from(ordinate_orientation))
+    }
+
+    /// Returns the rectangle of `glyph` on its page of `page_height` pixels, measured from the
+    /// [PageOrigin].
+    pub(crate) fn page_rect(&self, glyph: &Glyph, page_height: u32) -> Rect {
+        let y = match self.page_origin {
+            PageOrigin::TopLeft => glyph.y as i32,
+            PageOrigin::BottomLeft => (page_height as i32)
+                .saturating_sub((glyph.y as i32).saturating_add_unsigned(glyph.height)),
+        };
+        Rect {
+            x: glyph.x as i32,
+            y,
+            width: glyph.width,
+            height: glyph.height,
+        }
+    }
+
+    /// Flags `char_position` if it lies partly outside of the clip rectangle and returns `false`
+    /// if it lies entirely outside of it. Glyphs without area are kept if their position lies
+    /// inside of it.
+    pub(crate) fn clip(&self, char_position: &mut CharPosition) -> bool {
+        let clip_rect = match self.clip_rect {
+            Some(clip_rect) => clip_rect,
+            None => return true,
+        };
+        let mut rect = char_position.screen_rect;
+        if rect.is_empty() {
+            return clip_rect.contains(rect.x, rect.y);
+        }
+        // A fraction of a pixel further, the glyph reaches into one more pixel.
+        let (subpixel_x, subpixel_y) = char_position.subpixel;
+        rect.width += u32::from(subpixel_x > 0);
+        rect.height += u32::from(subpixel_y > 0);
+        match rect.intersection(&clip_rect) {
+            Some(visible) => {
+                char_position.clipped = visible != rect;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the advance, the kerning and the horizontal offset within its cell of a glyph.
+    pub(crate) fn advance(&self, xadvance: f64, kerning: f64) -> (f64, f64, f64) {
+        match self.fixed_advance {
+            Some(fixed_advance) => {
+                let fixed_advance = f64::from(fixed_advance);
+                (fixed_advance, 0.0, (fixed_advance - xadvance) / 2.0)
+            }
+            None => (xadvance, kerning, 0.0),
+        }
+    }
+
+    /// Returns the advance, the kerning and the horizontal offset within its cell of an unscaled
+    /// glyph in whole pixels, like [advance()](Self::advance) but without going through floating
+    /// point.
+    pub(crate) fn pixel_advance(&self, xadvance: i32, kerning: i32) -> (i32, i32, i32) {
+        match self.fixed_advance {
+            Some(fixed_advance) => {
+                let cell_offset = (i64::from(fixed_advance) - i64::from(xadvance)) / 2;
+                (fixed_advance, 0, cell_offset as i32)
+            }
+            None => (xadvance, kerning, 0),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use super::{CharPosition, LineMetrics};
+use std::ops::Range;
+
+/// A visual line of a [`Layout`](crate::Layout) together with its glyphs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutLine<'a> {
+    pub metrics: LineMetrics,
+    pub glyphs: &'a [CharPosition],
+}
+
+/// A paragraph of a [`Layout`](crate::Layout), the text between hard line breaks, together with
+/// its visual lines, see [`Layout::paragraphs()`](crate::Layout::paragraphs).
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutParagraph<'a> {
+    /// Byte range of the paragraph in [`Layout::text()`](crate::Layout::text), without the line
+    /// break ending it.
+    pub text: Range<usize>,
+    /// Visual lines of the paragraph, at least one.
+    pub lines: Vec<LayoutLine<'a>>,
+}
+
+impl<'a> LayoutParagraph<'a> {
+    /// Returns the glyphs of all lines of the paragraph.
+    pub fn glyphs(&self) -> impl Iterator<Item = &'a CharPosition> + '_ {
+        self.lines.iter().flat_map(|line| line.glyphs)
+    }
+}
@@ -4,27 +4,149 @@
 #[macro_use]
 extern crate serde;
 
-mod char;
+#[cfg(feature = "encoding_rs")]
+mod charset;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+mod color;
 mod config_parse_error;
+mod coordinate;
+mod coordinate_system;
+mod distance_field;
+#[cfg(feature = "ab_glyph")]
+mod dynamic_atlas;
+mod ellipsis_position;
 mod error;
+mod fingerprint_cache;
+mod fitted_text;
+mod font_collection;
+mod font_handle;
+mod font_metrics;
+mod font_registry;
+mod font_stats;
+#[cfg(feature = "ab_glyph")]
+mod generate;
+mod glyph;
+mod glyph_mesh;
+mod glyph_sink;
+mod glyph_transform;
+#[cfg(feature = "flate2")]
+mod gzip;
+#[cfg(feature = "notify")]
+mod hot_reload;
+mod inline_object;
 mod kerning_value;
+mod layout;
+mod layout_batch;
+mod layout_cache;
+mod layout_options;
+mod layout_paragraph;
+mod line_break;
+mod line_metrics;
+mod load_options;
+mod missing_char_action;
+#[cfg(feature = "memmap2")]
+mod mmap;
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+mod msdf;
+#[cfg(feature = "unicode-normalization")]
+mod nfc;
+mod overflow;
 mod page;
+mod page_batches;
+mod page_source;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parse_source;
+mod partial_parse;
 mod rect;
 mod sections;
+mod shaper;
+mod static_font;
 mod string_parse_error;
+mod text_plane;
+mod text_run;
+mod typesetter;
 mod utils;
+mod validation;
+mod word;
 
+#[cfg(feature = "encoding_rs")]
+pub use self::charset::DecodedChars;
+pub use self::color::Color;
 pub use self::config_parse_error::ConfigParseError;
+pub use self::coordinate::Coordinate;
+pub use self::coordinate_system::{AbscissaOrientation, CoordinateSystem, LineOrigin};
+pub use self::distance_field::{DistanceField, DistanceFieldType};
+#[cfg(feature = "ab_glyph")]
+pub use self::dynamic_atlas::{DirtyRegion, DynamicAtlas};
+pub use self::ellipsis_position::EllipsisPosition;
 pub use self::error::Error;
+pub use self::fitted_text::{FitToBox, FittedText};
+pub use self::font_collection::{FontCollection, FontCollectionError};
+pub use self::font_handle::FontHandle;
+pub use self::font_metrics::{FontMetrics, VerticalMetrics};
+pub use self::font_registry::FontRegistry;
+pub use self::font_stats::{BlockCoverage, FontStats};
+#[cfg(feature = "ab_glyph")]
+pub use self::generate::{GenerateError, GenerateOptions, GeneratedFont, PageImage};
+pub use self::glyph::Glyph;
+pub use self::glyph_mesh::{GlyphMesh, GlyphVertex, QUAD_INDICES};
+pub use self::glyph_sink::{GlyphSink, ParseInto};
+pub use self::glyph_transform::GlyphTransform;
+#[cfg(feature = "notify")]
+pub use self::hot_reload::{FontReload, FontWatcher};
+pub use self::inline_object::InlineObject;
+pub use self::layout::{Layout, LayoutDiff, ReplaceRange, SetText};
+pub use self::layout_batch::{LayoutBatch, ParseBatch};
+pub use self::layout_cache::{CachedParse, LayoutCache};
+pub use self::layout_options::{
+    Anchor, ControlCharPolicy, EmptyGlyphPolicy, EmptyLineAdvance, LayoutOptions,
+    MissingSpaceAdvance, PageOrigin, Positioning,
+};
+pub use self::layout_paragraph::{LayoutLine, LayoutParagraph};
+pub use self::line_metrics::{LineEnding, LineMetrics};
+pub use self::load_options::{
+    DuplicateCharPolicy, Exporter, KerningLookup, LoadOptions, TruncationPolicy,
+};
+pub use self::missing_char_action::MissingCharAction;
+pub use self::overflow::Overflow;
+pub use self::page_batches::PageBatches;
+pub use self::page_source::{DirectorySource, PageSource};
+#[cfg(feature = "rayon")]
+pub use self::parallel::ParParse;
+pub use self::parse_source::{CharIds, OwnedChars, Utf16Units};
+pub use self::partial_parse::PartialParse;
 pub use self::rect::Rect;
+pub use self::shaper::Shaper;
+pub use self::static_font::StaticFont;
 pub use self::string_parse_error::StringParseError;
+pub use self::text_plane::TextPlane;
+pub use self::text_run::{layout_runs, LayoutRuns, RunCharPosition, TextRun, SCRIPT_SCALE};
+pub use self::validation::{ValidationIssue, ValidationReport};
+pub use self::word::{Word, WordIter};
+
+/// Types named by the code `codegen` generates. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[deprecated(note = "Use `bmfont::Glyph` instead.")]
+    pub type Char = super::Glyph;
+    pub use super::kerning_value::KerningValue;
+    pub use super::page::Page;
+}
 
-use self::char::Char;
+use self::fingerprint_cache::FingerprintCache;
 use self::kerning_value::KerningValue;
+use self::layout_options::is_control_char;
+use self::line_break::{is_line_break, LineBreak, LineBreaks};
 use self::page::Page;
 use self::sections::Sections;
-use std::io::Read;
-use std::iter::Peekable;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
 use std::str::Chars;
 
 /// Alias of either [`Result<Vec<CharPosition>, StringParseError>`] _or_ [`Vec<CharPosition>`],
@@ -34,7 +156,7 @@ use std::str::Chars;
 ///
 /// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
 #[cfg(feature = "parse-error")]
-pub type Parse<'a> = Result<ParseIter<'a>, StringParseError>;
+pub type Parse<'a, T = CharIds<Chars<'a>>> = Result<ParseIter<'a, T>, StringParseError>;
 
 /// Alias of either [`Result<Vec<CharPosition>, StringParseError>`] _or_ [`Vec<CharPosition>`],
 /// returned by [`BMFont::parse()`].
@@ -43,26 +165,87 @@ pub type Parse<'a> = Result<ParseIter<'a>, StringParseError>;
 ///
 /// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
 #[cfg(not(feature = "parse-error"))]
-pub type Parse<'a> = ParseIter<'a>;
-
-#[cfg(feature = "parse-error")]
-type ParseLines<'a> = Result<LineIter<'a>, StringParseError>;
-
-#[cfg(not(feature = "parse-error"))]
-type ParseLines<'a> = LineIter<'a>;
+pub type Parse<'a, T = CharIds<Chars<'a>>> = ParseIter<'a, T>;
+
+// Fonts and layouts are shared between threads, so they must stay `Send` and `Sync`.
+#[allow(dead_code)]
+fn assert_send_sync() {
+    fn assert<T: Send + Sync>() {}
+    assert::<BMFont>();
+    assert::<FontHandle>();
+    assert::<Layout>();
+    assert::<ParseIter>();
+}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct CharPosition {
+    /// Rectangle of the glyph on its page, measured from the corner set by
+    /// [`LayoutOptions::page_origin`].
     pub page_rect: Rect,
     pub screen_rect: Rect,
     pub page_index: u32,
+    /// Horizontal advance of the glyph, excluding kerning.
+    pub xadvance: i32,
+    /// Kerning applied between the previous glyph on the line and this one.
+    pub kerning: i32,
+    /// Fraction of a pixel, in 256ths, by which the glyph lies further along the x and y axes than
+    /// `screen_rect`, when laid out with [`Positioning::Fractional`].
+    pub subpixel: (u8, u8),
+    /// Bitmask of the channels of the page the glyph is drawn in, for fonts packing glyphs into
+    /// separate channels: 1 for blue, 2 for green, 4 for red, 8 for alpha and 15 for all of them.
+    pub chnl: u32,
+    /// Color of the glyph, white unless given by a [TextRun] or [`ParseIter::colored()`].
+    pub color: Color,
+    /// Whether the glyph lies partly outside of [`LayoutOptions::clip_rect`], e.g. to draw it with
+    /// a scissor rectangle.
+    pub clipped: bool,
 }
 
-#[derive(Clone, Debug)]
+impl CharPosition {
+    /// Returns `[x, y, width, height]` of the screen rectangle in the number type `T`, including
+    /// the [`CharPosition::subpixel`] offset if `T` is a float, see [Coordinate].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// let char_position = CharPosition {
+    ///     screen_rect: Rect { x: 3, y: 5, width: 8, height: 10 },
+    ///     subpixel: (128, 64),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(char_position.screen_bounds::<i32>(), [3, 5, 8, 10]);
+    /// assert_eq!(char_position.screen_bounds::<f32>(), [3.5, 5.25, 8.0, 10.0]);
+    /// ```
+    pub fn screen_bounds<T: Coordinate>(&self) -> [T; 4] {
+        let rect = self.screen_rect;
+        [
+            T::from_pixels(rect.x, self.subpixel.0),
+            T::from_pixels(rect.y, self.subpixel.1),
+            T::from_pixels(rect.width as i32, 0),
+            T::from_pixels(rect.height as i32, 0),
+        ]
+    }
+
+    /// Returns the screen rectangle, laid out in logical units, in device pixels for the content
+    /// `scale` factor, see [`Rect::to_device()`]. The [`CharPosition::subpixel`] offset is taken
+    /// into account before rounding.
+    pub fn device_screen_rect(&self, scale: f32) -> Rect {
+        let [x, y, width, height] = self.screen_bounds::<f32>();
+        Rect::from_edges(
+            x * scale,
+            y * scale,
+            (x + width) * scale,
+            (y + height) * scale,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde_json", derive(Eq, PartialEq))]
 pub enum OrdinateOrientation {
     BottomToTop,
+    #[default]
     TopToBottom,
 }
 
@@ -73,104 +256,1475 @@ pub enum OrdinateOrientation {
 pub struct BMFont {
     base_height: u32,
     line_height: u32,
-    characters: Vec<Char>,
-    kerning_values: Vec<KerningValue>,
-    pages: Vec<Page>,
+    /// Size of the pages given by the "common" section, 0 if it is not given.
+    #[cfg_attr(feature = "serde", serde(default))]
+    page_width: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    page_height: u32,
+    /// Size of the font the glyphs were rendered from, given by the "info" section.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    size: Option<u32>,
+    /// Distance field the glyphs are drawn as, given by the "distanceField" section.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    distance_field: Option<DistanceField>,
+    characters: Cow<'static, [Glyph]>,
+    kerning_values: Cow<'static, [KerningValue]>,
+    /// Kerning values by `(first_char_id, second_char_id)`, built with [KerningLookup::HashMap].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    kerning_map: Option<HashMap<(u32, u32), i32>>,
+    pages: Cow<'static, [Page]>,
     ordinate_orientation: OrdinateOrientation,
+    /// Whether the file was cut off, see [TruncationPolicy].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    truncated: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fingerprint: FingerprintCache,
 }
 
-impl BMFont {
-    /// Constructs a new [BMFont].
+impl BMFont {
+    /// Constructs a new [BMFont].
+    ///
+    /// # Examples
+    ///
+    /// From a file:
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// From a slice of bytes:
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// # let my_font_bytes = std::fs::read("font.fnt")?;
+    /// let data = std::io::Cursor::new(my_font_bytes);
+    /// let font = BMFont::new(data, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn new<R>(source: R, ordinate_orientation: OrdinateOrientation) -> Result<BMFont, Error>
+    where
+        R: Read,
+    {
+        BMFont::with_options(source, ordinate_orientation, &LoadOptions::default())
+    }
+
+    /// Constructs a new [BMFont] using the given [LoadOptions].
+    ///
+    /// With the `flate2` feature, gzip-compressed sources are decompressed first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let options = LoadOptions {
+    ///     duplicate_char_policy: DuplicateCharPolicy::Error,
+    ///     ..Default::default()
+    /// };
+    /// let font = BMFont::with_options(file, OrdinateOrientation::TopToBottom, &options)?;
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_options<R>(
+        source: R,
+        ordinate_orientation: OrdinateOrientation,
+        options: &LoadOptions,
+    ) -> Result<BMFont, Error>
+    where
+        R: Read,
+    {
+        #[cfg(feature = "flate2")]
+        let content = gzip::read_to_string(source)?;
+
+        #[cfg(not(feature = "flate2"))]
+        let content = {
+            let mut source = source;
+            let mut content = String::new();
+            source.read_to_string(&mut content)?;
+            content
+        };
+
+        Ok(BMFont::from_str_with_options(
+            &content,
+            ordinate_orientation,
+            options,
+        )?)
+    }
+
+    /// Constructs a new [BMFont] from the bytes of a font, such as those fetched on the web.
+    ///
+    /// Unlike [BMFont::new()] it does not go through [std::io], so the only possible error is a
+    /// [ConfigParseError]. With the `flate2` feature, gzip-compressed bytes are decompressed first
+    /// like [BMFont::with_options()] does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let bytes = std::fs::read("font.fnt")?;
+    /// let font = BMFont::from_bytes(&bytes, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(
+        bytes: &[u8],
+        ordinate_orientation: OrdinateOrientation,
+    ) -> Result<BMFont, ConfigParseError> {
+        #[cfg(feature = "flate2")]
+        {
+            if let Some(content) = gzip::decompress(bytes) {
+                let content = content.map_err(ConfigParseError::InvalidGzip)?;
+                return BMFont::from_str_with_options(
+                    &content,
+                    ordinate_orientation,
+                    &LoadOptions::default(),
+                );
+            }
+        }
+
+        let content = std::str::from_utf8(bytes).map_err(ConfigParseError::InvalidUtf8)?;
+        BMFont::from_str_with_options(content, ordinate_orientation, &LoadOptions::default())
+    }
+
+    /// Constructs a new [BMFont] from the text of a font using the given [LoadOptions].
+    ///
+    /// Unlike [BMFont::with_options()] it does not go through [std::io], so the only possible
+    /// error is a [ConfigParseError].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let text = std::fs::read_to_string("font.fnt")?;
+    /// let options = LoadOptions::default();
+    /// let font = BMFont::from_str_with_options(&text, OrdinateOrientation::TopToBottom, &options)?;
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with_options(
+        s: &str,
+        ordinate_orientation: OrdinateOrientation,
+        options: &LoadOptions,
+    ) -> Result<BMFont, ConfigParseError> {
+        let quirks = options.exporter.quirks();
+        let sections = Sections::new(s, quirks)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            info = sections.info_section.is_some(),
+            distance_field = sections.distance_field_section.is_some(),
+            pages = sections.page_sections.len(),
+            chars = sections.char_sections.len(),
+            kernings = sections.kerning_sections.len(),
+            "found font sections"
+        );
+
+        // The "info" section only describes how the font was generated, so a malformed size is
+        // ignored. A negative size means it matches the height of the characters.
+        let size = sections
+            .info_section
+            .and_then(|info_section| {
+                utils::section_components(info_section, "info", quirks)
+                    .and_then(|components| components.optional_value::<i32>("size"))
+                    .ok()
+                    .flatten()
+            })
+            .map(i32::unsigned_abs);
+
+        // Fonts exported with unicode=0 have the char ids of the code page named by charset.
+        #[cfg(feature = "encoding_rs")]
+        let encoding = sections.info_section.and_then(|info_section| {
+            let components = utils::section_components(info_section, "info", quirks).ok()?;
+            let unicode = components.optional_value::<u32>("unicode").ok()??;
+            let charset = components.optional_value::<String>("charset").ok()??;
+            match unicode {
+                0 => charset::charset_encoding(utils::unquote(&charset)),
+                _ => None,
+            }
+        });
+
+        // An unknown or malformed "distanceField" section is ignored like the "info" section.
+        let distance_field = sections.distance_field_section.and_then(|section| {
+            let components = utils::section_components(section, "distanceField", quirks).ok()?;
+            let field_type = components.optional_value::<String>("fieldType").ok()??;
+            Some(DistanceField {
+                field_type: DistanceFieldType::from_name(utils::unquote(&field_type))?,
+                distance_range: components.optional_value("distanceRange").ok()??,
+            })
+        });
+
+        let base_height;
+        let line_height;
+        let page_width;
+        let page_height;
+        {
+            let components = utils::section_components(sections.common_section, "common", quirks)?;
+            line_height = components.value("lineHeight")?;
+            base_height = components.value("base")?;
+            // Older exporters leave out the size of the pages.
+            page_width = components.optional_value("scaleW")?.unwrap_or(0);
+            page_height = components.optional_value("scaleH")?.unwrap_or(0);
+        }
+
+        let mut pages = Vec::with_capacity(sections.page_sections.len());
+        for page_section in &sections.page_sections {
+            pages.push(Page::with_quirks(page_section, quirks)?);
+        }
+
+        // A file which declares more characters or kerning values than it has was cut off. With
+        // TruncationPolicy::Recover, its unterminated last line is dropped when it cannot be
+        // loaded or may hold cut off values.
+        let chars_missing = sections
+            .char_count
+            .is_some_and(|count| count > sections.char_sections.len());
+        let kernings_missing = sections
+            .kerning_count
+            .is_some_and(|count| count > sections.kerning_sections.len());
+        let mut truncated = chars_missing || kernings_missing;
+        let is_cut_off = |section: &str| {
+            options.truncation_policy == TruncationPolicy::Recover
+                && sections
+                    .unterminated_line
+                    .is_some_and(|line| std::ptr::eq(line, section))
+        };
+
+        // Sort the characters while loading them so that lookup can be faster during parse
+        let mut characters: Vec<Glyph> = Vec::with_capacity(sections.char_sections.len());
+        for char_section in &sections.char_sections {
+            let char = Glyph::with_quirks(char_section, quirks);
+            if is_cut_off(char_section) && (char.is_err() || chars_missing) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(line = char_section, "dropped cut off char line");
+
+                truncated = true;
+                continue;
+            }
+            let char = char?;
+            match characters.binary_search_by(|probe| probe.id.cmp(&char.id)) {
+                Err(idx) => characters.insert(idx, char),
+                Ok(idx) => match options.duplicate_char_policy {
+                    DuplicateCharPolicy::KeepFirst => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(char_id = char.id, "ignored duplicate char");
+                    }
+                    DuplicateCharPolicy::KeepLast => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(char_id = char.id, "replaced duplicate char");
+
+                        characters[idx] = char;
+                    }
+                    DuplicateCharPolicy::Error => {
+                        return Err(ConfigParseError::DuplicateChar(char.id))
+                    }
+                },
+            }
+        }
+
+        // Also sort kerning values for the same reason, but we allow duplicates
+        let mut kerning_values: Vec<KerningValue> =
+            Vec::with_capacity(sections.kerning_sections.len());
+        for kerning_section in &sections.kerning_sections {
+            let kerning = KerningValue::with_quirks(kerning_section, quirks);
+            if is_cut_off(kerning_section) && (kerning.is_err() || kernings_missing) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(line = kerning_section, "dropped cut off kerning line");
+
+                truncated = true;
+                continue;
+            }
+            let kerning = kerning?;
+
+            match kerning_values
+                .binary_search_by(|probe| probe.first_char_id.cmp(&kerning.first_char_id))
+            {
+                Err(idx) | Ok(idx) => kerning_values.insert(idx, kerning),
+            }
+        }
+
+        #[cfg(feature = "encoding_rs")]
+        if let Some(encoding) = encoding {
+            charset::decode_char_ids(encoding, &mut characters, &mut kerning_values);
+        }
+
+        let kerning_map = match options.kerning_lookup {
+            KerningLookup::BinarySearch => None,
+            KerningLookup::HashMap => {
+                let mut kerning_map = HashMap::with_capacity(kerning_values.len());
+                for kerning in &kerning_values {
+                    kerning_map
+                        .entry((kerning.first_char_id, kerning.second_char_id))
+                        .or_insert(kerning.value);
+                }
+                Some(kerning_map)
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            chars = characters.len(),
+            kernings = kerning_values.len(),
+            pages = pages.len(),
+            truncated,
+            "loaded font"
+        );
+
+        Ok(BMFont {
+            base_height,
+            line_height,
+            page_width,
+            page_height,
+            size,
+            distance_field,
+            characters: Cow::Owned(characters),
+            kerning_values: Cow::Owned(kerning_values),
+            kerning_map,
+            pages: Cow::Owned(pages),
+            ordinate_orientation,
+            truncated,
+            fingerprint: FingerprintCache::default(),
+        })
+    }
+
+    /// Constructs a new [BMFont] borrowing the tables of a [StaticFont], without parsing or
+    /// allocating.
+    ///
+    /// The tables are usually generated by `codegen::generate()` from a build script, which is
+    /// available with the `codegen` feature.
+    pub fn from_static(
+        font: &'static StaticFont,
+        ordinate_orientation: OrdinateOrientation,
+    ) -> BMFont {
+        BMFont {
+            base_height: font.base_height,
+            line_height: font.line_height,
+            page_width: font.page_width,
+            page_height: font.page_height,
+            size: font.size,
+            distance_field: font.distance_field,
+            characters: Cow::Borrowed(font.characters),
+            kerning_values: Cow::Borrowed(font.kerning_values),
+            kerning_map: None,
+            pages: Cow::Borrowed(font.pages),
+            ordinate_orientation,
+            truncated: false,
+            fingerprint: FingerprintCache::default(),
+        }
+    }
+
+    /// Returns the height of a `EM` in pixels.
+    pub fn base_height(&self) -> u32 {
+        self.base_height
+    }
+
+    pub fn line_height(&self) -> u32 {
+        self.line_height
+    }
+
+    pub fn ordinate_orientation(&self) -> OrdinateOrientation {
+        self.ordinate_orientation
+    }
+
+    /// Changes the orientation text is laid out with, without loading the font again.
+    pub fn set_ordinate_orientation(&mut self, ordinate_orientation: OrdinateOrientation) {
+        self.ordinate_orientation = ordinate_orientation;
+        self.fingerprint.reset();
+    }
+
+    /// Returns the font with the orientation text is laid out with changed, e.g. to reuse a loaded
+    /// font in a subsystem with other conventions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let font = font.with_ordinate_orientation(OrdinateOrientation::BottomToTop);
+    /// assert_eq!(font.ordinate_orientation(), OrdinateOrientation::BottomToTop);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn with_ordinate_orientation(mut self, ordinate_orientation: OrdinateOrientation) -> Self {
+        self.set_ordinate_orientation(ordinate_orientation);
+        self
+    }
+
+    /// Returns the vertical metrics of the font, e.g. to align its baseline with text rendered
+    /// from vector fonts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let metrics = font.metrics();
+    /// assert_eq!(metrics.ascent + metrics.descent + metrics.line_gap, metrics.line_height);
+    /// assert_eq!(metrics.size, Some(72));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn metrics(&self) -> FontMetrics {
+        FontMetrics {
+            ascent: self.base_height,
+            descent: self.line_height.saturating_sub(self.base_height),
+            line_gap: 0,
+            line_height: self.line_height,
+            size: self.size,
+        }
+    }
+
+    /// Returns the vertical metrics of the font with the descent measured from its glyphs, as the
+    /// distance from the baseline to the bottom of the lowest glyph, and the line gap as the rest
+    /// of the line height, so that lines can be spaced tighter or looser than the font does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let metrics = font.vertical_metrics();
+    /// assert_eq!((metrics.ascent, metrics.descent, metrics.line_gap), (57, 17, 6));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn vertical_metrics(&self) -> VerticalMetrics {
+        let bottom = self
+            .characters
+            .iter()
+            .map(|glyph| i64::from(glyph.yoffset) + i64::from(glyph.height))
+            .max()
+            .unwrap_or(0);
+        let descent = (bottom - i64::from(self.base_height)).clamp(0, i64::from(u32::MAX)) as u32;
+        VerticalMetrics {
+            descent,
+            line_gap: self
+                .line_height
+                .saturating_sub(self.base_height.saturating_add(descent)),
+            ..self.metrics()
+        }
+    }
+
+    /// Returns the distance field the glyphs are drawn as, or `None` if they are drawn as
+    /// coverage, e.g. to pick a shader.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let text = std::fs::read_to_string("font.fnt")?
+    ///     + "\ndistanceField fieldType=msdf distanceRange=4";
+    /// let options = LoadOptions::default();
+    /// let font = BMFont::from_str_with_options(&text, OrdinateOrientation::TopToBottom, &options)?;
+    /// let distance_field = font.distance_field().unwrap();
+    /// assert_eq!(distance_field.field_type, DistanceFieldType::Msdf);
+    /// assert_eq!(distance_field.distance_range, 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn distance_field(&self) -> Option<DistanceField> {
+        self.distance_field
+    }
+
+    /// Returns the width of each font page bitmap in pixels, or 0 if the font does not give it.
+    pub fn page_width(&self) -> u32 {
+        self.page_width
+    }
+
+    /// Returns the height of each font page bitmap in pixels, or 0 if the font does not give it.
+    pub fn page_height(&self) -> u32 {
+        self.page_height
+    }
+
+    /// Returns an `Iterator` of font page bitmap filenames.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.pages().next(), Some("font.png"));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pages(&self) -> PageIter<'_> {
+        PageIter::new(&self.pages)
+    }
+
+    /// Reads the page files of the font from `source`, in the order of [BMFont::pages()].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let pages = font.load_pages(&mut DirectorySource::new("."))?;
+    /// assert_eq!(pages[0], std::fs::read("font.png")?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn load_pages<S>(&self, source: &mut S) -> std::io::Result<Vec<Vec<u8>>>
+    where
+        S: PageSource + ?Sized,
+    {
+        self.pages().map(|name| source.read_page(name)).collect()
+    }
+
+    /// Returns an `Iterator` of the ids of the characters in the font, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.char_ids().next(), Some(32));
+    /// assert_eq!(font.char_ids().count(), 81);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn char_ids(&self) -> CharIdIter<'_> {
+        CharIdIter {
+            chars: self.characters.iter(),
+        }
+    }
+
+    /// Returns `true` if the font file was cut off, so that some characters or kerning values are
+    /// missing. Such fonts only load with [TruncationPolicy::Recover], unless the file was cut off
+    /// right after a line.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns a hash of everything laying out text depends on: the metrics, the glyphs, the
+    /// kerning and the ordinate orientation, e.g. to key cached layouts. It changes with
+    /// [BMFont::set_kerning()] and [BMFont::set_ordinate_orientation()], and is only stable within
+    /// a single run of the program.
+    ///
+    /// It is computed once and kept until the font changes, so asking for it again is cheap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let mut font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let fingerprint = font.fingerprint();
+    /// assert_eq!(font.clone().fingerprint(), fingerprint);
+    /// font.set_kerning('R', 'u', -3);
+    /// assert_ne!(font.fingerprint(), fingerprint);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+            .get_or_compute(|| self.compute_fingerprint())
+    }
+
+    fn compute_fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.base_height, self.line_height).hash(&mut hasher);
+        (self.page_width, self.page_height, self.size).hash(&mut hasher);
+        (self.distance_field, self.ordinate_orientation).hash(&mut hasher);
+        for char in self.characters.iter() {
+            (char.id, char.x, char.y, char.width, char.height).hash(&mut hasher);
+            (char.xoffset, char.yoffset, char.xadvance).hash(&mut hasher);
+            (char.page_index, char.chnl).hash(&mut hasher);
+        }
+        for kerning_value in self.kerning_values.iter() {
+            let pair = (kerning_value.first_char_id, kerning_value.second_char_id);
+            (pair, kerning_value.value).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Returns the number of kerning pairs in the font.
+    pub fn kerning_count(&self) -> usize {
+        self.kerning_values.len()
+    }
+
+    /// Sets the kerning between `first` and `second` to `value`, overriding the kerning the font
+    /// was loaded with, e.g. to fix a bad pair without editing the font file. A `value` of `0`
+    /// removes the pair. Pairs with characters outside of the Basic Multilingual Plane, which are
+    /// never laid out, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let mut font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// font.set_kerning('A', 'V', -10);
+    /// let char_positions = font.parse("AV");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.last().unwrap().kerning, -10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_kerning(&mut self, first: char, second: char, value: i32) {
+        let (first_char_id, second_char_id) = match (char_id(first), char_id(second)) {
+            (Some(first_char_id), Some(second_char_id)) => (first_char_id, second_char_id),
+            _ => return,
+        };
+        self.fingerprint.reset();
+        if let Some(kerning_map) = self.kerning_map.as_mut() {
+            if value == 0 {
+                kerning_map.remove(&(first_char_id, second_char_id));
+            } else {
+                kerning_map.insert((first_char_id, second_char_id), value);
+            }
+        }
+        let kerning_values = self.kerning_values.to_mut();
+        kerning_values
+            .retain(|k| k.first_char_id != first_char_id || k.second_char_id != second_char_id);
+        if value != 0 {
+            let idx = kerning_values.partition_point(|k| k.first_char_id <= first_char_id);
+            kerning_values.insert(
+                idx,
+                KerningValue {
+                    first_char_id,
+                    second_char_id,
+                    value,
+                },
+            );
+        }
+    }
+
+    /// Writes the font in the text format [BMFont::new()] reads.
+    ///
+    /// [BMFont] does not keep the "info" section, so only an empty one is written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut text = Vec::new();
+    /// font.write_text(&mut text)?;
+    /// let copy = BMFont::from_bytes(&text, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(copy.char_ids().count(), font.char_ids().count());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_text<W>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        match self.size {
+            Some(size) => writeln!(writer, "info face=\"\" size={}", size)?,
+            None => writeln!(writer, "info face=\"\"")?,
+        }
+        writeln!(
+            writer,
+            "common lineHeight={} base={} scaleW={} scaleH={} pages={}",
+            self.line_height,
+            self.base_height,
+            self.page_width,
+            self.page_height,
+            self.pages.len()
+        )?;
+        for page in self.pages.iter() {
+            writeln!(writer, "page id={} file=\"{}\"", page.id, page.file)?;
+        }
+        if let Some(distance_field) = self.distance_field {
+            writeln!(
+                writer,
+                "distanceField fieldType={} distanceRange={}",
+                distance_field.field_type.name(),
+                distance_field.distance_range
+            )?;
+        }
+        writeln!(writer, "chars count={}", self.characters.len())?;
+        for char in self.characters.iter() {
+            write!(
+                writer,
+                "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page={} chnl={}",
+                char.id,
+                char.x,
+                char.y,
+                char.width,
+                char.height,
+                char.xoffset,
+                char.yoffset,
+                char.xadvance,
+                char.page_index,
+                char.chnl
+            )?;
+            if let Some(ref letter) = char.letter {
+                write!(writer, " letter=\"{}\"", letter)?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "kernings count={}", self.kerning_values.len())?;
+        for kerning in self.kerning_values.iter() {
+            writeln!(
+                writer,
+                "kerning first={} second={} amount={}",
+                kerning.first_char_id, kerning.second_char_id, kerning.value
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the whole font, including the "info" and "common" data, its pages, characters and
+    /// kerning values, as pretty printed JSON, e.g. for web tools or golden files in tests.
+    ///
+    /// The JSON can be loaded back with `serde_json::from_str()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let json = font.to_json().unwrap();
+    /// assert!(json.contains("\"line_height\": 80"));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Checks the font for internal inconsistencies: glyphs lying outside of the page bitmap,
+    /// `letter` fields contradicting char ids, characters referencing undeclared pages, glyphs
+    /// overlapping each other and kerning pairs referencing undeclared characters.
+    ///
+    /// Glyphs are only checked against the page bitmap if the font gives the size of its pages.
+    ///
+    /// Glyphs in different channels of a packed page and characters sharing the very same glyph
+    /// rectangle are not considered overlapping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert!(font.validate().is_valid());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        if self.truncated {
+            report.issues.push(ValidationIssue::Truncated);
+        }
+
+        let page_size_known = self.page_width > 0 && self.page_height > 0;
+        for char in self.characters.iter() {
+            if page_size_known
+                && (char.x.saturating_add(char.width) > self.page_width
+                    || char.y.saturating_add(char.height) > self.page_height)
+            {
+                report
+                    .issues
+                    .push(ValidationIssue::GlyphOutsidePage { char_id: char.id });
+            }
+            if !char.letter_matches_id() {
+                report
+                    .issues
+                    .push(ValidationIssue::LetterMismatch { char_id: char.id });
+            }
+            if !self.pages.iter().any(|page| page.id == char.page_index) {
+                report.issues.push(ValidationIssue::MissingPage {
+                    char_id: char.id,
+                    page_index: char.page_index,
+                });
+            }
+        }
+
+        report.issues.extend(self.overlapping_glyphs());
+
+        for kerning_value in self.kerning_values.iter() {
+            for &char_id in &[kerning_value.first_char_id, kerning_value.second_char_id] {
+                if self.find_char(char_id).is_none() {
+                    report
+                        .issues
+                        .push(ValidationIssue::MissingKerningCharacter {
+                            first_char_id: kerning_value.first_char_id,
+                            second_char_id: kerning_value.second_char_id,
+                            missing_char_id: char_id,
+                        });
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Lays out `s` with the default [LayoutOptions].
+    ///
+    /// Laying out never panics, whatever the text and the font: control characters such as
+    /// `'\0'` are looked up as any other character, and the pen saturates at the bounds of `i32`
+    /// instead of overflowing on extremely long lines. The same holds for the other ways of laying
+    /// out text, which also keep the pen in whole pixels, with only the fraction of a pixel in
+    /// floating point, so glyphs stay exactly placed on lines millions of pixels long.
+    pub fn parse<'s>(&'s self, s: &'s str) -> Parse<'s> {
+        self.parse_with(s, &LayoutOptions::default())
+    }
+
+    /// Lays out `s` with the given [LayoutOptions].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let options = LayoutOptions {
+    ///     empty_glyph_policy: EmptyGlyphPolicy::Skip,
+    ///     ..Default::default()
+    /// };
+    /// let char_positions = font.parse_with("a b", &options);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 2);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with<'s>(&'s self, s: &'s str, options: &LayoutOptions) -> Parse<'s> {
+        #[cfg(feature = "parse-error")]
+        self.check_characters(s.chars(), options)?;
+
+        self.parse_char_ids(CharIds::new(s.chars()), options)
+    }
+
+    /// Lays out `s` with the given [LayoutOptions], returning the missing and unsupported
+    /// characters as an error regardless of the `parse-error` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let options = LayoutOptions::default();
+    /// assert_eq!(font.parse_checked("Rust", &options).unwrap().count(), 4);
+    /// let error = font.parse_checked("Řust", &options).err().unwrap();
+    /// assert_eq!(error.missing_characters, ['Ř']);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_checked<'s>(
+        &'s self,
+        s: &'s str,
+        options: &LayoutOptions,
+    ) -> Result<ParseIter<'s>, StringParseError> {
+        self.check_characters(s.chars(), options)?;
+        Ok(self.lay_out(CharIds::new(s.chars()), options))
+    }
+
+    /// Lays out `s` with the given [LayoutOptions], skipping missing and unsupported characters
+    /// regardless of the `parse-error` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.parse_lossy("Řust", &LayoutOptions::default()).count(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_lossy<'s>(&'s self, s: &'s str, options: &LayoutOptions) -> ParseIter<'s> {
+        self.lay_out(CharIds::new(s.chars()), options)
+    }
+
+    /// Lays out `s` with the given [LayoutOptions], calling `fallback` with the index and the
+    /// character for every character the font lacks or does not support to choose what to do
+    /// with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let options = LayoutOptions::default();
+    /// let char_positions = font.parse_with_fallback("Řust", &options, |_, c| match c {
+    ///     'Ř' => MissingCharAction::Substitute('R'),
+    ///     _ => MissingCharAction::Abort,
+    /// });
+    /// assert_eq!(char_positions.unwrap().count(), 4);
+    /// let char_positions = font.parse_with_fallback("Řust", &options, |_, _| MissingCharAction::Abort);
+    /// assert_eq!(char_positions.err().unwrap().missing_characters, ['Ř']);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with_fallback<F>(
+        &self,
+        s: &str,
+        options: &LayoutOptions,
+        mut fallback: F,
+    ) -> Result<ParseIter<'_, CharIds<OwnedChars>>, StringParseError>
+    where
+        F: FnMut(usize, char) -> MissingCharAction,
+    {
+        let mut text = String::with_capacity(s.len());
+        for (idx, c) in s.chars().enumerate() {
+            if is_line_break(c) {
+                text.push(c);
+                continue;
+            }
+            let is_control_char = is_control_char(c);
+            if is_control_char && options.control_char_policy == ControlCharPolicy::Skip {
+                continue;
+            }
+            let char_id = char_id(c);
+            if char_id
+                .and_then(|char_id| self.find_glyph(char_id, options))
+                .is_some()
+            {
+                text.push(c);
+                continue;
+            }
+            match fallback(idx, c) {
+                MissingCharAction::Skip => (),
+                MissingCharAction::Substitute(substitute) => text.push(substitute),
+                MissingCharAction::Abort => {
+                    let is_unsupported = char_id.is_none()
+                        || is_control_char
+                            && options.control_char_policy == ControlCharPolicy::Error;
+                    let (missing_characters, unsupported_characters) = if is_unsupported {
+                        (Vec::new(), vec![c])
+                    } else {
+                        (vec![c], Vec::new())
+                    };
+                    return Err(StringParseError {
+                        missing_characters,
+                        unsupported_characters,
+                    });
+                }
+            }
+        }
+
+        Ok(self.lay_out(CharIds::new(OwnedChars::new(text)), options))
+    }
+
+    /// Lays out `s` with the given [LayoutOptions] as [BMFont::parse_lossy()] does and returns
+    /// its words, the runs of characters between whitespace, with their byte ranges and screen
+    /// bounds, e.g. for tappable links, per-word animations or spell-check underlines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let words: Vec<_> = font.words(" Rust  You\n", &LayoutOptions::default()).collect();
+    /// assert_eq!(words.len(), 2);
+    /// assert_eq!(&words[1].range, &(7..10));
+    /// assert!(words[0].rect.max_x() < words[1].rect.x);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn words<'s>(&'s self, s: &'s str, options: &LayoutOptions) -> WordIter<'s> {
+        WordIter::new(s, self.parse_lossy(s, options))
+    }
+
+    /// Lays out all characters of `s` the font has with the given [LayoutOptions] and returns
+    /// their glyphs together with the skipped characters, e.g. to render text as well as possible
+    /// while logging the problem.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let partial_parse = font.parse_partial("Řust", &LayoutOptions::default());
+    /// assert_eq!(partial_parse.char_positions.len(), 3);
+    /// assert_eq!(partial_parse.error.unwrap().missing_characters, ['Ř']);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_partial(&self, s: &str, options: &LayoutOptions) -> PartialParse {
+        PartialParse {
+            char_positions: self.lay_out(CharIds::new(s.chars()), options).collect(),
+            error: self.check_characters(s.chars(), options).err(),
+        }
+    }
+
+    /// Lays out `s` with the given [LayoutOptions] into `sink` instead of returning an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut vertices: Vec<[f32; 2]> = Vec::new();
+    /// let mut sink = |char_position: CharPosition| {
+    ///     let rect = char_position.screen_rect;
+    ///     vertices.extend(rect.corners().iter().map(|&(x, y)| [x as f32, y as f32]));
+    /// };
+    /// let result = font.parse_into("Rust", &LayoutOptions::default(), &mut sink);
+    /// # #[cfg(feature = "parse-error")]
+    /// # result.unwrap();
+    /// assert_eq!(vertices.len(), 16);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_into<S>(&self, s: &str, options: &LayoutOptions, sink: &mut S) -> ParseInto
+    where
+        S: GlyphSink + ?Sized,
+    {
+        let char_positions = self.parse_with(s, options);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions?;
+
+        for char_position in char_positions {
+            sink.push(char_position);
+        }
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(())
+        }
+    }
+
+    /// Lays out `s` with the default [LayoutOptions], like [BMFont::parse()], but the returned
+    /// iterator owns the text, so it can outlive it, e.g. be returned from a function.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// fn lay_out_greeting<'a>(font: &'a BMFont, name: &str) -> impl Iterator<Item = CharPosition> + 'a {
+    ///     let char_positions = font.parse_owned(format!("Hello {}", name));
+    ///     # #[cfg(feature = "parse-error")]
+    ///     # let char_positions = char_positions.unwrap();
+    ///     char_positions
+    /// }
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(lay_out_greeting(&font, "Rust").count(), 10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_owned(&self, s: String) -> Parse<'_, CharIds<OwnedChars>> {
+        self.parse_owned_with(s, &LayoutOptions::default())
+    }
+
+    /// Lays out `s` with the given [LayoutOptions], like [BMFont::parse_with()], but the returned
+    /// iterator owns the text.
+    pub fn parse_owned_with(
+        &self,
+        s: String,
+        options: &LayoutOptions,
+    ) -> Parse<'_, CharIds<OwnedChars>> {
+        #[cfg(feature = "parse-error")]
+        self.check_characters(s.chars(), options)?;
+
+        self.parse_char_ids(CharIds::new(OwnedChars::new(s)), options)
+    }
+
+    /// Lays out the characters `chars` with the default [LayoutOptions], like [BMFont::parse()],
+    /// for text which is not stored as a contiguous string, e.g. in a rope. The iterator is cloned
+    /// to check the characters before laying them out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let chunks = ["Hello ", "Rust"];
+    /// let char_positions = font.parse_chars(chunks.iter().flat_map(|chunk| chunk.chars()));
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_chars<I>(&self, chars: I) -> Parse<'_, CharIds<I::IntoIter>>
+    where
+        I: IntoIterator<Item = char>,
+        I::IntoIter: Clone,
+    {
+        self.parse_chars_with(chars, &LayoutOptions::default())
+    }
+
+    /// Lays out the characters `chars` with the given [LayoutOptions], like
+    /// [BMFont::parse_with()].
+    pub fn parse_chars_with<I>(
+        &self,
+        chars: I,
+        options: &LayoutOptions,
+    ) -> Parse<'_, CharIds<I::IntoIter>>
+    where
+        I: IntoIterator<Item = char>,
+        I::IntoIter: Clone,
+    {
+        let chars = chars.into_iter();
+
+        #[cfg(feature = "parse-error")]
+        self.check_characters(chars.clone(), options)?;
+
+        self.parse_char_ids(CharIds::new(chars), options)
+    }
+
+    /// Lays out `s` with the given [LayoutOptions], like [BMFont::parse_with()], after `shaper`
+    /// rewrote the char ids of every line, see [Shaper]. The characters are checked after
+    /// shaping, so a shaper may map characters the font lacks to glyphs it has. Missing char ids
+    /// are reported as the characters they are ids of, and those which are not ids of any
+    /// character, such as private glyph ids written by the shaper, are skipped without an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// // Draws "<3" as a heart, which this font keeps at the char id of '_'.
+    /// let mut hearts = |_: &BMFont, line: &mut Vec<u32>| {
+    ///     while let Some(idx) = line.windows(2).position(|pair| pair == ['<' as u32, '3' as u32]) {
+    ///         line.splice(idx..idx + 2, Some('_' as u32));
+    ///     }
+    /// };
+    /// let char_positions = font.parse_shaped("Rust<3", &LayoutOptions::default(), &mut hearts);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 5);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_shaped<S>(
+        &self,
+        s: &str,
+        options: &LayoutOptions,
+        shaper: &mut S,
+    ) -> Parse<'_, std::vec::IntoIter<u32>>
+    where
+        S: Shaper + ?Sized,
+    {
+        let mut char_ids = Vec::with_capacity(s.len());
+        let mut line = Vec::new();
+        for c in s.chars() {
+            let char_id = match char_id(c) {
+                Some(char_id) => char_id,
+                None => continue,
+            };
+            if is_line_break(c) {
+                shaper.shape(self, &mut line);
+                char_ids.append(&mut line);
+                char_ids.push(char_id);
+            } else {
+                line.push(char_id);
+            }
+        }
+        shaper.shape(self, &mut line);
+        char_ids.append(&mut line);
+
+        // Characters without a char id never reach the shaper, but are still unsupported.
+        #[cfg(feature = "parse-error")]
+        self.check_characters(
+            s.chars().filter(|&c| char_id(c).is_none()).chain(
+                char_ids
+                    .iter()
+                    .filter_map(|&char_id| std::char::from_u32(char_id)),
+            ),
+            options,
+        )?;
+
+        self.parse_char_ids(char_ids.into_iter(), options)
+    }
+
+    /// Lays out the UTF-16 encoded text `units` with the default [LayoutOptions], like
+    /// [BMFont::parse()]. Font char ids are UTF-16 code units, so they are looked up directly
+    /// without decoding the text.
+    ///
+    /// Surrogate pairs, which encode characters outside of the Basic Multilingual Plane, are
+    /// unsupported characters, and so are unpaired surrogates, which are reported as U+FFFD as
+    /// they encode no character. Neither is looked up in the font.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let units = "Rust".encode_utf16().collect::<Vec<u16>>();
+    /// let char_positions = font.parse_utf16(&units);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_utf16<'s>(&'s self, units: &'s [u16]) -> Parse<'s, Utf16Units<'s>> {
+        self.parse_utf16_with(units, &LayoutOptions::default())
+    }
+
+    /// Lays out the UTF-16 encoded text `units` with the given [LayoutOptions], like
+    /// [BMFont::parse_with()]. Surrogates are handled as by [BMFont::parse_utf16()].
+    pub fn parse_utf16_with<'s>(
+        &'s self,
+        units: &'s [u16],
+        options: &LayoutOptions,
+    ) -> Parse<'s, Utf16Units<'s>> {
+        #[cfg(feature = "parse-error")]
+        self.check_decoded_characters(
+            std::char::decode_utf16(units.iter().copied()).map(Result::ok),
+            options,
+        )?;
+
+        self.parse_char_ids(Utf16Units::new(units), options)
+    }
+
+    /// Returns the width of `s` laid out on a single line, kerning included, without laying out
+    /// its glyphs, e.g. to align or truncate text.
+    ///
+    /// The width is the position of the pen after the last character, as for
+    /// [`ParseIter::pen()`]. Line breaks and characters missing from the font are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.width_of("You"), 121);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn width_of(&self, s: &str) -> i32 {
+        let mut width = 0;
+        let mut previous_char_id = None;
+        for c in s.chars() {
+            if is_line_break(c) {
+                continue;
+            }
+            let char = match char_id(c).and_then(|char_id| self.find_char(char_id)) {
+                Some(char) => char,
+                None => continue,
+            };
+            if let Some(previous_char_id) = previous_char_id {
+                width = i32::saturating_add(width, self.kerning(previous_char_id, char.id));
+            }
+            width = i32::saturating_add(width, char.xadvance);
+            previous_char_id = Some(char.id);
+        }
+        width
+    }
+
+    /// Returns `s` cut at `position` with an ellipsis so that it is at most `max_width` wide on a
+    /// single line, as measured by [BMFont::width_of()], or `s` itself if it fits, e.g. for file
+    /// names in fixed-width UI slots. The ellipsis is `'…'`, or `"..."` if the font lacks it.
+    ///
+    /// As many characters as fit are kept, half of them from either end for
+    /// [EllipsisPosition::Middle]. An empty string is returned if not even the ellipsis fits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let max_width = font.width_of("long...name") as u32;
+    /// let text = "long_file_name";
+    /// assert_eq!(font.truncate_to_width(text, max_width, EllipsisPosition::Middle), "long...name");
+    /// assert_eq!(font.truncate_to_width("name", max_width, EllipsisPosition::Start), "name");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn truncate_to_width<'s>(
+        &self,
+        s: &'s str,
+        max_width: u32,
+        position: EllipsisPosition,
+    ) -> Cow<'s, str> {
+        let max_width = i32::try_from(max_width).unwrap_or(i32::MAX);
+        if self.width_of(s) <= max_width {
+            return Cow::Borrowed(s);
+        }
+        let ellipsis = if self.has_glyph('…') { "…" } else { "..." };
+        let boundaries: Vec<usize> = s.char_indices().map(|(idx, _)| idx).collect();
+        let char_count = boundaries.len();
+        let byte_idx = |char_idx: usize| boundaries.get(char_idx).copied().unwrap_or(s.len());
+        // Returns the text keeping `kept` characters.
+        let truncated = |kept: usize| {
+            let (head, tail) = match position {
+                EllipsisPosition::End => (kept, 0),
+                EllipsisPosition::Start => (0, kept),
+                EllipsisPosition::Middle => (kept - kept / 2, kept / 2),
+            };
+            let mut truncated = String::with_capacity(s.len());
+            truncated.push_str(&s[..byte_idx(head)]);
+            truncated.push_str(ellipsis);
+            truncated.push_str(&s[byte_idx(char_count - tail)..]);
+            truncated
+        };
+
+        // Find the most characters to keep, as the width grows with them.
+        let (mut fitting, mut too_many) = (None, char_count);
+        let mut low = 0;
+        while low < too_many {
+            let kept = (low + too_many) / 2;
+            if self.width_of(&truncated(kept)) <= max_width {
+                fitting = Some(kept);
+                low = kept + 1;
+            } else {
+                too_many = kept;
+            }
+        }
+        Cow::Owned(fitting.map(truncated).unwrap_or_default())
+    }
+
+    /// Returns the second characters of the kerning pairs starting with `first` and the amounts
+    /// by which the pen moves between them, in no particular order, using the same sorted lookup
+    /// as layout, e.g. for external shapers and caret placement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let kernings: Vec<_> = font.kernings_for('Y').collect();
+    /// assert!(kernings.contains(&(',', -9)));
+    /// assert_eq!(font.kernings_for('Ř').count(), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn kernings_for(&self, first: char) -> KerningPairIter<'_> {
+        let kerning_values = match char_id(first) {
+            Some(first_char_id) => self.find_kerning_values(first_char_id),
+            None => KerningIter {
+                first_char_id: 0,
+                idx: self.kerning_values.len(),
+                values: &self.kerning_values,
+            },
+        };
+        KerningPairIter { kerning_values }
+    }
+
+    /// Returns the horizontal advance of the glyph of `c`, or `None` if the font lacks it.
+    ///
+    /// Kerning is not included, see [BMFont::width_of()] for the width of a string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert_eq!(font.char_advance('Y'), Some(48));
+    /// assert_eq!(font.char_advance('Ř'), None);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn char_advance(&self, c: char) -> Option<i32> {
+        char_id(c)
+            .and_then(|char_id| self.find_char(char_id))
+            .map(|char| char.xadvance)
+    }
+
+    /// Returns the glyph of `c`, if the font has one.
     ///
     /// # Examples
     ///
-    /// From a file:
-    ///
     /// ```rust
     /// # use bmfont::*;
     /// # fn main() -> Result<(), Error> {
     /// let file = std::fs::File::open("font.fnt")?;
     /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
-    /// assert_eq!(font.line_height(), 80);
+    /// let glyph = font.glyph('Y').unwrap();
+    /// assert_eq!((glyph.id, glyph.xadvance), ('Y' as u32, 48));
+    /// assert!(font.glyph('Ř').is_none());
     /// #     Ok(())
     /// # }
     /// ```
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        char_id(c).and_then(|char_id| self.find_char(char_id))
+    }
+
+    /// Returns all glyphs of the font, sorted by id.
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.characters
+    }
+
+    /// Returns `true` if the font has glyphs for all characters of `s`, line breaks aside, e.g. to
+    /// pick the first font of a fallback list which covers a string.
     ///
-    /// From a slice of bytes:
+    /// # Examples
     ///
     /// ```rust
     /// # use bmfont::*;
     /// # fn main() -> Result<(), Error> {
-    /// # let my_font_bytes = std::fs::read("font.fnt")?;
-    /// let data = std::io::Cursor::new(my_font_bytes);
-    /// let font = BMFont::new(data, OrdinateOrientation::TopToBottom)?;
-    /// assert_eq!(font.line_height(), 80);
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// assert!(font.supports("Rust\nYou"));
+    /// assert!(!font.supports("Řust"));
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn new<R>(source: R, ordinate_orientation: OrdinateOrientation) -> Result<BMFont, Error>
-    where
-        R: Read,
-    {
-        let sections = Sections::new(source)?;
-
-        let base_height;
-        let line_height;
-        {
-            let mut components = sections.common_section.split_whitespace();
-            components.next();
-            line_height =
-                utils::extract_component_value(components.next(), "common", "lineHeight")?;
-            base_height = utils::extract_component_value(components.next(), "common", "base")?;
-        }
-
-        let mut pages = Vec::with_capacity(sections.page_sections.len());
-        for page_section in &sections.page_sections {
-            pages.push(Page::new(page_section)?);
-        }
-
-        // Sort the characters while loading them so that lookup can be faster during parse
-        let mut characters: Vec<Char> = Vec::with_capacity(sections.char_sections.len());
-        for char_section in &sections.char_sections {
-            let char = Char::new(char_section)?;
-            if let Err(idx) = characters.binary_search_by(|probe| probe.id.cmp(&char.id)) {
-                characters.insert(idx, char);
-            }
-        }
-
-        // Also sort kerning values for the same reason, but we allow duplicates
-        let mut kerning_values: Vec<KerningValue> =
-            Vec::with_capacity(sections.kerning_sections.len());
-        for kerning_section in &sections.kerning_sections {
-            let kerning = KerningValue::new(kerning_section)?;
-
-            match kerning_values
-                .binary_search_by(|probe| probe.first_char_id.cmp(&kerning.first_char_id))
-            {
-                Err(idx) | Ok(idx) => kerning_values.insert(idx, kerning),
-            }
-        }
-
-        Ok(BMFont {
-            base_height,
-            line_height,
-            characters,
-            kerning_values,
-            pages,
-            ordinate_orientation,
-        })
-    }
-
-    /// Returns the height of a `EM` in pixels.
-    pub fn base_height(&self) -> u32 {
-        self.base_height
-    }
-
-    pub fn line_height(&self) -> u32 {
-        self.line_height
+    pub fn supports(&self, s: &str) -> bool {
+        s.chars().all(|c| is_line_break(c) || self.has_glyph(c))
     }
 
-    /// Returns an `Iterator` of font page bitmap filenames.
+    /// Returns the characters of `s` without a glyph, line breaks aside, each once in the order
+    /// of their first occurrence together with the number of their occurrences, e.g. to audit
+    /// translations against a font.
     ///
     /// # Examples
     ///
@@ -179,21 +1733,33 @@ impl BMFont {
     /// # fn main() -> Result<(), Error> {
     /// let file = std::fs::File::open("font.fnt")?;
     /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
-    /// assert_eq!(font.pages().next(), Some("font.png"));
+    /// assert_eq!(font.missing_chars("Řeřicha Ř"), [('Ř', 2), ('ř', 1)]);
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn pages(&self) -> PageIter {
-        PageIter::new(&self.pages)
+    pub fn missing_chars(&self, s: &str) -> Vec<(char, usize)> {
+        let mut missing_chars: Vec<(char, usize)> = Vec::new();
+        for c in s.chars() {
+            if is_line_break(c) || self.has_glyph(c) {
+                continue;
+            }
+            match missing_chars
+                .iter_mut()
+                .find(|(missing_char, _)| *missing_char == c)
+            {
+                Some((_, count)) => *count += 1,
+                None => missing_chars.push((c, 1)),
+            }
+        }
+        missing_chars
     }
 
-    pub fn parse<'s>(&'s self, s: &'s str) -> Parse<'s> {
-        let lines = self.parse_lines(s);
-
-        #[cfg(feature = "parse-error")]
-        let lines = lines?;
-
-        let char_positions = ParseIter::new(self, lines);
+    /// Lays out char ids whose characters were checked already.
+    fn parse_char_ids<T>(&self, char_ids: T, options: &LayoutOptions) -> Parse<'_, T>
+    where
+        T: Iterator<Item = u32> + Clone,
+    {
+        let char_positions = self.lay_out(char_ids, options);
 
         #[cfg(feature = "parse-error")]
         {
@@ -206,12 +1772,122 @@ impl BMFont {
         }
     }
 
-    fn find_kerning_values(&self, first_char_id: u32) -> KerningIter {
-        let needle = (first_char_id << 1) - 1;
+    /// Lays out char ids, skipping those missing from the font.
+    fn lay_out<T>(&self, char_ids: T, options: &LayoutOptions) -> ParseIter<'_, T>
+    where
+        T: Iterator<Item = u32> + Clone,
+    {
+        let mut char_positions = ParseIter::new(self, char_ids, *options);
+        if let Some(anchor) = options.anchor {
+            let (x, y) = char_positions.anchor_offset(anchor, options.anchor_point);
+            char_positions.x = x;
+            char_positions.y = y;
+            char_positions.x_origin = x;
+        }
+        char_positions
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        char_id(c).is_some_and(|char_id| self.find_char(char_id).is_some())
+    }
+
+    /// Returns the glyph laid out for `char_id`, falling back as `options` allow.
+    fn find_glyph(&self, char_id: u32, options: &LayoutOptions) -> Option<Cow<'_, Glyph>> {
+        let char_id = options.control_char_policy.replace(char_id)?;
+        if let Some(char) = self.find_char(char_id) {
+            return Some(Cow::Borrowed(char));
+        }
+        if options.case_fallback {
+            let other_case_char = std::char::from_u32(char_id)
+                .and_then(other_case)
+                .and_then(self::char_id)
+                .and_then(|char_id| self.find_char(char_id));
+            if let Some(char) = other_case_char {
+                return Some(Cow::Borrowed(char));
+            }
+        }
+        if char_id == ' ' as u32 {
+            let xadvance = options.missing_space_advance.advance(self.base_height)?;
+            return Some(Cow::Owned(Glyph::space(xadvance)));
+        }
+        None
+    }
+
+    /// Returns a [`ValidationIssue::OverlappingGlyphs`] for each pair of overlapping glyphs,
+    /// sorted by char ids.
+    fn overlapping_glyphs(&self) -> Vec<ValidationIssue> {
+        let page_rect = |char: &Glyph| Rect {
+            x: char.x as i32,
+            y: char.y as i32,
+            width: char.width,
+            height: char.height,
+        };
+        let mut glyphs: Vec<&Glyph> = self
+            .characters
+            .iter()
+            .filter(|char| char.width > 0 && char.height > 0)
+            .collect();
+        glyphs.sort_by_key(|char| (char.page_index, char.x));
+
+        // Sweep along x, so only glyphs starting before the end of a glyph are compared with it.
+        let mut overlaps = Vec::new();
+        for (idx, char) in glyphs.iter().enumerate() {
+            let rect = page_rect(char);
+            for other in &glyphs[idx + 1..] {
+                if other.page_index != char.page_index || other.x as i32 >= rect.max_x() {
+                    break;
+                }
+                let other_rect = page_rect(other);
+                if other.channels() & char.channels() != 0
+                    && other_rect != rect
+                    && other_rect.intersects(&rect)
+                {
+                    overlaps.push((
+                        char.id.min(other.id),
+                        char.id.max(other.id),
+                        char.page_index,
+                    ));
+                }
+            }
+        }
+        overlaps.sort_unstable();
+        overlaps
+            .into_iter()
+            .map(
+                |(first_char_id, second_char_id, page_index)| ValidationIssue::OverlappingGlyphs {
+                    first_char_id,
+                    second_char_id,
+                    page_index,
+                },
+            )
+            .collect()
+    }
+
+    fn find_char(&self, char_id: u32) -> Option<&Glyph> {
+        self.characters
+            .binary_search_by(|probe| probe.id.cmp(&char_id))
+            .ok()
+            .map(|idx| &self.characters[idx])
+    }
+
+    fn kerning(&self, first_char_id: u32, second_char_id: u32) -> i32 {
+        match self.kerning_map.as_ref() {
+            Some(kerning_map) => kerning_map
+                .get(&(first_char_id, second_char_id))
+                .copied()
+                .unwrap_or(0),
+            None => self
+                .find_kerning_values(first_char_id)
+                .find(|k| k.second_char_id == second_char_id)
+                .map(|k| k.value)
+                .unwrap_or(0),
+        }
+    }
+
+    fn find_kerning_values(&self, first_char_id: u32) -> KerningIter<'_> {
         let idx = self
             .kerning_values
-            .binary_search_by(|probe| (probe.first_char_id << 1).cmp(&needle))
-            .unwrap_err();
+            .partition_point(|probe| probe.first_char_id < first_char_id);
 
         KerningIter {
             first_char_id,
@@ -220,17 +1896,54 @@ impl BMFont {
         }
     }
 
-    fn parse_lines<'a>(&'a self, s: &'a str) -> ParseLines<'a> {
-        #[cfg(feature = "parse-error")]
-        {
-            let mut temp = [0u16; 2];
-            let mut missing_characters: Option<Vec<char>> = None;
-            let mut unsupported_characters: Option<Vec<char>> = None;
+    fn check_characters<I>(&self, chars: I, options: &LayoutOptions) -> Result<(), StringParseError>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.check_decoded_characters(chars.into_iter().map(Some), options)
+    }
 
-            for c in s.chars() {
-                if c == '\n' {
+    /// Checks characters like [BMFont::check_characters()], `None` standing for text which does
+    /// not decode to a character, which is reported as an unsupported U+FFFD.
+    fn check_decoded_characters<I>(
+        &self,
+        chars: I,
+        options: &LayoutOptions,
+    ) -> Result<(), StringParseError>
+    where
+        I: IntoIterator<Item = Option<char>>,
+    {
+        let mut missing_characters: Option<Vec<char>> = None;
+        let mut unsupported_characters: Option<Vec<char>> = None;
+
+        for c in chars {
+            let c = match c {
+                Some(c) => c,
+                None => {
+                    unsupported_characters
+                        .get_or_insert_with(Vec::new)
+                        .push(std::char::REPLACEMENT_CHARACTER);
                     continue;
-                } else if c.len_utf16() != 1 {
+                }
+            };
+            if is_line_break(c) {
+                continue;
+            }
+
+            if is_control_char(c) {
+                match options.control_char_policy {
+                    ControlCharPolicy::Glyph | ControlCharPolicy::Substitute(_) => (),
+                    ControlCharPolicy::Skip => continue,
+                    ControlCharPolicy::Error => {
+                        unsupported_characters.get_or_insert_with(Vec::new).push(c);
+                        continue;
+                    }
+                }
+            }
+
+            let char_id = match char_id(c) {
+                Some(char_id) => char_id,
+                None => {
                     if let Some(vec) = unsupported_characters.as_mut() {
                         vec.push(c);
                     } else {
@@ -239,96 +1952,63 @@ impl BMFont {
 
                     continue;
                 }
+            };
 
-                c.encode_utf16(&mut temp);
-                let char_id = temp[0] as u32;
-
-                if self
-                    .characters
-                    .binary_search_by(|probe| probe.id.cmp(&char_id))
-                    .is_ok()
-                {
-                    continue;
-                }
-
-                if let Some(vec) = missing_characters.as_mut() {
-                    vec.push(c);
-                } else {
-                    missing_characters = Some(vec![c]);
-                }
+            if self.find_glyph(char_id, options).is_some() {
+                continue;
             }
 
-            if missing_characters.is_some() || unsupported_characters.is_some() {
-                return Err(StringParseError {
-                    missing_characters: missing_characters.unwrap_or_default(),
-                    unsupported_characters: unsupported_characters.unwrap_or_default(),
-                });
+            if let Some(vec) = missing_characters.as_mut() {
+                vec.push(c);
+            } else {
+                missing_characters = Some(vec![c]);
             }
         }
 
-        let lines = LineIter {
-            characters: &self.characters,
-            text: Some(s.chars().peekable()),
-        };
-
-        #[cfg(feature = "parse-error")]
-        {
-            Ok(lines)
+        if missing_characters.is_some() || unsupported_characters.is_some() {
+            return Err(StringParseError {
+                missing_characters: missing_characters.unwrap_or_default(),
+                unsupported_characters: unsupported_characters.unwrap_or_default(),
+            });
         }
 
-        #[cfg(not(feature = "parse-error"))]
-        lines
+        Ok(())
     }
 }
 
-struct CharIter<'a> {
-    characters: &'a Vec<Char>,
-    text: Peekable<Chars<'a>>,
+/// Returns the uppercase variant of a lowercase `c` and the lowercase variant of any other `c`, if
+/// it is a single character different from `c`.
+fn other_case(c: char) -> Option<char> {
+    let mut other_case: Vec<char> = if c.is_lowercase() {
+        c.to_uppercase().collect()
+    } else {
+        c.to_lowercase().collect()
+    };
+    match other_case.pop() {
+        Some(other_case_char) if other_case.is_empty() && other_case_char != c => {
+            Some(other_case_char)
+        }
+        _ => None,
+    }
 }
 
-impl<'a> Iterator for CharIter<'a> {
-    type Item = &'a Char;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            return match self.text.next() {
-                None | Some('\n') => None,
-                Some(chr) if chr.len_utf16() != 1 => continue,
-                Some(chr) => {
-                    let mut temp = [0u16; 2];
-                    chr.encode_utf16(&mut temp);
-                    let char_id = temp[0] as u32;
-                    let char_idx = self
-                        .characters
-                        .binary_search_by(|probe| probe.id.cmp(&char_id));
-
-                    #[cfg(not(feature = "parse-error"))]
-                    if char_idx.is_err() {
-                        continue;
-                    }
-
-                    let char_idx = char_idx.unwrap();
-                    Some(&self.characters[char_idx])
-                }
-            };
-        }
+/// Returns the id of the glyph for `c`, which is its UTF-16 code unit. Characters outside of the
+/// Basic Multilingual Plane are not supported.
+fn char_id(c: char) -> Option<u32> {
+    if c.len_utf16() != 1 {
+        return None;
     }
+
+    let mut temp = [0u16; 2];
+    c.encode_utf16(&mut temp);
+    Some(temp[0] as u32)
 }
 
+#[derive(Clone, Debug)]
 struct KerningIter<'a> {
     first_char_id: u32,
     idx: usize,
-    values: &'a Vec<KerningValue>,
-}
-
-impl<'a> KerningIter<'a> {
-    fn empty(values: &'a Vec<KerningValue>) -> Self {
-        Self {
-            first_char_id: 0,
-            idx: values.len(),
-            values,
-        }
-    }
+    values: &'a [KerningValue],
 }
 
 impl<'a> Iterator for KerningIter<'a> {
@@ -346,33 +2026,45 @@ impl<'a> Iterator for KerningIter<'a> {
     }
 }
 
-struct LineIter<'a> {
-    characters: &'a Vec<Char>,
-    text: Option<Peekable<Chars<'a>>>,
+/// Iterator over the kerning pairs starting with a character, see [BMFont::kernings_for()].
+#[derive(Clone, Debug)]
+pub struct KerningPairIter<'a> {
+    kerning_values: KerningIter<'a>,
 }
 
-impl<'a> Iterator for LineIter<'a> {
-    type Item = CharIter<'a>;
+impl<'a> Iterator for KerningPairIter<'a> {
+    type Item = (char, i32);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.text.as_mut().unwrap().peek() {
-            Some(_) => Some(CharIter {
-                characters: &self.characters,
-                text: self.text.take().unwrap(),
-            }),
-            _ => None,
-        }
+        // Pairs with an unpaired surrogate as the second character are skipped.
+        self.kerning_values.by_ref().find_map(|kerning_value| {
+            std::char::from_u32(kerning_value.second_char_id)
+                .map(|second| (second, kerning_value.value))
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CharIdIter<'a> {
+    chars: std::slice::Iter<'a, Glyph>,
+}
+
+impl<'a> Iterator for CharIdIter<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chars.next().map(|char| char.id)
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct PageIter<'a> {
     idx: usize,
-    pages: &'a Vec<Page>,
+    pages: &'a [Page],
 }
 
 impl<'a> PageIter<'a> {
-    fn new(pages: &'a Vec<Page>) -> Self {
+    fn new(pages: &'a [Page]) -> Self {
         Self { idx: 0, pages }
     }
 }
@@ -383,121 +2075,341 @@ impl<'a> Iterator for PageIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(page) = self.pages.get(self.idx) {
             self.idx += 1;
-            Some(page.file.as_str())
+            Some(page.file.as_ref())
         } else {
             None
         }
     }
 }
 
-pub struct ParseIter<'a> {
+/// Lazily lays out text, given as the char ids `T`, on a single line per paragraph.
+pub struct ParseIter<'a, T = CharIds<Chars<'a>>> {
     font: &'a BMFont,
-    line: Option<ParseLineIter<'a>>,
-    lines: LineIter<'a>,
+    options: LayoutOptions,
+    char_ids: T,
+    line_breaks: LineBreaks,
+    /// Whether the current line has any characters, for [EmptyLineAdvance].
+    line_has_chars: bool,
+    previous_char_id: Option<u32>,
+    x: i32,
     y: i32,
+    /// Pen position at the start of every line.
+    x_origin: i32,
+    /// Largest pen position at the end of a line so far.
+    max_x: i32,
+    line_count: u32,
+    /// Number of characters read from `char_ids`.
+    char_count: usize,
 }
 
-impl<'a> ParseIter<'a> {
-    fn new(font: &'a BMFont, lines: LineIter<'a>) -> Self {
+impl<'a, T> ParseIter<'a, T> {
+    fn new(font: &'a BMFont, char_ids: T, options: LayoutOptions) -> Self {
         Self {
             font,
-            line: None,
-            lines,
+            options,
+            char_ids,
+            line_breaks: LineBreaks::default(),
+            line_has_chars: false,
+            previous_char_id: None,
+            x: 0,
             y: 0,
+            x_origin: 0,
+            max_x: 0,
+            line_count: 1,
+            char_count: 0,
+        }
+    }
+
+    /// Returns the position of the pen, where the next glyph would be placed. Once the iterator is
+    /// exhausted this is the end of the laid out text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let mut char_positions = font.parse("Rust\nYou");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let mut char_positions = char_positions.unwrap();
+    /// char_positions.by_ref().for_each(drop);
+    /// assert_eq!(char_positions.pen(), (121, 80));
+    /// assert_eq!(char_positions.line_count(), 2);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pen(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    /// Returns the number of lines started so far.
+    pub fn line_count(&self) -> u32 {
+        self.line_count
+    }
+
+    /// Colors every glyph with the color `color` returns for the index of its character in the
+    /// text, e.g. for syntax highlighting. Characters outside the Basic Multilingual Plane are not
+    /// laid out and not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let char_positions = font.parse("Rust\nYou");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// let red = Color::rgb(255, 0, 0);
+    /// let colors: Vec<_> = char_positions
+    ///     .colored(|idx| if idx < 4 { red } else { Color::WHITE })
+    ///     .map(|char_position| char_position.color)
+    ///     .collect();
+    /// assert_eq!(colors, [red, red, red, red, Color::WHITE, Color::WHITE, Color::WHITE]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn colored<F>(self, color: F) -> Colored<'a, T, F>
+    where
+        F: FnMut(usize) -> Color,
+    {
+        Colored { inner: self, color }
+    }
+
+    /// Applies the [GlyphTransform] `transform` returns for every glyph to its screen rectangle,
+    /// e.g. to animate text by offsetting every glyph over time. `transform` is given the index of
+    /// the glyph among the laid out glyphs and its screen rectangle as laid out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let time = 0.5_f32;
+    /// let char_positions = font.parse("Rust");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// let wave = char_positions.transformed(|idx, _| {
+    ///     GlyphTransform::offset(0, (((time + idx as f32) * 2.0).sin() * 4.0) as i32)
+    /// });
+    /// assert_eq!(wave.count(), 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn transformed<F>(self, transform: F) -> Transformed<'a, T, F>
+    where
+        F: FnMut(usize, Rect) -> GlyphTransform,
+    {
+        Transformed {
+            inner: self,
+            transform,
+            glyph_count: 0,
         }
     }
 }
 
-impl<'a> Iterator for ParseIter<'a> {
+/// Iterator returned by [`ParseIter::colored()`].
+pub struct Colored<'a, T, F> {
+    inner: ParseIter<'a, T>,
+    color: F,
+}
+
+impl<'a, T, F> Colored<'a, T, F> {
+    /// Returns the position of the pen, see [`ParseIter::pen()`].
+    pub fn pen(&self) -> (i32, i32) {
+        self.inner.pen()
+    }
+}
+
+impl<'a, T, F> Iterator for Colored<'a, T, F>
+where
+    T: Iterator<Item = u32>,
+    F: FnMut(usize) -> Color,
+{
     type Item = CharPosition;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.line.is_none() {
-                if let Some(chars) = self.lines.next() {
-                    self.line = Some(ParseLineIter::new(&self.font, chars, self.y));
-                } else {
-                    return None;
-                }
-            }
+        let mut char_position = self.inner.next()?;
+        char_position.color = (self.color)(self.inner.char_count - 1);
+        Some(char_position)
+    }
+}
 
-            let line = self.line.as_mut().unwrap();
-            if let Some(char_position) = line.next() {
-                return Some(char_position);
-            }
+/// Iterator returned by [`ParseIter::transformed()`].
+pub struct Transformed<'a, T, F> {
+    inner: ParseIter<'a, T>,
+    transform: F,
+    glyph_count: usize,
+}
 
-            self.lines
-                .text
-                .replace(self.line.take().unwrap().chars.text);
-            match self.font.ordinate_orientation {
-                OrdinateOrientation::TopToBottom => self.y += self.font.line_height as i32,
-                OrdinateOrientation::BottomToTop => self.y -= self.font.line_height as i32,
-            }
-        }
+impl<'a, T, F> Transformed<'a, T, F> {
+    /// Returns the position of the pen, see [`ParseIter::pen()`].
+    pub fn pen(&self) -> (i32, i32) {
+        self.inner.pen()
     }
 }
 
-struct ParseLineIter<'a> {
-    font: &'a BMFont,
-    chars: CharIter<'a>,
-    kerning_values: KerningIter<'a>,
-    x: i32,
-    y: i32,
+impl<'a, T, F> Iterator for Transformed<'a, T, F>
+where
+    T: Iterator<Item = u32>,
+    F: FnMut(usize, Rect) -> GlyphTransform,
+{
+    type Item = CharPosition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut char_position = self.inner.next()?;
+        let transform = (self.transform)(self.glyph_count, char_position.screen_rect);
+        char_position.screen_rect = transform.apply(char_position.screen_rect);
+        self.glyph_count += 1;
+        Some(char_position)
+    }
 }
 
-impl<'a> ParseLineIter<'a> {
-    fn new(font: &'a BMFont, chars: CharIter<'a>, y: i32) -> Self {
-        Self {
-            font,
-            chars,
-            kerning_values: KerningIter::empty(&font.kerning_values),
-            x: 0,
-            y,
+impl<'a, T> ParseIter<'a, T>
+where
+    T: Iterator<Item = u32> + Clone,
+{
+    /// Lays out a copy of the text to measure it and returns the pen position at which the text
+    /// starts so that its `anchor` lies at `point`.
+    fn anchor_offset(&self, anchor: Anchor, point: (i32, i32)) -> (i32, i32) {
+        let font = self.font;
+        let mut measure = ParseIter::new(font, self.char_ids.clone(), self.options);
+        measure.by_ref().for_each(drop);
+
+        // Measured in i64, as the pen may have saturated at the bounds of i32.
+        let (base_height, line_height) = (font.base_height as i64, font.line_height as i64);
+        let coordinate_system = self.options.coordinate_system(font.ordinate_orientation);
+        let (top, bottom) = match coordinate_system.ordinate_orientation {
+            OrdinateOrientation::TopToBottom => (0, measure.y as i64 + line_height),
+            OrdinateOrientation::BottomToTop => {
+                (base_height, measure.y as i64 + base_height - line_height)
+            }
+        };
+        let dy = coordinate_system.glyph_dy(base_height as i32, line_height as i32) as i64;
+        let (horizontal, vertical) = anchor.halves();
+        let x = coordinate_system.dx(point.0) as i64 - measure.max_x as i64 * horizontal as i64 / 2;
+        let y = point.1 as i64 - top - dy - (bottom - top) * vertical as i64 / 2;
+        let clamp = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        (clamp(x), clamp(y))
+    }
+}
+
+impl<'a, T> ParseIter<'a, T>
+where
+    T: Iterator<Item = u32>,
+{
+    fn next_char(&mut self) -> Option<Cow<'a, Glyph>> {
+        let font = self.font;
+        loop {
+            let char_id = self.char_ids.next()?;
+            self.char_count += 1;
+            match self.line_breaks.classify(char_id) {
+                LineBreak::NewLine => {
+                    let mut line_offset = font.line_height as i32;
+                    if !self.line_has_chars {
+                        line_offset = self.options.empty_line_advance.advance(line_offset);
+                    }
+                    self.x = self.x_origin;
+                    self.line_count = self.line_count.saturating_add(1);
+                    let coordinate_system =
+                        self.options.coordinate_system(font.ordinate_orientation);
+                    self.y = match coordinate_system.ordinate_orientation {
+                        OrdinateOrientation::TopToBottom => self.y.saturating_add(line_offset),
+                        OrdinateOrientation::BottomToTop => self.y.saturating_sub(line_offset),
+                    };
+                    self.line_has_chars = false;
+                    self.previous_char_id = None;
+                }
+                LineBreak::Joined => (),
+                LineBreak::None => {
+                    self.line_has_chars = true;
+                    // Missing and unsupported characters are skipped, unless they have been
+                    // rejected before.
+                    if let Some(char) = font.find_glyph(char_id, &self.options) {
+                        return Some(char);
+                    }
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(char_id, "skipped character without glyph");
+                }
+            }
         }
     }
 }
 
-impl<'a> Iterator for ParseLineIter<'a> {
+impl<'a, T> Iterator for ParseIter<'a, T>
+where
+    T: Iterator<Item = u32>,
+{
     type Item = CharPosition;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.chars.next() {
-            Some(char) => {
-                let kerning_value = self
-                    .kerning_values
-                    .find(|k| k.second_char_id == char.id)
-                    .map(|k| k.value)
-                    .unwrap_or(0);
-                let page_rect = Rect {
-                    x: char.x as i32,
-                    y: char.y as i32,
-                    width: char.width,
-                    height: char.height,
-                };
-                let screen_x = self.x + char.xoffset + kerning_value;
-                let screen_y = match self.font.ordinate_orientation {
-                    OrdinateOrientation::BottomToTop => {
-                        self.y + self.font.base_height as i32 - char.yoffset - char.height as i32
-                    }
-                    OrdinateOrientation::TopToBottom => self.y + char.yoffset,
-                };
-                let screen_rect = Rect {
-                    x: screen_x,
-                    y: screen_y,
-                    width: char.width,
-                    height: char.height,
-                };
-                let char_position = CharPosition {
-                    page_rect,
-                    screen_rect,
-                    page_index: char.page_index,
-                };
-                self.x += char.xadvance + kerning_value;
-                self.kerning_values = self.font.find_kerning_values(char.id);
-
-                Some(char_position)
+        loop {
+            let char = self.next_char()?;
+            let kerning_value = self
+                .previous_char_id
+                .map(|previous_char_id| self.font.kerning(previous_char_id, char.id))
+                .unwrap_or(0);
+            let (xadvance, kerning_value, cell_offset) =
+                self.options.pixel_advance(char.xadvance, kerning_value);
+            let font = self.font;
+            let page_rect = self.options.page_rect(&char, font.page_height);
+            let coordinate_system = self.options.coordinate_system(font.ordinate_orientation);
+            let screen_x = self
+                .x
+                .saturating_add(cell_offset)
+                .saturating_add(char.xoffset)
+                .saturating_add(kerning_value);
+            let screen_x = coordinate_system.glyph_pixel_x(screen_x, char.width);
+            let screen_y = match coordinate_system.ordinate_orientation {
+                OrdinateOrientation::BottomToTop => self
+                    .y
+                    .saturating_add(font.base_height as i32)
+                    .saturating_sub(char.yoffset)
+                    .saturating_sub(char.height as i32),
+                OrdinateOrientation::TopToBottom => self.y.saturating_add(char.yoffset),
+            }
+            .saturating_add(
+                coordinate_system.glyph_dy(font.base_height as i32, font.line_height as i32),
+            );
+            let screen_rect = Rect {
+                x: screen_x,
+                y: screen_y,
+                width: char.width,
+                height: char.height,
+            };
+            let mut char_position = CharPosition {
+                page_rect,
+                screen_rect,
+                page_index: char.page_index,
+                chnl: char.chnl,
+                xadvance,
+                kerning: kerning_value,
+                subpixel: (0, 0),
+                color: Color::default(),
+                clipped: false,
+            };
+            self.x = self
+                .x
+                .saturating_add(xadvance)
+                .saturating_add(kerning_value);
+            self.max_x = self.max_x.max(self.x);
+            self.previous_char_id = Some(char.id);
+
+            if self.options.empty_glyph_policy == EmptyGlyphPolicy::Skip
+                && char_position.page_rect.is_empty()
+            {
+                continue;
             }
-            _ => None,
+            if !self.options.clip(&mut char_position) {
+                continue;
+            }
+
+            return Some(char_position);
         }
     }
 }
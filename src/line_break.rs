@@ -0,0 +1,42 @@
+/// Returns `true` for the characters which break lines: `'\n'`, `'\r'`, U+2028 LINE SEPARATOR and
+/// U+2029 PARAGRAPH SEPARATOR.
+pub fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Role of a character in breaking lines.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineBreak {
+    /// The character does not break the line.
+    None,
+    /// The character starts a new line.
+    NewLine,
+    /// The `'\n'` of a `"\r\n"` pair, whose `'\r'` started a new line already.
+    Joined,
+}
+
+/// Classifies the characters of a text in order, so that `"\r\n"` breaks the line once.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LineBreaks {
+    after_carriage_return: bool,
+}
+
+impl LineBreaks {
+    /// Starts classifying after `previous`, the character before the text, if any.
+    pub fn after(previous: Option<char>) -> Self {
+        LineBreaks {
+            after_carriage_return: previous == Some('\r'),
+        }
+    }
+
+    /// Classifies the character with the code point or UTF-16 unit `c`.
+    pub fn classify(&mut self, c: u32) -> LineBreak {
+        let after_carriage_return = self.after_carriage_return;
+        self.after_carriage_return = c == '\r' as u32;
+        match std::char::from_u32(c) {
+            Some('\n') if after_carriage_return => LineBreak::Joined,
+            Some(c) if is_line_break(c) => LineBreak::NewLine,
+            _ => LineBreak::None,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::ops::Range;
+
+/// How a line laid out by a [`Layout`](crate::Layout) ends.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineEnding {
+    /// The line is ended by a line break in the text, such as `'\n'`, so the next line starts a
+    /// new logical line.
+    Hard,
+    /// The line is ended by wrapping at [`Layout::max_width()`](crate::Layout::max_width), so
+    /// the next line continues the same logical line.
+    Soft,
+    /// The line is the last one.
+    End,
+}
+
+/// A visual line laid out by a [`Layout`](crate::Layout).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LineMetrics {
+    /// Indices of the glyphs of the line in [`Layout::char_positions()`](crate::Layout::char_positions).
+    pub glyphs: Range<usize>,
+    /// Position of the pen at the start of the line.
+    pub y: i32,
+    /// Position of the pen at the end of the line, including trailing whitespace.
+    pub width: i32,
+    pub ending: LineEnding,
+}
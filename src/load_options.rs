@@ -0,0 +1,95 @@
+/// Policy applied when a font declares the same char id more than once.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DuplicateCharPolicy {
+    /// Keep the first declaration and ignore the later ones.
+    #[default]
+    KeepFirst,
+    /// Keep the last declaration, replacing the earlier ones.
+    KeepLast,
+    /// Fail with [`ConfigParseError::DuplicateChar`](crate::ConfigParseError::DuplicateChar).
+    Error,
+}
+
+/// How kerning values are looked up during layout.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum KerningLookup {
+    /// Binary search the sorted kerning values, which needs no extra memory.
+    #[default]
+    BinarySearch,
+    /// Build a hash map of kerning pairs on load, making each lookup O(1).
+    HashMap,
+}
+
+/// Policy applied when a font file is cut off, such as by an interrupted download.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TruncationPolicy {
+    /// Fail if the last line was cut off in the middle.
+    #[default]
+    Error,
+    /// Drop the cut off last line and keep the characters and kerning values loaded so far.
+    /// [`BMFont::is_truncated()`](crate::BMFont::is_truncated) reports whether anything is missing.
+    Recover,
+}
+
+/// Tool a font was exported with, which selects the deviations from the format written by the
+/// AngelCode BMFont tool that are tolerated while loading it.
+///
+/// Unknown components, components in any order and missing or wrong `chars` and `kernings` counts
+/// are tolerated regardless of the exporter.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Exporter {
+    /// Tolerate no deviations.
+    #[default]
+    AngelCode,
+    /// Tolerate numbers written in quotes, such as `id="32"`.
+    Littera,
+    /// Tolerate numbers written in quotes and a missing "info" section.
+    ShoeBox,
+    /// Tolerate numbers written with a fraction, such as `xoffset=-0.5`, which are rounded.
+    GlyphDesigner,
+    /// Tolerate numbers written with a fraction and a missing "info" section.
+    BmGlyph,
+}
+
+impl Exporter {
+    pub(crate) fn quirks(self) -> Quirks {
+        match self {
+            Exporter::AngelCode => Quirks::default(),
+            Exporter::Littera => Quirks {
+                quoted_numbers: true,
+                ..Quirks::default()
+            },
+            Exporter::ShoeBox => Quirks {
+                quoted_numbers: true,
+                missing_info: true,
+                ..Quirks::default()
+            },
+            Exporter::GlyphDesigner => Quirks {
+                fractional_numbers: true,
+                ..Quirks::default()
+            },
+            Exporter::BmGlyph => Quirks {
+                fractional_numbers: true,
+                missing_info: true,
+                ..Quirks::default()
+            },
+        }
+    }
+}
+
+/// Deviations from the AngelCode BMFont format tolerated for an [Exporter].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Quirks {
+    pub quoted_numbers: bool,
+    pub fractional_numbers: bool,
+    pub missing_info: bool,
+}
+
+/// Options controlling how [`BMFont::with_options()`](crate::BMFont::with_options) loads a font.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LoadOptions {
+    pub duplicate_char_policy: DuplicateCharPolicy,
+    pub kerning_lookup: KerningLookup,
+    pub truncation_policy: TruncationPolicy,
+    pub exporter: Exporter,
+}
@@ -0,0 +1,11 @@
+/// What [`BMFont::parse_with_fallback()`](crate::BMFont::parse_with_fallback) does with a
+/// character the font lacks, as chosen by its callback.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MissingCharAction {
+    /// Lay out nothing for the character.
+    Skip,
+    /// Lay out the given character instead, e.g. `'?'`. It is skipped if the font lacks it too.
+    Substitute(char),
+    /// Stop and return the character as an error.
+    Abort,
+}
@@ -0,0 +1,46 @@
+use super::{BMFont, ConfigParseError, Error, LoadOptions, OrdinateOrientation};
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+impl BMFont {
+    /// Constructs a new [BMFont] from the file at `path`, parsing it straight from a memory map
+    /// instead of reading it into a `String` first, e.g. for fonts with tens of thousands of
+    /// characters. The file is unmapped once the font is loaded.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, e.g. by another process, while the font is loaded, see
+    /// [`Mmap::map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let options = LoadOptions::default();
+    /// let font = unsafe {
+    ///     BMFont::open_mapped("font.fnt", OrdinateOrientation::TopToBottom, &options)?
+    /// };
+    /// assert_eq!(font.line_height(), 80);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn open_mapped<P>(
+        path: P,
+        ordinate_orientation: OrdinateOrientation,
+        options: &LoadOptions,
+    ) -> Result<BMFont, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        let map = Mmap::map(&file)?;
+        let content = std::str::from_utf8(&map).map_err(ConfigParseError::InvalidUtf8)?;
+        Ok(BMFont::from_str_with_options(
+            content,
+            ordinate_orientation,
+            options,
+        )?)
+    }
+}
@@ -0,0 +1,181 @@
+use super::{
+    BMFont, DistanceField, DistanceFieldType, Glyph, KerningValue, OrdinateOrientation, Page,
+};
+use std::borrow::Cow;
+
+/// Layout of the JSON written by msdf-atlas-gen, with metrics in ems and bounds in pixels.
+#[derive(Deserialize)]
+struct MsdfFont {
+    atlas: Atlas,
+    metrics: Metrics,
+    glyphs: Vec<MsdfGlyph>,
+    #[serde(default)]
+    kerning: Vec<Kerning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Atlas {
+    #[serde(rename = "type")]
+    atlas_type: String,
+    distance_range: Option<f32>,
+    /// Size of an em in pixels.
+    size: f32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    y_origin: YOrigin,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum YOrigin {
+    #[default]
+    Bottom,
+    Top,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metrics {
+    line_height: f32,
+    ascender: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfGlyph {
+    unicode: u32,
+    advance: f32,
+    plane_bounds: Option<Bounds>,
+    atlas_bounds: Option<Bounds>,
+}
+
+#[derive(Deserialize)]
+struct Bounds {
+    left: f32,
+    bottom: f32,
+    right: f32,
+    top: f32,
+}
+
+#[derive(Deserialize)]
+struct Kerning {
+    unicode1: u32,
+    unicode2: u32,
+    advance: f32,
+}
+
+impl BMFont {
+    /// Constructs a new [BMFont] from the JSON written by msdf-atlas-gen with `-json`, whose
+    /// atlas is the image at `page_file`, e.g. to lay out multi-channel signed distance field
+    /// fonts.
+    ///
+    /// The metrics, given in ems, are converted to pixels at the size of an em in the atlas and
+    /// rounded like the atlas bounds of the glyphs, which are grown to whole pixels, and the
+    /// distance range, see [BMFont::distance_field()].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// let json = r#"{
+    ///     "atlas": { "type": "msdf", "distanceRange": 4, "size": 32, "width": 64, "height": 64, "yOrigin": "bottom" },
+    ///     "metrics": { "emSize": 1, "lineHeight": 1.25, "ascender": 0.9, "descender": -0.25 },
+    ///     "glyphs": [
+    ///         { "unicode": 32, "advance": 0.25 },
+    ///         {
+    ///             "unicode": 65, "advance": 0.625,
+    ///             "planeBounds": { "left": 0, "bottom": 0, "right": 0.625, "top": 0.75 },
+    ///             "atlasBounds": { "left": 0.5, "bottom": 0.5, "right": 20.5, "top": 24.5 }
+    ///         }
+    ///     ],
+    ///     "kerning": []
+    /// }"#;
+    /// let font = BMFont::from_msdf_json(json, "atlas.png", OrdinateOrientation::TopToBottom).unwrap();
+    /// assert_eq!(font.line_height(), 40);
+    /// assert_eq!(font.width_of("A A"), 48);
+    /// assert_eq!(font.distance_field().unwrap().distance_range, 4);
+    /// ```
+    pub fn from_msdf_json(
+        json: &str,
+        page_file: &str,
+        ordinate_orientation: OrdinateOrientation,
+    ) -> serde_json::Result<BMFont> {
+        let font: MsdfFont = serde_json::from_str(json)?;
+        let atlas = &font.atlas;
+        let em = |value: f32| (value * atlas.size).round() as i32;
+        // Flip vertical values so that they point up, as with the bottom origin.
+        let up = match atlas.y_origin {
+            YOrigin::Bottom => 1.0,
+            YOrigin::Top => -1.0,
+        };
+        let ascender = up * font.metrics.ascender;
+        let distance_field =
+            DistanceFieldType::from_name(&atlas.atlas_type).and_then(|field_type| {
+                Some(DistanceField {
+                    field_type,
+                    distance_range: atlas.distance_range?.round() as u32,
+                })
+            });
+
+        let mut characters: Vec<Glyph> = Vec::with_capacity(font.glyphs.len());
+        for glyph in &font.glyphs {
+            let mut char = Glyph::space(em(glyph.advance));
+            char.id = glyph.unicode;
+            if let (Some(plane), Some(bounds)) = (&glyph.plane_bounds, &glyph.atlas_bounds) {
+                let (min_y, max_y) = match atlas.y_origin {
+                    YOrigin::Bottom => (
+                        atlas.height as f32 - bounds.top,
+                        atlas.height as f32 - bounds.bottom,
+                    ),
+                    YOrigin::Top => (bounds.top, bounds.bottom),
+                };
+                let (x, y) = (bounds.left.floor(), min_y.floor());
+                char.x = x as u32;
+                char.y = y as u32;
+                char.width = (bounds.right.ceil() - x) as u32;
+                char.height = (max_y.ceil() - y) as u32;
+                char.xoffset = em(plane.left);
+                char.yoffset = em(ascender - up * plane.top);
+            }
+            // Keep the first of duplicate glyphs, as with DuplicateCharPolicy::KeepFirst.
+            if let Err(idx) = characters.binary_search_by(|probe| probe.id.cmp(&char.id)) {
+                characters.insert(idx, char);
+            }
+        }
+
+        let mut kerning_values: Vec<KerningValue> = Vec::with_capacity(font.kerning.len());
+        for kerning in &font.kerning {
+            let kerning = KerningValue {
+                first_char_id: kerning.unicode1,
+                second_char_id: kerning.unicode2,
+                value: em(kerning.advance),
+            };
+            match kerning_values
+                .binary_search_by(|probe| probe.first_char_id.cmp(&kerning.first_char_id))
+            {
+                Err(idx) | Ok(idx) => kerning_values.insert(idx, kerning),
+            }
+        }
+
+        Ok(BMFont {
+            base_height: em(ascender).max(0) as u32,
+            line_height: em(font.metrics.line_height).max(0) as u32,
+            page_width: atlas.width,
+            page_height: atlas.height,
+            size: Some(atlas.size.round() as u32),
+            distance_field,
+            characters: Cow::Owned(characters),
+            kerning_values: Cow::Owned(kerning_values),
+            kerning_map: None,
+            pages: Cow::Owned(vec![Page {
+                id: 0,
+                file: Cow::Owned(page_file.to_string()),
+            }]),
+            ordinate_orientation,
+            truncated: false,
+            fingerprint: Default::default(),
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use super::{BMFont, CharIds, LayoutOptions, Parse};
+use std::str::Chars;
+use unicode_normalization::{Recompositions, UnicodeNormalization};
+
+impl BMFont {
+    /// Lays out `s` with the default [LayoutOptions], like [BMFont::parse()], but normalizes it to
+    /// NFC first, so that decomposed characters, such as `e` followed by a combining acute accent,
+    /// use the glyphs of their precomposed characters, such as `é`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let text = std::fs::read_to_string("font.fnt")?
+    ///     + "\nchar id=233 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0";
+    /// let font = BMFont::from_str_with_options(
+    ///     &text,
+    ///     OrdinateOrientation::TopToBottom,
+    ///     &LoadOptions::default(),
+    /// )?;
+    /// let char_positions = font.parse_nfc("Caf\u{65}\u{301}");
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.count(), 4);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parse_nfc<'s>(&'s self, s: &'s str) -> Parse<'s, CharIds<Recompositions<Chars<'s>>>> {
+        self.parse_nfc_with(s, &LayoutOptions::default())
+    }
+
+    /// Lays out `s` normalized to NFC with the given [LayoutOptions], like
+    /// [BMFont::parse_with()].
+    pub fn parse_nfc_with<'s>(
+        &'s self,
+        s: &'s str,
+        options: &LayoutOptions,
+    ) -> Parse<'s, CharIds<Recompositions<Chars<'s>>>> {
+        self.parse_chars_with(s.nfc(), options)
+    }
+}
@@ -0,0 +1,13 @@
+/// Where the text of a [`Layout`](crate::Layout) is cut off by
+/// [`Layout::set_max_lines()`](crate::Layout::set_max_lines), see
+/// [`Layout::overflow()`](crate::Layout::overflow).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Overflow {
+    /// Index of the first hidden line in [`Layout::lines()`](crate::Layout::lines).
+    pub line: usize,
+    /// Index of the first hidden glyph in
+    /// [`Layout::char_positions()`](crate::Layout::char_positions).
+    pub glyph: usize,
+    /// Byte index of the first hidden character in [`Layout::text()`](crate::Layout::text).
+    pub text: usize,
+}
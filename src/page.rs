@@ -1,6 +1,6 @@
+use super::load_options::Quirks;
 use super::{utils, ConfigParseError};
-use std::fmt::Debug;
-use std::str::FromStr;
+use std::borrow::Cow;
 
 const SECTION_NAME: &str = "page";
 
@@ -9,28 +9,19 @@ const SECTION_NAME: &str = "page";
 #[cfg_attr(feature = "serde_json", derive(Eq, PartialEq))]
 pub struct Page {
     pub id: u32,
-    pub file: String,
+    pub file: Cow<'static, str>,
 }
 
 impl Page {
     pub fn new(s: &str) -> Result<Page, ConfigParseError> {
-        let mut components = s.split_whitespace();
-        let section_name = components.next();
-        assert_eq!(
-            section_name.expect("Page cannot be created from empty string"),
-            SECTION_NAME
-        );
-        let id: u32 = extract_component_value(components.next(), "id")?;
-        let file: String = extract_component_value(components.next(), "file")?;
-        let file = file.trim_matches('"').to_string();
-        Ok(Page { id, file })
+        Page::with_quirks(s, Quirks::default())
     }
-}
 
-fn extract_component_value<T>(s: Option<&str>, component: &str) -> Result<T, ConfigParseError>
-where
-    T: FromStr,
-    T::Err: Debug,
-{
-    utils::extract_component_value(s, SECTION_NAME, component)
+    pub(crate) fn with_quirks(s: &str, quirks: Quirks) -> Result<Page, ConfigParseError> {
+        let components = utils::section_components(s, SECTION_NAME, quirks)?;
+        let id: u32 = components.value("id")?;
+        let file: String = components.value("file")?;
+        let file = Cow::Owned(utils::unquote(&file).to_string());
+        Ok(Page { id, file })
+    }
 }
@@ -0,0 +1,113 @@
+use super::CharPosition;
+use std::iter::FromIterator;
+use std::ops::Range;
+
+/// Laid out glyphs grouped by [`CharPosition::page_index`], so that a renderer binds the texture
+/// of each page once and draws all of its glyphs in one batch, however the pages interleave in
+/// the text.
+///
+/// Glyphs keep the order they were laid out in within their page, and pages come in ascending
+/// order. Keeping the batches around and grouping again with [`PageBatches::group()`] reuses
+/// their allocations.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let char_positions = font.parse("Rust");
+/// # #[cfg(feature = "parse-error")]
+/// # let char_positions = char_positions.unwrap();
+/// let batches: PageBatches = char_positions.collect();
+/// for (page_index, char_positions) in batches.iter() {
+///     // Bind the texture of the page and draw its glyphs.
+///     assert_eq!(page_index, 0);
+///     assert_eq!(char_positions.len(), 4);
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PageBatches {
+    char_positions: Vec<CharPosition>,
+    pages: Vec<(u32, Range<usize>)>,
+}
+
+impl PageBatches {
+    /// Creates empty batches.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Groups `char_positions` by page, replacing the previous glyphs.
+    pub fn group<I>(&mut self, char_positions: I)
+    where
+        I: IntoIterator<Item = CharPosition>,
+    {
+        self.clear();
+        self.char_positions.extend(char_positions);
+        // The sort is stable, so glyphs keep their order within a page.
+        self.char_positions
+            .sort_by_key(|char_position| char_position.page_index);
+        let mut start = 0;
+        while start < self.char_positions.len() {
+            let page_index = self.char_positions[start].page_index;
+            let len = self.char_positions[start..]
+                .iter()
+                .take_while(|char_position| char_position.page_index == page_index)
+                .count();
+            self.pages.push((page_index, start..start + len));
+            start += len;
+        }
+    }
+
+    /// Returns the glyphs of all pages, one page after another.
+    pub fn char_positions(&self) -> &[CharPosition] {
+        &self.char_positions
+    }
+
+    /// Returns the glyphs on the page `page_index`, if any.
+    pub fn get(&self, page_index: u32) -> Option<&[CharPosition]> {
+        self.pages
+            .binary_search_by_key(&page_index, |&(page_index, _)| page_index)
+            .ok()
+            .map(|idx| &self.char_positions[self.pages[idx].1.clone()])
+    }
+
+    /// Returns an iterator over the index of each page with glyphs together with its glyphs, in
+    /// ascending order of pages.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &[CharPosition])> + '_ {
+        self.pages
+            .iter()
+            .map(move |(page_index, range)| (*page_index, &self.char_positions[range.clone()]))
+    }
+
+    /// Returns the number of pages with glyphs, which is the number of batches to draw.
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns `true` if there are no glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Removes all glyphs, keeping the allocated buffers.
+    pub fn clear(&mut self) {
+        self.char_positions.clear();
+        self.pages.clear();
+    }
+}
+
+impl FromIterator<CharPosition> for PageBatches {
+    fn from_iter<I>(char_positions: I) -> Self
+    where
+        I: IntoIterator<Item = CharPosition>,
+    {
+        let mut batches = PageBatches::new();
+        batches.group(char_positions);
+        batches
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::Result;
+use std::path::PathBuf;
+
+/// Resolves the page files named by a font into their contents, e.g. from a pack file, a zip
+/// archive or over the network, for [`BMFont::load_pages()`](crate::BMFont::load_pages).
+///
+/// It is implemented for closures taking the file name.
+pub trait PageSource {
+    /// Returns the contents of the page file `name`, as given by the font.
+    fn read_page(&mut self, name: &str) -> Result<Vec<u8>>;
+}
+
+impl<F> PageSource for F
+where
+    F: FnMut(&str) -> Result<Vec<u8>>,
+{
+    fn read_page(&mut self, name: &str) -> Result<Vec<u8>> {
+        self(name)
+    }
+}
+
+/// A [PageSource] which reads the page files from a directory, usually the one containing the
+/// font file, as the file names are relative to it.
+#[derive(Clone, Debug)]
+pub struct DirectorySource {
+    directory: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new<P>(directory: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        DirectorySource {
+            directory: directory.into(),
+        }
+    }
+}
+
+impl PageSource for DirectorySource {
+    fn read_page(&mut self, name: &str) -> Result<Vec<u8>> {
+        std::fs::read(self.directory.join(name))
+    }
+}
@@ -0,0 +1,117 @@
+use super::{BMFont, CharIds, CharPosition, LayoutOptions, ParseIter};
+use rayon::prelude::*;
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<Vec<CharPosition>, StringParseError>`] _or_ [`Vec<CharPosition>`],
+/// returned by [`BMFont::par_parse_with()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type ParParse = Result<Vec<CharPosition>, StringParseError>;
+
+/// Alias of either [`Result<Vec<CharPosition>, StringParseError>`] _or_ [`Vec<CharPosition>`],
+/// returned by [`BMFont::par_parse_with()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type ParParse = Vec<CharPosition>;
+
+/// Smallest number of bytes laid out by one task, so short texts are not split.
+const MIN_CHUNK_LEN: usize = 16 * 1024;
+
+impl BMFont {
+    /// Lays out `s` like [BMFont::parse_with()], but splits it at line breaks into chunks which
+    /// are laid out in parallel with rayon, e.g. for whole documents.
+    ///
+    /// [`LayoutOptions::anchor`] is not supported and ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let file = std::fs::File::open("font.fnt")?;
+    /// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+    /// let credits = "Rust\nYou\n".repeat(10_000);
+    /// let char_positions = font.par_parse_with(&credits, &LayoutOptions::default());
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// assert_eq!(char_positions.len(), 70_000);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn par_parse_with(&self, s: &str, options: &LayoutOptions) -> ParParse {
+        #[cfg(feature = "parse-error")]
+        self.check_characters(s.chars(), options)?;
+
+        // Chunks are clipped once moved to their lines.
+        let chunk_options = LayoutOptions {
+            anchor: None,
+            clip_rect: None,
+            ..*options
+        };
+        let chunk_len = (s.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_LEN);
+        let chunks = split_at_line_breaks(s, chunk_len);
+        let laid_out_chunks = chunks
+            .par_iter()
+            .map(|chunk| {
+                let mut char_positions =
+                    ParseIter::new(self, CharIds::new(chunk.chars()), chunk_options);
+                let laid_out = char_positions.by_ref().collect::<Vec<_>>();
+                (laid_out, char_positions.y)
+            })
+            .collect::<Vec<_>>();
+
+        // Every chunk but the last ends with a line break, so it ends at the start of the line
+        // where the next chunk begins.
+        let len = laid_out_chunks.iter().map(|(chunk, _)| chunk.len()).sum();
+        let mut char_positions = Vec::with_capacity(len);
+        let mut y = 0;
+        for (chunk, height) in laid_out_chunks {
+            char_positions.extend(chunk.into_iter().filter_map(|mut char_position| {
+                char_position.screen_rect.y += y;
+                options.clip(&mut char_position).then_some(char_position)
+            }));
+            y += height;
+        }
+
+        #[cfg(feature = "parse-error")]
+        {
+            Ok(char_positions)
+        }
+
+        #[cfg(not(feature = "parse-error"))]
+        {
+            char_positions
+        }
+    }
+}
+
+/// Splits `s` into chunks of at least `chunk_len` bytes which end right after a `'\n'`, so that
+/// no chunk but the first starts within a line or a `"\r\n"` pair.
+fn split_at_line_breaks(s: &str, chunk_len: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+    while rest.len() > chunk_len {
+        let mut end = chunk_len;
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        match rest[end..].find('\n') {
+            Some(idx) => {
+                let (chunk, tail) = rest.split_at(end + idx + 1);
+                chunks.push(chunk);
+                rest = tail;
+            }
+            None => break,
+        }
+    }
+    chunks.push(rest);
+    chunks
+}
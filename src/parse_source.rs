@@ -0,0 +1,76 @@
+use super::char_id;
+
+/// Iterator adapter turning characters into the UTF-16 based char ids of a font, which
+/// [ParseIter](crate::ParseIter) lays out. Characters outside of the Basic Multilingual Plane have
+/// no char id and are skipped.
+#[derive(Clone, Debug)]
+pub struct CharIds<I> {
+    chars: I,
+}
+
+impl<I> CharIds<I> {
+    pub(crate) fn new(chars: I) -> Self {
+        CharIds { chars }
+    }
+}
+
+impl<I> Iterator for CharIds<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chars.by_ref().find_map(char_id)
+    }
+}
+
+/// Iterator over the characters of an owned `String`, used by
+/// [`BMFont::parse_owned()`](crate::BMFont::parse_owned).
+#[derive(Clone, Debug)]
+pub struct OwnedChars {
+    text: String,
+    idx: usize,
+}
+
+impl OwnedChars {
+    pub(crate) fn new(text: String) -> Self {
+        OwnedChars { text, idx: 0 }
+    }
+}
+
+impl Iterator for OwnedChars {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.text[self.idx..].chars().next()?;
+        self.idx += c.len_utf8();
+        Some(c)
+    }
+}
+
+/// Iterator over UTF-16 code units, which are the char ids of a font as they are, skipping
+/// surrogates, used by [`BMFont::parse_utf16()`](crate::BMFont::parse_utf16).
+#[derive(Clone, Debug)]
+pub struct Utf16Units<'a> {
+    units: std::slice::Iter<'a, u16>,
+}
+
+impl<'a> Utf16Units<'a> {
+    pub(crate) fn new(units: &'a [u16]) -> Self {
+        Utf16Units {
+            units: units.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Utf16Units<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Surrogates, paired or not, encode no character of the Basic Multilingual Plane.
+        self.units
+            .find(|&&unit| !(0xd800..=0xdfff).contains(&unit))
+            .map(|&unit| u32::from(unit))
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{CharPosition, StringParseError};
+
+/// Best-effort layout returned by [`BMFont::parse_partial()`](crate::BMFont::parse_partial).
+#[derive(Debug)]
+pub struct PartialParse {
+    /// Glyphs of all characters the font has.
+    pub char_positions: Vec<CharPosition>,
+    /// The skipped missing and unsupported characters, if any.
+    pub error: Option<StringParseError>,
+}
+
+impl PartialParse {
+    /// Returns `true` if no character was skipped.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+}
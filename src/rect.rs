@@ -1,5 +1,5 @@
 /// Rectangle
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Rect {
     /// Minimum x
     pub x: i32,
@@ -10,11 +10,152 @@ pub struct Rect {
 }
 
 impl Rect {
+    /// Returns the maximum x, saturating at `i32::MAX`.
     pub fn max_x(&self) -> i32 {
-        self.x + self.width as i32
+        self.x.saturating_add_unsigned(self.width)
     }
 
+    /// Returns the maximum y, saturating at `i32::MAX`.
     pub fn max_y(&self) -> i32 {
-        self.y + self.height as i32
+        self.y.saturating_add_unsigned(self.height)
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns `true` if the point lies inside the rectangle, minimum edges included and maximum
+    /// edges excluded.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.max_x() && y >= self.y && y < self.max_y()
+    }
+
+    /// Returns `true` if the rectangles share some area.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the area shared by the rectangles, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let max_x = self.max_x().min(other.max_x());
+        let max_y = self.max_y().min(other.max_y());
+        if x < max_x && y < max_y {
+            Some(Rect {
+                x,
+                y,
+                width: max_x.abs_diff(x),
+                height: max_y.abs_diff(y),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle containing both rectangles. An empty rectangle is ignored,
+    /// so that bounds can be accumulated starting from an empty rectangle.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            width: self.max_x().max(other.max_x()).abs_diff(x),
+            height: self.max_y().max(other.max_y()).abs_diff(y),
+        }
+    }
+
+    /// Returns the corner at the minimum x and y.
+    pub fn min_corner(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    /// Returns the corner at the maximum x and y.
+    pub fn max_corner(&self) -> (i32, i32) {
+        (self.max_x(), self.max_y())
+    }
+
+    /// Returns the four corners, going from the minimum corner along the x axis first.
+    pub fn corners(&self) -> [(i32, i32); 4] {
+        let (x, y) = self.min_corner();
+        let (max_x, max_y) = self.max_corner();
+        [(x, y), (max_x, y), (max_x, max_y), (x, max_y)]
+    }
+
+    /// Returns `[x, y, width, height]` as `f32`, such as for texture coordinates and vertices.
+    pub fn to_f32(&self) -> [f32; 4] {
+        [
+            self.x as f32,
+            self.y as f32,
+            self.width as f32,
+            self.height as f32,
+        ]
+    }
+
+    /// Converts a rectangle in logical units into device pixels for the content `scale` factor,
+    /// e.g. `1.5` or `2.0` on HiDPI displays.
+    ///
+    /// The edges are rounded to the nearest pixel rather than the size, so rectangles which touch
+    /// in logical units still touch, and neither overlap nor leave gaps, in device pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::Rect;
+    /// let left = Rect { x: 0, y: 0, width: 3, height: 3 };
+    /// let right = Rect { x: 3, y: 0, width: 3, height: 3 };
+    /// let (left, right) = (left.to_device(1.5), right.to_device(1.5));
+    /// assert_eq!(left.max_x(), right.x);
+    /// assert_eq!(left.width + right.width, 9);
+    /// ```
+    pub fn to_device(&self, scale: f32) -> Rect {
+        Rect::from_edges(
+            self.x as f32 * scale,
+            self.y as f32 * scale,
+            self.max_x() as f32 * scale,
+            self.max_y() as f32 * scale,
+        )
+    }
+
+    /// Returns the rectangle between the edges rounded to the nearest pixel.
+    pub(crate) fn from_edges(x: f32, y: f32, max_x: f32, max_y: f32) -> Rect {
+        let (x, y) = (x.round() as i32, y.round() as i32);
+        Rect {
+            x,
+            y,
+            width: (max_x.round() as i32).saturating_sub(x).max(0) as u32,
+            height: (max_y.round() as i32).saturating_sub(y).max(0) as u32,
+        }
+    }
+
+    /// Returns the rectangle moved by `dx` and `dy`, saturating at the bounds of `i32`.
+    pub fn translate(&self, dx: i32, dy: i32) -> Rect {
+        Rect {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+            ..*self
+        }
+    }
+}
+
+impl From<Rect> for [i32; 4] {
+    /// Converts into `[x, y, width, height]`.
+    fn from(rect: Rect) -> Self {
+        [rect.x, rect.y, rect.width as i32, rect.height as i32]
+    }
+}
+
+impl From<Rect> for (i32, i32, u32, u32) {
+    /// Converts into `(x, y, width, height)`.
+    fn from(rect: Rect) -> Self {
+        (rect.x, rect.y, rect.width, rect.height)
     }
 }
@@ -1,88 +1,103 @@
-use super::{ConfigParseError, Error};
-use std::io::Read;
+use super::load_options::Quirks;
+use super::{utils, ConfigParseError};
 
 #[derive(Clone, Debug)]
-pub struct Sections {
-    pub common_section: String,
-    pub page_sections: Vec<String>,
-    pub char_sections: Vec<String>,
-    pub kerning_sections: Vec<String>,
+pub struct Sections<'a> {
+    /// The "info" section, which is only missing if the quirks tolerate it.
+    pub info_section: Option<&'a str>,
+    /// The "distanceField" section written for distance field fonts, e.g. by msdf-bmfont-xml.
+    pub distance_field_section: Option<&'a str>,
+    pub common_section: &'a str,
+    pub page_sections: Vec<&'a str>,
+    pub char_sections: Vec<&'a str>,
+    pub kerning_sections: Vec<&'a str>,
+    /// Counts declared by the "chars" and "kernings" sections.
+    pub char_count: Option<usize>,
+    pub kerning_count: Option<usize>,
+    /// The last line, if it is not terminated by a line break and so may have been cut off.
+    pub unterminated_line: Option<&'a str>,
 }
 
-impl Sections {
-    pub fn new<R>(mut source: R) -> Result<Sections, Error>
-    where
-        R: Read,
-    {
-        // Load the entire file into a String.
-        let mut content = String::new();
-        source.read_to_string(&mut content)?;
+impl<'a> Sections<'a> {
+    pub fn new(content: &'a str, quirks: Quirks) -> Result<Sections<'a>, ConfigParseError> {
+        // Ignore the UTF-8 byte order mark and carriage returns left by Windows line endings.
+        let content = content.trim_start_matches('\u{feff}');
+        let lines = content.lines().map(|l| l.trim_end_matches('\r'));
+        let unterminated_line = if content.ends_with('\n') {
+            None
+        } else {
+            lines.clone().next_back()
+        };
+
+        // Classify the lines by their keyword, so the sections may come in any order. Blank lines
+        // and unknown keywords are skipped, the "chars" and "kernings" counts are only recorded.
+        let mut info_section = None;
+        let mut distance_field_section = None;
+        let mut has_char_count_section = false;
+        let mut char_count = None;
+        let mut kerning_count = None;
+        let mut common_section = None;
+        let mut page_sections = Vec::new();
+        let mut char_sections = Vec::new();
+        let mut kerning_sections = Vec::new();
+        for line in lines {
+            match utils::split_components(line).next() {
+                Some("info") => info_section = Some(line),
+                Some("distanceField") => distance_field_section = Some(line),
+                Some("common") => common_section = Some(line),
+                Some("page") => page_sections.push(line),
+                Some("chars") => {
+                    has_char_count_section = true;
+                    char_count = count(line, "chars");
+                }
+                Some("kernings") => kerning_count = count(line, "kernings"),
+                Some("char") => char_sections.push(line),
+                Some("kerning") => kerning_sections.push(line),
+                Some(_keyword) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(keyword = _keyword, "skipped line with unknown keyword");
+                }
+                None => (),
+            }
+        }
 
         // Expect the "info" section.
-        let mut lines = content.lines();
-        if !lines.next().map(|l| l.starts_with("info")).unwrap_or(false) {
-            return Err(Error::from(ConfigParseError::MissingSection(String::from(
-                "info",
-            ))));
+        if info_section.is_none() && !quirks.missing_info {
+            return Err(ConfigParseError::MissingSection(String::from("info")));
         }
 
         // Expect the "common" section.
-        let common_section = match lines.next() {
-            Some(line) if line.starts_with("common") => line.to_owned(),
-            _ => {
-                return Err(Error::from(ConfigParseError::MissingSection(String::from(
-                    "common",
-                ))))
-            }
+        let common_section = match common_section {
+            Some(common_section) => common_section,
+            None => return Err(ConfigParseError::MissingSection(String::from("common"))),
         };
 
         // Expect the "page" sections.
-        let lines = lines
-            .skip_while(|l| !l.starts_with("page"))
-            .collect::<Vec<_>>();
-        let lines = lines.iter();
-        let page_sections = lines
-            .clone()
-            .take_while(|l| l.starts_with("page"))
-            .map(|s| s.to_string())
-            .collect::<Vec<_>>();
         if page_sections.is_empty() {
-            return Err(Error::from(ConfigParseError::MissingSection(String::from(
-                "page",
-            ))));
+            return Err(ConfigParseError::MissingSection(String::from("page")));
         }
-        let mut lines = lines.skip(page_sections.len());
 
-        // Expect the "char" sections.
-        let _ = lines.next().unwrap(); // char_count_section
-        let char_sections = lines
-            .clone()
-            .take_while(|l| l.starts_with("char"))
-            .map(|s| s.to_string())
-            .collect::<Vec<_>>();
-        if char_sections.is_empty() {
-            return Err(Error::from(ConfigParseError::MissingSection(String::from(
-                "char",
-            ))));
+        // Expect the "char" sections, unless the font explicitly declares it has none.
+        if char_sections.is_empty() && !has_char_count_section {
+            return Err(ConfigParseError::MissingSection(String::from("char")));
         }
-        let mut lines = lines.skip(char_sections.len());
-
-        // Expect the "kerning" sections.
-        let kerning_sections = if lines.next().is_some() {
-            lines
-                .clone()
-                .take_while(|l| l.starts_with("kerning"))
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
 
         Ok(Sections {
+            info_section,
+            distance_field_section,
             common_section,
             page_sections,
             char_sections,
             kerning_sections,
+            char_count,
+            kerning_count,
+            unterminated_line,
         })
     }
 }
+
+fn count(line: &str, section: &str) -> Option<usize> {
+    utils::section_components(line, section, Quirks::default())
+        .and_then(|components| components.value("count"))
+        .ok()
+}
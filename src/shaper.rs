@@ -0,0 +1,21 @@
+use super::BMFont;
+
+/// Rewrites the char ids of every line before
+/// [`BMFont::parse_shaped()`](crate::BMFont::parse_shaped) positions them, e.g. to substitute
+/// ligatures or Arabic presentation forms the font provides, or to map private-use icons.
+///
+/// It is implemented for closures taking the font and the char ids of a line.
+pub trait Shaper {
+    /// Rewrites `line`, the char ids of a line without its line break, in place. Ids may be
+    /// replaced, inserted or removed.
+    fn shape(&mut self, font: &BMFont, line: &mut Vec<u32>);
+}
+
+impl<F> Shaper for F
+where
+    F: FnMut(&BMFont, &mut Vec<u32>),
+{
+    fn shape(&mut self, font: &BMFont, line: &mut Vec<u32>) {
+        self(font, line);
+    }
+}
@@ -0,0 +1,22 @@
+use super::distance_field::DistanceField;
+use super::glyph::Glyph;
+use super::kerning_value::KerningValue;
+use super::page::Page;
+
+/// Tables of a bitmap font compiled into the binary, usually generated by
+/// [`codegen::generate()`](crate::codegen::generate) from a build script.
+///
+/// `characters` must be sorted by id and `kerning_values` by first character id, as the generated
+/// tables are.
+#[derive(Debug)]
+pub struct StaticFont {
+    pub base_height: u32,
+    pub line_height: u32,
+    pub page_width: u32,
+    pub page_height: u32,
+    pub size: Option<u32>,
+    pub distance_field: Option<DistanceField>,
+    pub characters: &'static [Glyph],
+    pub kerning_values: &'static [KerningValue],
+    pub pages: &'static [Page],
+}
@@ -0,0 +1,107 @@
+use super::CharPosition;
+
+/// Places laid out text on a plane in 3D space, e.g. for name tags over characters or signs in a
+/// level, by mapping screen coordinates to world space with a transform.
+///
+/// The transform is a column-major matrix, the layout used by most math libraries for graphics,
+/// applied to the point `(x, y, 0, 1)` of each screen coordinate.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let file = std::fs::File::open("font.fnt")?;
+/// let font = BMFont::new(file, OrdinateOrientation::TopToBottom)?;
+/// let mut layout = Layout::new(LayoutOptions::default());
+/// let result = layout.set_text(&font, "Rust");
+/// # #[cfg(feature = "parse-error")]
+/// # result.unwrap();
+/// // One pixel is a hundredth of a world unit, y grows downwards on screen but upwards in the
+/// // world, and the text lies on the plane z = 5.
+/// let plane = TextPlane::from_axes([0.0, 2.0, 5.0], [0.01, 0.0, 0.0], [0.0, -0.01, 0.0]);
+/// let quads: Vec<[[f32; 3]; 4]> = plane.quads(layout.char_positions()).collect();
+/// assert_eq!(quads.len(), 4);
+/// assert!(quads.iter().flatten().all(|corner| corner[2] == 5.0));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextPlane {
+    columns: [[f32; 4]; 4],
+}
+
+impl TextPlane {
+    /// Creates a plane from a 4×4 column-major matrix. If it is projective, the corners are
+    /// divided by `w`.
+    pub fn from_cols_4x4(columns: [[f32; 4]; 4]) -> Self {
+        TextPlane { columns }
+    }
+
+    /// Creates a plane from a 3×4 affine transform, given as its four columns: the images of the
+    /// x, y and z axes and the translation.
+    pub fn from_cols_3x4(columns: [[f32; 3]; 4]) -> Self {
+        let column = |[x, y, z]: [f32; 3], w| [x, y, z, w];
+        TextPlane {
+            columns: [
+                column(columns[0], 0.0),
+                column(columns[1], 0.0),
+                column(columns[2], 0.0),
+                column(columns[3], 1.0),
+            ],
+        }
+    }
+
+    /// Creates a plane where the screen origin lies at `origin` and one pixel along the x and y
+    /// axes moves by `right` and `down` in world space, e.g. the right and down vectors of the
+    /// camera, scaled, for billboarded text.
+    pub fn from_axes(origin: [f32; 3], right: [f32; 3], down: [f32; 3]) -> Self {
+        TextPlane::from_cols_3x4([right, down, cross(right, down), origin])
+    }
+
+    /// Returns the world position of the screen point `(x, y)`.
+    pub fn transform_point(&self, x: f32, y: f32) -> [f32; 3] {
+        let [x_axis, y_axis, _, translation] = &self.columns;
+        let mut point = [0.0; 4];
+        for (i, coordinate) in point.iter_mut().enumerate() {
+            *coordinate = x_axis[i] * x + y_axis[i] * y + translation[i];
+        }
+        match point[3] {
+            w if w == 1.0 || w == 0.0 => [point[0], point[1], point[2]],
+            w => [point[0] / w, point[1] / w, point[2] / w],
+        }
+    }
+
+    /// Returns the world positions of the corners of the screen rectangle of `char_position`,
+    /// including its [`CharPosition::subpixel`] offset, in the order of
+    /// [`Rect::corners()`](crate::Rect::corners).
+    pub fn quad(&self, char_position: &CharPosition) -> [[f32; 3]; 4] {
+        let [x, y, width, height] = char_position.screen_bounds::<f32>();
+        [
+            self.transform_point(x, y),
+            self.transform_point(x + width, y),
+            self.transform_point(x + width, y + height),
+            self.transform_point(x, y + height),
+        ]
+    }
+
+    /// Returns an iterator over the world space quads of `char_positions`, e.g. those of
+    /// [`Layout::char_positions()`](crate::Layout::char_positions).
+    pub fn quads<'a, I>(&'a self, char_positions: I) -> impl Iterator<Item = [[f32; 3]; 4]> + 'a
+    where
+        I: IntoIterator<Item = &'a CharPosition>,
+        I::IntoIter: 'a,
+    {
+        char_positions
+            .into_iter()
+            .map(move |char_position| self.quad(char_position))
+    }
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
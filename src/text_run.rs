@@ -0,0 +1,205 @@
+use super::typesetter::{Cursor, Typesetter};
+use super::{BMFont, CharPosition, Color, InlineObject, LayoutOptions};
+
+#[cfg(feature = "parse-error")]
+use super::StringParseError;
+
+/// Alias of either [`Result<Vec<RunCharPosition>, StringParseError>`] _or_
+/// [`Vec<RunCharPosition>`], returned by [`layout_runs()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _with_ the `parse-error` feature.
+#[cfg(feature = "parse-error")]
+pub type LayoutRuns = Result<Vec<RunCharPosition>, StringParseError>;
+
+/// Alias of either [`Result<Vec<RunCharPosition>, StringParseError>`] _or_
+/// [`Vec<RunCharPosition>`], returned by [`layout_runs()`].
+///
+/// The output type depends on the value of the `parse-error` package feature.
+///
+/// **_NOTE:_** This documentation was generated _without_ the `parse-error` feature.
+#[cfg(not(feature = "parse-error"))]
+pub type LayoutRuns = Vec<RunCharPosition>;
+
+/// A piece of text laid out with its own font by [`layout_runs()`].
+#[derive(Clone, Copy, Debug)]
+pub struct TextRun<'a> {
+    pub text: &'a str,
+    pub font: &'a BMFont,
+    /// Factor by which the glyphs, their advances, kerning and offsets are scaled. The sizes of
+    /// the glyphs are rounded to whole pixels, their positions as set by
+    /// [LayoutOptions::positioning].
+    pub scale: f32,
+    /// Distance in pixels by which the glyphs are raised above the baseline, or lowered below it
+    /// if negative, regardless of the ordinate orientation.
+    pub baseline_shift: i32,
+    /// Color of the glyphs, see [`CharPosition::color`].
+    pub color: Color,
+    /// Object laid out in place of `text`, see [`TextRun::object()`].
+    pub object: Option<InlineObject>,
+}
+
+impl<'a> TextRun<'a> {
+    /// Returns a white run of `text` at the normal size on the baseline.
+    pub fn new(text: &'a str, font: &'a BMFont) -> Self {
+        TextRun {
+            text,
+            font,
+            scale: 1.0,
+            baseline_shift: 0,
+            color: Color::default(),
+            object: None,
+        }
+    }
+
+    /// Returns a run reserving the space of `object` on the baseline, e.g. for a button prompt or
+    /// a currency icon flowing inside text. The object is laid out as a single placeholder glyph
+    /// with an empty page rectangle, which [`RunCharPosition::object`] tells apart. `font` only
+    /// provides the line metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bmfont::*;
+    /// # fn main() -> Result<(), Error> {
+    /// let font = BMFont::new(std::fs::File::open("font.fnt")?, OrdinateOrientation::TopToBottom)?;
+    /// let coin = InlineObject { width: 32, height: 32, advance: 36, tag: 7 };
+    /// let runs = [
+    ///     TextRun::new("Costs ", &font),
+    ///     TextRun::object(coin, &font),
+    ///     TextRun::new(" each", &font),
+    /// ];
+    /// let char_positions = layout_runs(&runs, &LayoutOptions::default(), None);
+    /// # #[cfg(feature = "parse-error")]
+    /// # let char_positions = char_positions.unwrap();
+    /// let placeholder = char_positions.iter().find(|p| p.object.is_some()).unwrap();
+    /// assert_eq!(placeholder.object.unwrap().tag, 7);
+    /// assert_eq!(placeholder.char_position.screen_rect.max_y(), font.base_height() as i32);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn object(object: InlineObject, font: &'a BMFont) -> Self {
+        TextRun {
+            object: Some(object),
+            ..TextRun::new("", font)
+        }
+    }
+
+    /// Returns a run of `text` at [`SCRIPT_SCALE`] raised by a third of the base height, e.g. for
+    /// footnote markers.
+    pub fn superscript(text: &'a str, font: &'a BMFont) -> Self {
+        TextRun {
+            scale: SCRIPT_SCALE,
+            baseline_shift: (font.base_height as f32 / 3.0).round() as i32,
+            ..TextRun::new(text, font)
+        }
+    }
+
+    /// Returns a run of `text` at [`SCRIPT_SCALE`] lowered by a sixth of the base height, e.g. for
+    /// chemical formulas.
+    pub fn subscript(text: &'a str, font: &'a BMFont) -> Self {
+        TextRun {
+            scale: SCRIPT_SCALE,
+            baseline_shift: -(font.base_height as f32 / 6.0).round() as i32,
+            ..TextRun::new(text, font)
+        }
+    }
+}
+
+/// Scale of the runs returned by [`TextRun::superscript()`] and [`TextRun::subscript()`].
+pub const SCRIPT_SCALE: f32 = 0.6;
+
+/// A [CharPosition] produced by [`layout_runs()`], along with the index of the [TextRun] it
+/// belongs to, which tells the font whose pages it refers to.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RunCharPosition {
+    pub run_index: usize,
+    pub char_position: CharPosition,
+    /// The object the glyph is a placeholder for, if it was laid out from
+    /// [`TextRun::object()`].
+    pub object: Option<InlineObject>,
+}
+
+/// Lays out `runs` as a single paragraph, e.g. a bold word inside a regular sentence.
+///
+/// All lines share the largest base height and line height of the fonts, so the glyphs of every
+/// run sit on a common baseline, shifted by the [`TextRun::baseline_shift`] of their run. The
+/// coordinate system of the ordinate orientation of the first font is used, unless
+/// [`LayoutOptions::coordinate_system`](crate::LayoutOptions::coordinate_system) is set. If
+/// `max_width` is given, lines are broken after the last whitespace which keeps them within it;
+/// words wider than `max_width` are not broken. Glyphs outside of
+/// [`LayoutOptions::clip_rect`](crate::LayoutOptions::clip_rect) are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bmfont::*;
+/// # fn main() -> Result<(), Error> {
+/// let regular = BMFont::new(std::fs::File::open("font.fnt")?, OrdinateOrientation::TopToBottom)?;
+/// let bold = BMFont::new(std::fs::File::open("font.fnt")?, OrdinateOrientation::TopToBottom)?;
+/// let runs = [
+///     TextRun::new("a ", &regular),
+///     TextRun::new("bold", &bold),
+///     TextRun::new(" word", &regular),
+/// ];
+/// let char_positions = layout_runs(&runs, &LayoutOptions::default(), Some(200));
+/// # #[cfg(feature = "parse-error")]
+/// # let char_positions = char_positions.unwrap();
+/// assert_eq!(char_positions[2].run_index, 1);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn layout_runs(
+    runs: &[TextRun<'_>],
+    options: &LayoutOptions,
+    max_width: Option<u32>,
+) -> LayoutRuns {
+    #[cfg(feature = "parse-error")]
+    {
+        let mut error: Option<StringParseError> = None;
+        for run in runs {
+            if let Err(run_error) = run.font.check_characters(run.text.chars(), options) {
+                if let Some(error) = error.as_mut() {
+                    error
+                        .missing_characters
+                        .extend(run_error.missing_characters);
+                    error
+                        .unsupported_characters
+                        .extend(run_error.unsupported_characters);
+                } else {
+                    error = Some(run_error);
+                }
+            }
+        }
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+
+    let mut char_positions: Vec<RunCharPosition> = Vec::new();
+    let mut typesetter = Typesetter::new(
+        runs.iter().map(|run| run.font),
+        options,
+        max_width,
+        &mut char_positions,
+        Cursor::default(),
+    );
+    for (run_index, run) in runs.iter().enumerate() {
+        typesetter.push_run(run_index, run);
+    }
+    // Lines are only final once laid out, as breaking a line moves its last glyphs.
+    if options.clip_rect.is_some() {
+        char_positions.retain_mut(|p| options.clip(&mut p.char_position));
+    }
+
+    #[cfg(feature = "parse-error")]
+    {
+        Ok(char_positions)
+    }
+
+    #[cfg(not(feature = "parse-error"))]
+    {
+        char_positions
+    }
+}
@@ -0,0 +1,468 @@
+use super::line_break::{LineBreak, LineBreaks};
+use super::LayoutOptions;
+use super::TextRun;
+use super::{char_id, BMFont, CharPosition, CoordinateSystem, EmptyGlyphPolicy, InlineObject};
+use super::{LineEnding, LineMetrics, OrdinateOrientation, Positioning, Rect, RunCharPosition};
+use std::convert::TryFrom;
+
+/// A glyph produced by the [Typesetter].
+pub trait TypesetGlyph {
+    fn new(run_index: usize, char_position: CharPosition) -> Self;
+
+    fn char_position_mut(&mut self) -> &mut CharPosition;
+
+    /// Marks the glyph as the placeholder of `object`.
+    fn set_object(&mut self, _object: InlineObject) {}
+}
+
+impl TypesetGlyph for CharPosition {
+    fn new(_: usize, char_position: CharPosition) -> Self {
+        char_position
+    }
+
+    fn char_position_mut(&mut self) -> &mut CharPosition {
+        self
+    }
+}
+
+impl TypesetGlyph for RunCharPosition {
+    fn new(run_index: usize, char_position: CharPosition) -> Self {
+        RunCharPosition {
+            run_index,
+            char_position,
+            object: None,
+        }
+    }
+
+    fn char_position_mut(&mut self) -> &mut CharPosition {
+        &mut self.char_position
+    }
+
+    fn set_object(&mut self, object: InlineObject) {
+        self.object = Some(object);
+    }
+}
+
+/// Splits `value` into whole pixels and the fraction of a pixel above them, rounded to the
+/// nearest 256th so that rounding errors don't pile up in one direction along a line.
+fn split_fraction(value: f64) -> (i32, u8) {
+    let fractions = (value * 256.0).round();
+    let whole = (fractions / 256.0).floor();
+    (whole as i32, (fractions - whole * 256.0) as u8)
+}
+
+/// Rounds `value` to the nearest whole pixel, halves upwards. Every scaled quantity placed at
+/// whole pixels goes through it, so that all of them round the same way.
+fn snap(value: f64) -> f64 {
+    (value + 0.5).floor()
+}
+
+/// Rounds a position given as whole pixels and the fraction of a pixel above them in 256ths to
+/// the nearest whole pixel, halves upwards like [snap()], saturating at the bounds of `i32`.
+fn snap_subpixel((whole, fraction): (i32, u8)) -> (i32, u8) {
+    (whole.saturating_add(i32::from(fraction >= 128)), 0)
+}
+
+/// Position of a [Typesetter] in the text, kept to continue laying out later.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cursor {
+    pub x: i32,
+    pub y: i32,
+    /// Index in the output and pen position right after the last whitespace on the current line.
+    line_break: Option<(usize, i32)>,
+    /// Address of the font and id of the previous glyph on the current line, for kerning.
+    previous_char: Option<(usize, u32)>,
+    line_breaks: LineBreaks,
+    /// Whether the current line has any characters, for [EmptyLineAdvance](crate::EmptyLineAdvance).
+    has_chars: bool,
+    /// Index in the output of the first glyph on the current line.
+    line_glyph_start: usize,
+    /// Fraction of a pixel, in 256ths, by which the pen lies further than `x`, for
+    /// [Positioning::Fractional].
+    x_fraction: u8,
+}
+
+impl Cursor {
+    /// Returns a cursor at the start of the line at `y`, whose first glyph will be placed at
+    /// `glyph_start` in the output.
+    pub fn line_start(y: i32, glyph_start: usize) -> Self {
+        Cursor {
+            y,
+            line_glyph_start: glyph_start,
+            ..Cursor::default()
+        }
+    }
+
+    /// Returns the exact position of the pen on the x axis.
+    fn pen_x(&self) -> f64 {
+        self.x as f64 + self.x_fraction as f64 / 256.0
+    }
+
+    /// Returns the position of the pen on the x axis rounded to whole pixels, where lines end and
+    /// break.
+    fn pen_pixel(&self) -> i32 {
+        snap_subpixel((self.x, self.x_fraction)).0
+    }
+
+    /// Returns the position `dx` past the pen on the x axis, as whole pixels and the fraction of
+    /// a pixel above them in 256ths, saturating at the bounds of `i32`. Only the fraction of the
+    /// pen goes through floating point, so positions stay exact however long the line is.
+    fn offset(&self, dx: f64) -> (i32, u8) {
+        let (whole, fraction) = split_fraction(self.x_fraction as f64 / 256.0 + dx);
+        (self.x.saturating_add(whole), fraction)
+    }
+
+    /// Moves the cursor by `dy` and its line by `glyph_delta` glyphs in the output, for text
+    /// inserted or removed before it.
+    pub fn shift(&mut self, dy: i32, glyph_delta: isize) {
+        self.y = self.y.saturating_add(dy);
+        self.line_glyph_start = (self.line_glyph_start as isize + glyph_delta) as usize;
+    }
+
+    /// Returns the metrics of the current line, which has not ended yet.
+    pub fn line(&self, glyph_end: usize) -> LineMetrics {
+        LineMetrics {
+            glyphs: self.line_glyph_start..glyph_end,
+            y: self.y,
+            width: self.pen_pixel(),
+            ending: LineEnding::End,
+        }
+    }
+}
+
+/// Eagerly lays out text from one or more fonts into `output`, breaking lines which exceed
+/// `max_width` after whitespace.
+///
+/// Every line shares one baseline and line height, the largest of all fonts involved.
+pub struct Typesetter<'a, T> {
+    options: &'a LayoutOptions,
+    coordinate_system: CoordinateSystem,
+    base_height: i32,
+    line_height: i32,
+    max_width: Option<i32>,
+    output: &'a mut Vec<T>,
+    lines: Option<&'a mut Vec<LineMetrics>>,
+    cursor: Cursor,
+}
+
+impl<'a, T> Typesetter<'a, T>
+where
+    T: TypesetGlyph,
+{
+    pub fn new<'f, I>(
+        fonts: I,
+        options: &'a LayoutOptions,
+        max_width: Option<u32>,
+        output: &'a mut Vec<T>,
+        cursor: Cursor,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'f BMFont>,
+    {
+        let mut fonts = fonts.into_iter();
+        let first = fonts.next();
+        let coordinate_system = options.coordinate_system(
+            first
+                .map(|font| font.ordinate_orientation)
+                .unwrap_or(OrdinateOrientation::TopToBottom),
+        );
+        let (base_height, line_height) =
+            first.into_iter().chain(fonts).fold((0, 0), |acc, font| {
+                (
+                    acc.0.max(font.base_height as i32),
+                    acc.1.max(font.line_height as i32),
+                )
+            });
+
+        Typesetter {
+            options,
+            coordinate_system,
+            base_height,
+            line_height,
+            max_width: max_width.map(|max_width| i32::try_from(max_width).unwrap_or(i32::MAX)),
+            output,
+            lines: None,
+            cursor,
+        }
+    }
+
+    /// Scales the shared base height and line height by `scale`, for text whose runs are all
+    /// scaled by it. Lines start at whole pixels, so both are rounded whatever the
+    /// [Positioning].
+    pub fn scale_lines(&mut self, scale: f32) {
+        self.base_height = snap(self.base_height as f64 * f64::from(scale)) as i32;
+        self.line_height = snap(self.line_height as f64 * f64::from(scale)) as i32;
+    }
+
+    /// Returns the line height shared by all lines.
+    pub fn line_height(&self) -> u32 {
+        self.line_height as u32
+    }
+
+    /// Records every line which ends from now on into `lines`.
+    pub fn record_lines(&mut self, lines: &'a mut Vec<LineMetrics>) {
+        self.lines = Some(lines);
+    }
+
+    pub fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    /// Lays out `text` with `font`, continuing from the end of the previous text.
+    pub fn push_str(&mut self, run_index: usize, font: &BMFont, text: &str) {
+        self.push_run(run_index, &TextRun::new(text, font));
+    }
+
+    /// Lays out `run` with its scale and baseline shift, continuing from the end of the previous
+    /// text.
+    ///
+    /// Advances, kerning and offsets are scaled exactly and the pen moves by them exactly. With
+    /// [Positioning::Snapped] only the resulting position of each glyph is rounded, so rounding
+    /// errors don't add up along a line.
+    pub fn push_run(&mut self, run_index: usize, run: &TextRun<'_>) {
+        if let Some(object) = run.object {
+            self.push_object(run_index, run, object);
+            return;
+        }
+        let font = run.font;
+        let fractional = self.options.positioning == Positioning::Fractional;
+        let scaled = |value: i32| value as f64 * f64::from(run.scale);
+        for c in run.text.chars() {
+            let mut line_breaks = self.cursor.line_breaks;
+            match line_breaks.classify(c as u32) {
+                LineBreak::NewLine => {
+                    self.new_line();
+                    self.cursor.line_breaks = line_breaks;
+                    continue;
+                }
+                LineBreak::Joined => {
+                    self.cursor.line_breaks = line_breaks;
+                    continue;
+                }
+                LineBreak::None => {
+                    self.cursor.line_breaks = line_breaks;
+                    self.cursor.has_chars = true;
+                }
+            }
+
+            let char = match char_id(c).and_then(|char_id| font.find_glyph(char_id, self.options)) {
+                Some(char) => char,
+                None => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(character = %c, "skipped character without glyph");
+
+                    continue;
+                }
+            };
+            let font_address = font as *const BMFont as usize;
+            let kerning = match self.cursor.previous_char {
+                Some((previous_font, previous_id)) if previous_font == font_address => {
+                    scaled(font.kerning(previous_id, char.id))
+                }
+                _ => 0.0,
+            };
+            let (xadvance, kerning, cell_offset) =
+                self.options.advance(scaled(char.xadvance), kerning);
+
+            if let (Some(max_width), Some((idx, break_x))) =
+                (self.max_width, self.cursor.line_break)
+            {
+                if !c.is_whitespace()
+                    && self.cursor.pen_x() + kerning + xadvance > f64::from(max_width)
+                {
+                    self.wrap(idx, break_x);
+                }
+            }
+
+            let is_empty = char.width == 0 || char.height == 0;
+            if !is_empty || self.options.empty_glyph_policy == EmptyGlyphPolicy::Emit {
+                let height = snap(scaled(char.height as i32));
+                let width = snap(scaled(char.width as i32));
+                let glyph_dx = cell_offset + kerning + scaled(char.xoffset);
+                let (mut start, mut end) = (
+                    self.cursor.offset(glyph_dx),
+                    self.cursor.offset(glyph_dx + width),
+                );
+                if !fractional {
+                    start = snap_subpixel(start);
+                    end = snap_subpixel(end);
+                }
+                let (x, subpixel_x) = self.coordinate_system.glyph_subpixel_x(start, end);
+                let dy = self
+                    .coordinate_system
+                    .glyph_dy(self.base_height, self.line_height);
+                let glyph_dy = dy as f64
+                    + match self.coordinate_system.ordinate_orientation {
+                        OrdinateOrientation::BottomToTop => {
+                            scaled(font.base_height as i32 - char.yoffset) - height
+                                + run.baseline_shift as f64
+                        }
+                        OrdinateOrientation::TopToBottom => {
+                            self.base_height as f64
+                                - scaled(font.base_height as i32 - char.yoffset)
+                                - run.baseline_shift as f64
+                        }
+                    };
+                let (y, subpixel_y) = match fractional {
+                    true => split_fraction(glyph_dy),
+                    false => (snap(glyph_dy) as i32, 0),
+                };
+                let y = self.cursor.y.saturating_add(y);
+                // Snapped glyphs report the distances between their rounded positions, which add
+                // up to where the next glyph is placed.
+                let (xadvance, kerning) = match fractional {
+                    true => (xadvance.round() as i32, kerning.round() as i32),
+                    false => {
+                        let pen = self.cursor.pen_pixel();
+                        let kerned = snap_subpixel(self.cursor.offset(kerning)).0;
+                        let advanced = snap_subpixel(self.cursor.offset(kerning + xadvance)).0;
+                        (advanced.saturating_sub(kerned), kerned.saturating_sub(pen))
+                    }
+                };
+                let char_position = CharPosition {
+                    page_rect: self.options.page_rect(&char, font.page_height),
+                    screen_rect: Rect {
+                        x,
+                        y,
+                        width: width as u32,
+                        height: height as u32,
+                    },
+                    page_index: char.page_index,
+                    chnl: char.chnl,
+                    xadvance,
+                    kerning,
+                    subpixel: (subpixel_x, subpixel_y),
+                    color: run.color,
+                    clipped: false,
+                };
+                self.output.push(T::new(run_index, char_position));
+            }
+            let (x, x_fraction) = self.cursor.offset(xadvance + kerning);
+            self.cursor.x = x;
+            self.cursor.x_fraction = x_fraction;
+            self.cursor.previous_char = Some((font_address, char.id));
+
+            if c.is_whitespace() {
+                self.cursor.line_break = Some((self.output.len(), self.cursor.pen_pixel()));
+            }
+        }
+    }
+
+    /// Lays out the placeholder of `object` on the baseline, shifted by the baseline shift of
+    /// `run`, continuing from the end of the previous text.
+    fn push_object(&mut self, run_index: usize, run: &TextRun<'_>, object: InlineObject) {
+        self.cursor.has_chars = true;
+        let advance = object.advance as f64;
+        if let (Some(max_width), Some((idx, break_x))) = (self.max_width, self.cursor.line_break) {
+            if self.cursor.pen_x() + advance > f64::from(max_width) {
+                self.wrap(idx, break_x);
+            }
+        }
+
+        let (mut start, mut end) = (
+            self.cursor.offset(0.0),
+            self.cursor.offset(object.width as f64),
+        );
+        if self.options.positioning == Positioning::Snapped {
+            start = snap_subpixel(start);
+            end = snap_subpixel(end);
+        }
+        let (x, subpixel_x) = self.coordinate_system.glyph_subpixel_x(start, end);
+        let dy = self
+            .coordinate_system
+            .glyph_dy(self.base_height, self.line_height);
+        let object_dy = dy as f64
+            + match self.coordinate_system.ordinate_orientation {
+                OrdinateOrientation::BottomToTop => run.baseline_shift as f64,
+                OrdinateOrientation::TopToBottom => {
+                    self.base_height as f64 - object.height as f64 - run.baseline_shift as f64
+                }
+            };
+        let (y, subpixel_y) = split_fraction(object_dy);
+        let y = self.cursor.y.saturating_add(y);
+        let char_position = CharPosition {
+            screen_rect: Rect {
+                x,
+                y,
+                width: object.width,
+                height: object.height,
+            },
+            xadvance: object.advance,
+            subpixel: (subpixel_x, subpixel_y),
+            color: run.color,
+            ..CharPosition::default()
+        };
+        let mut glyph = T::new(run_index, char_position);
+        glyph.set_object(object);
+        self.output.push(glyph);
+
+        let (x, x_fraction) = self.cursor.offset(advance);
+        self.cursor.x = x;
+        self.cursor.x_fraction = x_fraction;
+        self.cursor.previous_char = None;
+    }
+
+    /// Moves the glyphs from `idx` on to a new line, `break_x` being the pen position at `idx`.
+    fn wrap(&mut self, idx: usize, break_x: i32) {
+        let (x, x_fraction) = (self.cursor.x, self.cursor.x_fraction);
+        let previous_char = self.cursor.previous_char;
+        self.end_line(LineMetrics {
+            glyphs: self.cursor.line_glyph_start..idx,
+            y: self.cursor.y,
+            width: break_x,
+            ending: LineEnding::Soft,
+        });
+        self.cursor.line_glyph_start = idx;
+
+        let dy = self.line_offset();
+        for glyph in &mut self.output[idx..] {
+            let char_position = glyph.char_position_mut();
+            let screen_rect = &mut char_position.screen_rect;
+            screen_rect.x = screen_rect
+                .x
+                .saturating_sub(self.coordinate_system.dx(break_x));
+            screen_rect.y = screen_rect.y.saturating_add(dy);
+        }
+        self.cursor.x = x.saturating_sub(break_x);
+        self.cursor.x_fraction = x_fraction;
+        self.cursor.previous_char = previous_char;
+        self.cursor.has_chars = true;
+    }
+
+    /// Returns the number of glyphs in the output.
+    pub fn glyph_count(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Returns the number of lines recorded by [`Typesetter::record_lines()`].
+    pub fn line_count(&self) -> usize {
+        self.lines.as_ref().map_or(0, |lines| lines.len())
+    }
+
+    /// Starts a new line, as a `'\n'` does.
+    pub fn new_line(&mut self) {
+        let mut line = self.cursor.line(self.output.len());
+        line.ending = LineEnding::Hard;
+        self.end_line(line);
+    }
+
+    /// Records `line` and moves the cursor to the start of the next line.
+    fn end_line(&mut self, line: LineMetrics) {
+        let mut line_offset = self.line_offset();
+        if !self.cursor.has_chars {
+            line_offset = self.options.empty_line_advance.advance(line_offset);
+        }
+        let glyph_end = line.glyphs.end;
+        if let Some(lines) = &mut self.lines {
+            lines.push(line);
+        }
+        self.cursor = Cursor::line_start(self.cursor.y.saturating_add(line_offset), glyph_end);
+    }
+
+    fn line_offset(&self) -> i32 {
+        match self.coordinate_system.ordinate_orientation {
+            OrdinateOrientation::TopToBottom => self.line_height,
+            OrdinateOrientation::BottomToTop => -self.line_height,
+        }
+    }
+}
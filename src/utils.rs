@@ -1,74 +1,159 @@
+use super::load_options::Quirks;
 use super::ConfigParseError;
 use std::fmt::Debug;
 use std::str::FromStr;
 
-pub fn extract_component_value<T>(
-    s: Option<&str>,
-    section: &str,
-    component: &str,
-) -> Result<T, ConfigParseError>
-where
-    T: FromStr,
-    T::Err: Debug,
-{
-    let s = unwrap_string(s, section, component)?;
-    let string_parts = s.splitn(2, '=').collect::<Vec<_>>();
-    check_string_parts(&string_parts, section, component)?;
-    let value = extract_value(&string_parts, section, component)?;
-    if let Ok(value) = value.parse() {
-        Ok(value)
-    } else {
-        Err(ConfigParseError::InvalidComponentValue {
-            section: section.to_string(),
-            component: component.to_string(),
-            value: value.to_string(),
-        })
+/// Splits a section line into whitespace separated components, keeping double quoted values
+/// such as `file="my font 01.png"` in one piece.
+pub fn split_components(s: &str) -> Components<'_> {
+    Components { s }
+}
+
+pub struct Components<'a> {
+    s: &'a str,
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.s.trim_start();
+        if s.is_empty() {
+            self.s = s;
+            return None;
+        }
+
+        let end = component_len(s);
+        self.s = &s[end..];
+        Some(&s[..end])
     }
 }
 
-fn unwrap_string<'a>(
-    s: Option<&'a str>,
-    section: &'a str,
-    component: &'a str,
-) -> Result<&'a str, ConfigParseError> {
-    if let Some(s) = s {
-        Ok(s)
+fn component_len(s: &str) -> usize {
+    let mut chars = s.char_indices().peekable();
+    let mut in_quotes = false;
+    while let Some((idx, c)) = chars.next() {
+        if c == '"' {
+            // A quote only closes the value when it is followed by whitespace or the end of the
+            // line, which keeps values like `letter="""` intact.
+            in_quotes = !in_quotes
+                || chars
+                    .peek()
+                    .map(|&(_, next)| !next.is_whitespace())
+                    .unwrap_or(false);
+        } else if !in_quotes && c.is_whitespace() {
+            return idx;
+        }
+    }
+    s.len()
+}
+
+/// Removes one pair of surrounding double quotes, if present.
+pub fn unquote(s: &str) -> &str {
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        &s[1..s.len() - 1]
     } else {
-        Err(ConfigParseError::MissingComponent {
-            section: String::from(section),
-            component: String::from(component),
-        })
+        s
     }
 }
 
-fn check_string_parts(
-    string_parts: &[&str],
-    section: &str,
-    component: &str,
-) -> Result<(), ConfigParseError> {
-    if string_parts.len() == 2 {
-        return Ok(());
+/// Splits the section line `s` into its components, failing if it is not a `section` line. Their
+/// values are parsed tolerating `quirks`.
+pub fn section_components<'a>(
+    s: &'a str,
+    section: &'a str,
+    quirks: Quirks,
+) -> Result<SectionComponents<'a>, ConfigParseError> {
+    let mut components = split_components(s);
+    if components.next() != Some(section) {
+        return Err(ConfigParseError::MissingSection(String::from(section)));
     }
-    assert_eq!(string_parts.len(), 1);
-    Err(ConfigParseError::InvalidComponentValue {
-        section: section.to_string(),
-        component: component.to_string(),
-        value: String::new(),
-    })
+    Ok(SectionComponents::new(components, section, quirks))
+}
+
+/// Components of a section line, looked up by name regardless of their order.
+pub struct SectionComponents<'a> {
+    section: &'a str,
+    components: Vec<(&'a str, &'a str)>,
+    quirks: Quirks,
 }
 
-fn extract_value<'a>(
-    string_parts: &'a [&'a str],
-    section: &str,
-    component: &str,
-) -> Result<&'a str, ConfigParseError> {
-    let actual_component = string_parts[0];
-    if actual_component == component {
-        return Ok(string_parts[1]);
+impl<'a> SectionComponents<'a> {
+    /// Collects the `name=value` components remaining in `components`. Components without a value
+    /// are kept with an empty one.
+    pub fn new(components: Components<'a>, section: &'a str, quirks: Quirks) -> Self {
+        let components = components
+            .map(|component| {
+                let mut parts = component.splitn(2, '=');
+                (parts.next().unwrap_or(""), parts.next().unwrap_or(""))
+            })
+            .collect();
+        SectionComponents {
+            section,
+            components,
+            quirks,
+        }
+    }
+
+    /// Returns the parsed value of `component`, failing if it is absent.
+    pub fn value<T>(&self, component: &str) -> Result<T, ConfigParseError>
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        match self.optional_value(component)? {
+            Some(value) => Ok(value),
+            None => Err(ConfigParseError::MissingComponent {
+                section: String::from(self.section),
+                component: String::from(component),
+            }),
+        }
+    }
+
+    /// Returns the parsed value of `component` or `default` if it is absent.
+    pub fn value_or<T>(&self, component: &str, default: T) -> Result<T, ConfigParseError>
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        Ok(self.optional_value(component)?.unwrap_or(default))
+    }
+
+    /// Returns the parsed value of `component` or `None` if it is absent.
+    pub fn optional_value<T>(&self, component: &str) -> Result<Option<T>, ConfigParseError>
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        let value = match self.components.iter().find(|&&(name, _)| name == component) {
+            Some(&(_, value)) => value,
+            None => return Ok(None),
+        };
+        if let Ok(value) = value.parse() {
+            return Ok(Some(value));
+        }
+
+        let mut number = value;
+        if self.quirks.quoted_numbers {
+            number = unquote(number);
+        }
+        if let Ok(number) = number.parse() {
+            return Ok(Some(number));
+        }
+        if self.quirks.fractional_numbers {
+            let rounded = number
+                .parse::<f64>()
+                .ok()
+                .and_then(|number| (number.round() as i64).to_string().parse().ok());
+            if let Some(rounded) = rounded {
+                return Ok(Some(rounded));
+            }
+        }
+
+        Err(ConfigParseError::InvalidComponentValue {
+            section: self.section.to_string(),
+            component: component.to_string(),
+            value: value.to_string(),
+        })
     }
-    Err(ConfigParseError::InvalidComponent {
-        section: String::from(section),
-        expected_component: String::from(component),
-        actual_component: String::from(actual_component),
-    })
 }
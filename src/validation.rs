@@ -0,0 +1,95 @@
+use std::fmt::{Display, Error, Formatter};
+
+/// A single inconsistency found by [`BMFont::validate()`](crate::BMFont::validate).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ValidationIssue {
+    /// The glyph rectangle of a character extends past the page dimensions.
+    GlyphOutsidePage { char_id: u32 },
+    /// The `letter` field of a character names a different character than its id.
+    LetterMismatch { char_id: u32 },
+    /// A character references a page which is not declared.
+    MissingPage { char_id: u32, page_index: u32 },
+    /// A kerning pair references a character which is not declared.
+    MissingKerningCharacter {
+        first_char_id: u32,
+        second_char_id: u32,
+        missing_char_id: u32,
+    },
+    /// The glyph rectangles of two characters on the same page share some area in the same
+    /// channels, which is a symptom of broken packing. `first_char_id` is the smaller id.
+    OverlappingGlyphs {
+        first_char_id: u32,
+        second_char_id: u32,
+        page_index: u32,
+    },
+    /// The font file was cut off, see [`BMFont::is_truncated()`](crate::BMFont::is_truncated).
+    Truncated,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match *self {
+            ValidationIssue::GlyphOutsidePage { char_id } => {
+                write!(
+                    formatter,
+                    "Glyph of char = {} lies outside the page",
+                    char_id
+                )
+            }
+            ValidationIssue::LetterMismatch { char_id } => write!(
+                formatter,
+                "Letter of char = {} does not match its id",
+                char_id
+            ),
+            ValidationIssue::MissingPage {
+                char_id,
+                page_index,
+            } => write!(
+                formatter,
+                "Char = {} references missing page = {}",
+                char_id, page_index
+            ),
+            ValidationIssue::MissingKerningCharacter {
+                first_char_id,
+                second_char_id,
+                missing_char_id,
+            } => write!(
+                formatter,
+                "Kerning pair = ({}, {}) references missing char = {}",
+                first_char_id, second_char_id, missing_char_id
+            ),
+            ValidationIssue::OverlappingGlyphs {
+                first_char_id,
+                second_char_id,
+                page_index,
+            } => write!(
+                formatter,
+                "Glyphs of chars = ({}, {}) overlap on page = {}",
+                first_char_id, second_char_id, page_index
+            ),
+            ValidationIssue::Truncated => write!(formatter, "The font file is truncated"),
+        }
+    }
+}
+
+/// Result of [`BMFont::validate()`](crate::BMFont::validate).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        for issue in &self.issues {
+            writeln!(formatter, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
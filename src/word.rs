@@ -0,0 +1,77 @@
+use super::{CharPosition, ParseIter, Rect};
+use std::ops::Range;
+use std::str::CharIndices;
+
+/// A word laid out by [`BMFont::words()`](crate::BMFont::words).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Word {
+    /// Byte range of the word in the text.
+    pub range: Range<usize>,
+    /// Union of the screen rects of the glyphs of the word, empty if none of them has area.
+    pub rect: Rect,
+}
+
+/// Lazily lays out text and yields its words, the runs of characters between whitespace.
+pub struct WordIter<'a> {
+    char_indices: CharIndices<'a>,
+    /// Index of the next character of `char_indices`.
+    char_idx: usize,
+    char_positions: ParseIter<'a>,
+    /// Next glyph together with the index of the character it is laid out for.
+    next_glyph: Option<(usize, CharPosition)>,
+}
+
+impl<'a> WordIter<'a> {
+    pub(crate) fn new(text: &'a str, char_positions: ParseIter<'a>) -> Self {
+        let mut words = WordIter {
+            char_indices: text.char_indices(),
+            char_idx: 0,
+            char_positions,
+            next_glyph: None,
+        };
+        words.advance_glyph();
+        words
+    }
+
+    fn advance_glyph(&mut self) {
+        self.next_glyph = self
+            .char_positions
+            .next()
+            .map(|char_position| (self.char_positions.char_count - 1, char_position));
+    }
+}
+
+impl<'a> Iterator for WordIter<'a> {
+    type Item = Word;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut range: Option<Range<usize>> = None;
+        let mut rect = Rect::default();
+        while let Some((byte_idx, c)) = self.char_indices.next() {
+            let char_idx = self.char_idx;
+            self.char_idx += 1;
+            if c.is_whitespace() {
+                if range.is_some() {
+                    break;
+                }
+                continue;
+            }
+            let end = byte_idx + c.len_utf8();
+            match range.as_mut() {
+                Some(range) => range.end = end,
+                None => range = Some(byte_idx..end),
+            }
+            // Glyphs of the whitespace before the character are passed over.
+            while let Some((glyph_char_idx, char_position)) = self.next_glyph {
+                if glyph_char_idx > char_idx {
+                    break;
+                }
+                if glyph_char_idx == char_idx {
+                    rect = rect.union(&char_position.screen_rect);
+                }
+                self.advance_glyph();
+            }
+        }
+        range.map(|range| Word { range, rect })
+    }
+}
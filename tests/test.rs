@@ -1,11 +1,31 @@
+// The tests kept from the first versions of the crate predate these lints.
+#![allow(
+    clippy::identity_op,
+    clippy::redundant_field_names,
+    clippy::redundant_static_lifetimes,
+    clippy::useless_conversion
+)]
+
 extern crate bmfont;
 
 extern crate serde_json;
 
-use bmfont::{BMFont, CharPosition, OrdinateOrientation, Rect};
+use bmfont::{
+    layout_runs, AbscissaOrientation, Anchor, BMFont, BlockCoverage, CharPosition, Color,
+    ConfigParseError, ControlCharPolicy, Coordinate, CoordinateSystem, DirectorySource,
+    DirtyRegion, DistanceField, DistanceFieldType, DuplicateCharPolicy, DynamicAtlas,
+    EllipsisPosition, EmptyGlyphPolicy, EmptyLineAdvance, Error, Exporter, FontCollection,
+    FontRegistry, FontWatcher, GenerateError, GenerateOptions, Glyph, GlyphMesh, GlyphSink,
+    GlyphTransform, InlineObject, KerningLookup, Layout, LayoutBatch, LayoutCache, LayoutOptions,
+    LineEnding, LineOrigin, LoadOptions, MissingCharAction, MissingSpaceAdvance,
+    OrdinateOrientation, Overflow, PageBatches, PageOrigin, Positioning, Rect, RunCharPosition,
+    StaticFont, TextPlane, TextRun, TruncationPolicy, ValidationIssue, VerticalMetrics, Word,
+};
 use serde_json::{from_str, to_string_pretty};
-use std::fs::{read_to_string, File};
+use std::fs::{read, read_to_string, File};
 use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Instant;
 
 const RUST_WORD: &'static str = "Rust";
 const UNDERSCORE_CHARACTER: &'static str = "_";
@@ -16,8 +36,7 @@ fn create_bmfont(ordinate_orientation: OrdinateOrientation) -> BMFont {
     BMFont::new(file, ordinate_orientation).unwrap()
 }
 
-fn create_simple_bmfont(ordinate_orientation: OrdinateOrientation) -> BMFont {
-    let simple = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+const SIMPLE_FONT: &str = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
 common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
 page id=0 file="font.png"
 chars count=1
@@ -25,7 +44,8 @@ char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0
 kernings count=1
 kerning first=100 second=100 amount=-4"#;
 
-    BMFont::new(Cursor::new(simple), ordinate_orientation).unwrap()
+fn create_simple_bmfont(ordinate_orientation: OrdinateOrientation) -> BMFont {
+    BMFont::new(Cursor::new(SIMPLE_FONT), ordinate_orientation).unwrap()
 }
 
 fn parse(s: &str, ordinate_orientation: OrdinateOrientation) -> Vec<CharPosition> {
@@ -38,16 +58,9 @@ fn parse(s: &str, ordinate_orientation: OrdinateOrientation) -> Vec<CharPosition
     parse.collect()
 }
 
-fn assert_rect_equal(rect: &Rect, another_rect: &Rect) {
-    assert_eq!(rect.x, another_rect.x);
-    assert_eq!(rect.y, another_rect.y);
-    assert_eq!(rect.max_x(), another_rect.max_x());
-    assert_eq!(rect.max_y(), another_rect.max_y());
-}
-
 fn assert_char_positions_equal(char_position: &CharPosition, another_char_position: &CharPosition) {
-    assert_rect_equal(&char_position.page_rect, &another_char_position.page_rect);
-    assert_rect_equal(
+    assert_eq!(&char_position.page_rect, &another_char_position.page_rect);
+    assert_eq!(
         &char_position.screen_rect,
         &another_char_position.screen_rect,
     );
@@ -59,6 +72,9 @@ fn create_char_position(page_rect: Rect, screen_rect: Rect) -> CharPosition {
         page_rect: page_rect,
         screen_rect: screen_rect,
         page_index: 0,
+        xadvance: 0,
+        kerning: 0,
+        ..Default::default()
     }
 }
 
@@ -318,62 +334,3737 @@ fn letters_with_kerning_for_bottom_to_top_orientation_parsed_correctly() {
     assert_letters_with_kerning_parsed_correctly(OrdinateOrientation::BottomToTop, [-2, -2, -3]);
 }
 
-#[cfg(feature = "parse-error")]
 #[test]
-fn missing_character_handled_correctly() {
-    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
-    match bmfont.parse("Ř") {
-        Err(error) => assert_eq!(error.missing_characters, vec!['Ř']),
-        Ok(_) => panic!(),
+fn orientation_overridden_per_parse_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions {
+        coordinate_system: Some(OrdinateOrientation::BottomToTop.into()),
+        ..Default::default()
+    };
+    let text = "Rust\nYou";
+    let expected = parse(text, OrdinateOrientation::BottomToTop);
+    let parse = font.parse_with(text, &options);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(char_positions.len(), expected.len());
+    for (actual, expected) in char_positions.iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
     }
+
+    let mut layout = Layout::new(options);
+    set_layout_text(&mut layout, &font, text);
+    for (actual, expected) in layout.char_positions().iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+    }
+
+    #[allow(deprecated)]
+    let deprecated_options = LayoutOptions {
+        ordinate_orientation: Some(OrdinateOrientation::BottomToTop),
+        ..Default::default()
+    };
+    let parse = font.parse_with(text, &deprecated_options);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.collect::<Vec<_>>(), char_positions);
 }
 
-#[cfg(not(feature = "parse-error"))]
 #[test]
-fn missing_character_handled_correctly() {
-    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
-    assert_eq!(bmfont.parse("Ř").count(), 0);
+fn coordinate_system_applied_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "Rust\nYou";
+    let expected = parse(text, OrdinateOrientation::TopToBottom);
+    let base_height = font.base_height() as i32;
+    let line_height = font.line_height() as i32;
+    for &(line_origin, dy) in &[
+        (LineOrigin::Top, 0),
+        (LineOrigin::Baseline, -base_height),
+        (LineOrigin::Bottom, -line_height),
+    ] {
+        let options = LayoutOptions {
+            coordinate_system: Some(CoordinateSystem {
+                ordinate_orientation: OrdinateOrientation::TopToBottom,
+                abscissa_orientation: AbscissaOrientation::RightToLeft,
+                line_origin,
+            }),
+            ..Default::default()
+        };
+        let parse = font.parse_with(text, &options);
+
+        #[cfg(feature = "parse-error")]
+        let parse = parse.unwrap();
+
+        let char_positions: Vec<_> = parse.collect();
+        assert_eq!(char_positions.len(), expected.len());
+        for (actual, expected) in char_positions.iter().zip(&expected) {
+            let rect = expected.screen_rect;
+            assert_eq!(
+                actual.screen_rect,
+                Rect {
+                    x: -rect.max_x(),
+                    y: rect.y + dy,
+                    ..rect
+                }
+            );
+        }
+
+        let mut layout = Layout::new(options);
+        layout.set_max_width(Some(200));
+        set_layout_text(&mut layout, &font, "Rust Rust");
+        let mut expected_layout = Layout::new(LayoutOptions::default());
+        expected_layout.set_max_width(Some(200));
+        set_layout_text(&mut expected_layout, &font, "Rust Rust");
+        assert_eq!(layout.pen(), expected_layout.pen());
+        for (actual, expected) in layout
+            .char_positions()
+            .iter()
+            .zip(expected_layout.char_positions())
+        {
+            let rect = expected.screen_rect;
+            assert_eq!(actual.screen_rect.x, -rect.max_x());
+            assert_eq!(actual.screen_rect.y, rect.y + dy);
+        }
+    }
+
+    let up = CoordinateSystem {
+        ordinate_orientation: OrdinateOrientation::BottomToTop,
+        abscissa_orientation: AbscissaOrientation::LeftToRight,
+        line_origin: LineOrigin::Bottom,
+    };
+    let options = LayoutOptions {
+        coordinate_system: Some(up),
+        ..Default::default()
+    };
+    let expected = parse(text, OrdinateOrientation::BottomToTop);
+    let parse = font.parse_with(text, &options);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    for (actual, expected) in parse.zip(&expected) {
+        let mut rect = expected.screen_rect;
+        rect.y += line_height - base_height;
+        assert_eq!(actual.screen_rect, rect);
+    }
+}
+
+/// Returns `font.fnt` with `ascent` more pixels above the baseline of its lines.
+fn create_taller_bmfont(ascent: i32) -> BMFont {
+    let source = std::fs::read_to_string("font.fnt").unwrap();
+    let source = source
+        .replace("lineHeight=80 base=57", "lineHeight=110 base=87")
+        .lines()
+        .map(|line| match line.find("yoffset=") {
+            Some(start) => {
+                let start = start + "yoffset=".len();
+                let end = start + line[start..].find(' ').unwrap();
+                let yoffset: i32 = line[start..end].parse().unwrap();
+                format!("{}{}{}", &line[..start], yoffset + ascent, &line[end..])
+            }
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    BMFont::from_str_with_options(
+        &source,
+        OrdinateOrientation::TopToBottom,
+        &LoadOptions::default(),
+    )
+    .unwrap()
 }
 
-#[cfg(feature = "parse-error")]
 #[test]
-fn unsupported_character_handled_correctly() {
-    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
-    match bmfont.parse("𐃌") {
-        Err(error) => assert_eq!(error.unsupported_characters, vec!['𐃌']),
-        Ok(_) => panic!(),
+fn fonts_of_different_line_heights_share_baseline_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let taller = create_taller_bmfont(30);
+    assert_eq!(taller.base_height(), font.base_height() + 30);
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let rects = |font: &BMFont, coordinate_system: CoordinateSystem| {
+            let options = LayoutOptions {
+                coordinate_system: Some(coordinate_system),
+                ..Default::default()
+            };
+            let parse = font.parse_with("Rust_You", &options);
+
+            #[cfg(feature = "parse-error")]
+            let parse = parse.unwrap();
+
+            parse.map(|p| p.screen_rect).collect::<Vec<_>>()
+        };
+        let baseline = CoordinateSystem::baseline(orientation);
+        assert_eq!(rects(&font, baseline), rects(&taller, baseline));
+        let top = CoordinateSystem {
+            line_origin: LineOrigin::Top,
+            ..baseline
+        };
+        assert_ne!(rects(&font, top), rects(&taller, top));
     }
 }
 
 #[test]
-fn serde() {
-    let bmfont = create_simple_bmfont(OrdinateOrientation::TopToBottom);
-    let serialized = to_string_pretty(&bmfont).unwrap().trim().to_owned();
-    let specimen = read_to_string("font.json").unwrap().trim().to_owned();
+fn orientation_changed_after_loading_correctly() {
+    let mut font = create_bmfont(OrdinateOrientation::TopToBottom);
+    font.set_ordinate_orientation(OrdinateOrientation::BottomToTop);
+    assert_eq!(
+        font.ordinate_orientation(),
+        OrdinateOrientation::BottomToTop
+    );
+    let text = "Rust\nYou";
+    let expected = parse(text, OrdinateOrientation::BottomToTop);
+    let parse = font.parse(text);
 
-    /* Useful while debugging large documents: Save it out and use a merge tool to view differences
-    if specimen != serialized {
-        std::fs::write("test-font.json", &serialized).unwrap();
-    } */
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
 
-    // Note: These additional assertions serve to (hopefully) make it more clear where it failed ..
-    assert_eq!(specimen.len(), serialized.len());
-    assert_eq!(specimen[..10], serialized[..10]);
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(char_positions.len(), expected.len());
+    for (actual, expected) in char_positions.iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+    }
+}
+
+#[test]
+fn channels_exposed_correctly() {
+    for &(line, chnl) in &[(" chnl=4", 4), (" chnl=15", 15), ("", 15)] {
+        let source = SIMPLE_FONT.replace(" chnl=15", line);
+        let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+        let char_positions = font.parse("dd");
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        assert!(char_positions.map(|p| p.chnl).eq(vec![chnl, chnl]));
+
+        let mut text = Vec::new();
+        font.write_text(&mut text).unwrap();
+        let written = String::from_utf8(text).unwrap();
+        assert!(written.contains(&format!(" chnl={}", chnl)));
+    }
+}
+
+#[test]
+fn metrics_computed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let metrics = font.metrics();
     assert_eq!(
-        specimen[specimen.len() - 10..],
-        serialized[serialized.len() - 10..]
+        (metrics.ascent, metrics.descent, metrics.line_gap),
+        (57, 23, 0)
     );
+    assert_eq!(metrics.line_height, 80);
+    assert_eq!(metrics.size, Some(72));
+    assert_eq!(metrics.em_height(), 72);
 
-    // ... before this catch-all
-    assert_eq!(specimen, serialized);
+    for &(size, expected) in &[(" size=-72", Some(72)), (" size=big", None), ("", None)] {
+        let source = SIMPLE_FONT.replace(" size=72", size);
+        let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+        let metrics: VerticalMetrics = font.metrics();
+        assert_eq!(metrics.size, expected);
+        assert_eq!(metrics.em_height(), expected.unwrap_or(80));
+    }
+}
 
-    let deserialized = from_str(&serialized).unwrap();
-    assert_eq!(bmfont, deserialized);
+#[test]
+fn vertical_metrics_computed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let metrics = font.vertical_metrics();
+    assert_eq!(
+        (metrics.ascent, metrics.descent, metrics.line_gap),
+        (57, 17, 6)
+    );
+    assert_eq!(metrics.line_height, 80);
+    assert_eq!(metrics.em_height(), 72);
+
+    // Glyphs reaching below the line leave no gap.
+    let font = BMFont::new(
+        Cursor::new(SIMPLE_FONT.replace("height=55", "height=95")),
+        OrdinateOrientation::TopToBottom,
+    )
+    .unwrap();
+    let metrics = font.vertical_metrics();
+    assert_eq!(
+        (metrics.ascent, metrics.descent, metrics.line_gap),
+        (57, 43, 0)
+    );
 }
 
-#[cfg(not(feature = "parse-error"))]
 #[test]
-fn unsupported_character_handled_correctly() {
+fn width_measured_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    for &s in &["", YOU_WORD, RUST_WORD, "Rust You", " You Rust "] {
+        let parse = font.parse(s);
+
+        #[cfg(feature = "parse-error")]
+        let parse = parse.unwrap();
+
+        let mut parse = parse;
+        parse.by_ref().for_each(drop);
+        assert_eq!(font.width_of(s), parse.pen().0);
+    }
+    assert_eq!(font.width_of("You\n"), font.width_of(YOU_WORD));
+}
+
+#[test]
+fn text_truncated_to_width_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "Rust/You/Rust";
+    let max_width = font.width_of("Rust...") as u32;
+    let cases = [
+        (EllipsisPosition::End, "Rust..."),
+        (EllipsisPosition::Start, "...Rust"),
+        (EllipsisPosition::Middle, "Ru...st"),
+    ];
+    for &(position, expected) in &cases {
+        let truncated = font.truncate_to_width(text, max_width, position);
+        assert_eq!(truncated, expected);
+        assert!(font.width_of(&truncated) <= max_width as i32);
+        assert_eq!(font.truncate_to_width(text, 1000, position), text);
+        assert_eq!(font.truncate_to_width(text, u32::MAX, position), text);
+        assert_eq!(font.truncate_to_width(text, 10, position), "");
+    }
+
+    let mut with_ellipsis = SIMPLE_FONT.replace("chars count=1", "chars count=2");
+    with_ellipsis.push_str(
+        "\nchar id=8230 x=0 y=0 width=10 height=10 xoffset=0 yoffset=0 xadvance=10 page=0 chnl=15",
+    );
+    let font = BMFont::new(Cursor::new(with_ellipsis), OrdinateOrientation::TopToBottom).unwrap();
+    let truncated = font.truncate_to_width("dddd", 50, EllipsisPosition::End);
+    assert_eq!(truncated, "d…");
+}
+
+#[test]
+fn odd_text_laid_out_without_panicking() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "\0R\u{1}u\u{7f}\u{feff}s\u{200b}t\r\r\n\u{2028}\u{10ffff}";
+    let char_positions = font.parse_lossy(text, &LayoutOptions::default());
+    assert_eq!(char_positions.count(), 4);
+    let units = [0xd800, 'R' as u16, 0xdc00, 0xdc00, 0xd800];
+    let char_positions = font.parse_utf16_with(&units, &LayoutOptions::default());
+
+    #[cfg(feature = "parse-error")]
+    assert!(char_positions.is_err());
+
+    #[cfg(not(feature = "parse-error"))]
+    assert_eq!(char_positions.count(), 1);
+
+    // A char with the id 0 which has kerning.
+    let source = SIMPLE_FONT
+        .replace("chars count=1", "chars count=2")
+        .replace("kerning first=100", "kerning first=0")
+        + "\nchar id=0 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=0 chnl=15";
+    let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+    let char_positions: Vec<_> = font
+        .parse_lossy("\0d\0", &LayoutOptions::default())
+        .collect();
+    assert_eq!(char_positions[1].kerning, -4);
+
+    // Glyphs so wide that the pen runs past the bounds of i32.
+    let source = SIMPLE_FONT
+        .replace("xadvance=40", "xadvance=2000000000")
+        .replace("xoffset=2", "xoffset=-2000000000");
+    let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+    let text = "dddd\n\n".repeat(1000) + "dddd";
+    for &anchor in &[None, Some(Anchor::BottomRight)] {
+        let options = LayoutOptions {
+            anchor,
+            anchor_point: (i32::MIN, i32::MAX),
+            empty_line_advance: EmptyLineAdvance::Percent(u32::MAX),
+            ..Default::default()
+        };
+        let mut char_positions = font.parse_lossy(&text, &options);
+        let last = char_positions.by_ref().last().unwrap();
+        // The pen saturates, then the kerning of the last pair moves it back.
+        assert_eq!(char_positions.pen().0, i32::MAX - 4);
+        assert!(last.screen_rect.x > 0);
+    }
+    assert_eq!(font.width_of("dddd"), i32::MAX);
+}
+
+#[test]
+fn control_characters_handled_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "\u{feff}R\u{200b}u\u{202e}st\u{1}";
+    let options = LayoutOptions::default();
+    let error = font.parse_checked(text, &options).err().unwrap();
+    assert_eq!(
+        error.missing_characters,
+        ['\u{feff}', '\u{200b}', '\u{202e}', '\u{1}']
+    );
+
+    let expected: Vec<_> = font.parse_lossy("Rust", &options).collect();
+    let options = LayoutOptions {
+        control_char_policy: ControlCharPolicy::Skip,
+        ..Default::default()
+    };
+    let char_positions: Vec<_> = font.parse_checked(text, &options).unwrap().collect();
+    assert_eq!(char_positions, expected);
+    let char_positions = font.parse_with_fallback(text, &options, |_, _| MissingCharAction::Abort);
+    assert_eq!(char_positions.unwrap().collect::<Vec<_>>(), expected);
+
+    let options = LayoutOptions {
+        control_char_policy: ControlCharPolicy::Substitute('u'),
+        ..Default::default()
+    };
+    let char_positions = font.parse_checked("R\u{200f}st", &options).unwrap();
+    assert_eq!(char_positions.collect::<Vec<_>>(), expected);
+
+    let options = LayoutOptions {
+        control_char_policy: ControlCharPolicy::Error,
+        ..Default::default()
+    };
+    let error = font.parse_checked(text, &options).err().unwrap();
+    assert!(error.missing_characters.is_empty());
+    assert_eq!(
+        error.unsupported_characters,
+        ['\u{feff}', '\u{200b}', '\u{202e}', '\u{1}']
+    );
+    assert_eq!(
+        font.parse_lossy(text, &options).collect::<Vec<_>>(),
+        expected
+    );
+
+    // Line breaks are not control characters.
+    let expected: Vec<_> = font.parse_lossy("R\nu", &options).collect();
+    let char_positions: Vec<_> = font.parse_lossy("R\r\nu", &options).collect();
+    assert_eq!(char_positions, expected);
+}
+
+#[test]
+fn words_laid_out_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "  Rust Řust\r\nYou  ";
+    let options = LayoutOptions::default();
+    let char_positions: Vec<_> = font.parse_lossy(text, &options).collect();
+    let words: Vec<_> = font.words(text, &options).collect();
+    let bounds = |glyphs: &[CharPosition]| {
+        glyphs.iter().fold(Rect::default(), |rect, glyph| {
+            rect.union(&glyph.screen_rect)
+        })
+    };
+    assert_eq!(
+        words,
+        [
+            Word {
+                range: 2..6,
+                rect: bounds(&char_positions[2..6]),
+            },
+            Word {
+                range: 7..12,
+                rect: bounds(&char_positions[7..10]),
+            },
+            Word {
+                range: 14..17,
+                rect: bounds(&char_positions[10..13]),
+            },
+        ]
+    );
+    assert_eq!(&text[words[1].range.clone()], "Řust");
+    assert!(font.words(" \n ", &options).next().is_none());
+}
+
+#[test]
+fn layout_grouped_into_paragraphs_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut layout = Layout::new(LayoutOptions::default());
+    layout.set_max_width(Some(100));
+    let text = "Rust Rust\r\n\r\nYou";
+    let result = layout.set_text(&font, text);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    let paragraphs = layout.paragraphs();
+    let texts: Vec<_> = paragraphs
+        .iter()
+        .map(|paragraph| &text[paragraph.text.clone()])
+        .collect();
+    assert_eq!(texts, ["Rust Rust", "", "You"]);
+    let lines: Vec<_> = paragraphs
+        .iter()
+        .flat_map(|paragraph| paragraph.lines.iter().map(|line| line.metrics.clone()))
+        .collect();
+    assert_eq!(lines, layout.lines().collect::<Vec<_>>());
+    assert_eq!(paragraphs[0].lines.len(), 2);
+    assert_eq!(
+        paragraphs[0].lines[1].glyphs,
+        &layout.char_positions()[5..9]
+    );
+    assert!(paragraphs[1].lines[0].glyphs.is_empty());
+    let glyphs: Vec<_> = paragraphs
+        .iter()
+        .flat_map(|paragraph| paragraph.glyphs())
+        .copied()
+        .collect();
+    assert_eq!(glyphs, layout.char_positions());
+
+    layout.clear();
+    let paragraphs = layout.paragraphs();
+    assert_eq!(paragraphs.len(), 1);
+    assert_eq!(paragraphs[0].lines.len(), 1);
+}
+
+#[test]
+fn layout_overflow_reported_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut layout = Layout::new(LayoutOptions::default());
+    assert_eq!(layout.overflow(&font), None);
+    layout.set_max_lines(Some(0));
+    assert_eq!(
+        layout.overflow(&font),
+        Some(Overflow {
+            line: 0,
+            glyph: 0,
+            text: 0,
+        })
+    );
+
+    layout.set_max_width(Some(100));
+    layout.set_max_lines(Some(2));
+    let result = layout.set_text(&font, "Rust\r\nRust");
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    assert_eq!(layout.overflow(&font), None);
+    let result = layout.insert(&font, 10, " Rust You");
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    let lines: Vec<_> = layout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    let overflow = layout.overflow(&font).unwrap();
+    assert_eq!(overflow.line, 2);
+    assert_eq!(overflow.glyph, lines[2].glyphs.start);
+    assert_eq!(&layout.text()[overflow.text..], "Rust You");
+
+    // Whitespace without glyphs ends the visible lines.
+    let options = LayoutOptions {
+        empty_glyph_policy: EmptyGlyphPolicy::Skip,
+        ..Default::default()
+    };
+    let mut layout = Layout::new(options);
+    layout.set_max_width(Some(100));
+    layout.set_max_lines(Some(1));
+    let result = layout.set_text(&font, "Rust  You");
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    let overflow = layout.overflow(&font).unwrap();
+    assert_eq!(overflow.glyph, 4);
+    assert_eq!(&layout.text()[overflow.text..], "You");
+}
+
+#[test]
+fn text_fitted_to_box_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions::default();
+    let fitted_text = font.fit_to_box("Rust\nYou", &options, 200, 200);
+
+    #[cfg(feature = "parse-error")]
+    let fitted_text = fitted_text.unwrap();
+
+    assert_eq!(fitted_text.scale, 1.0);
+    let expected: Vec<_> = font.parse_lossy("Rust\nYou", &options).collect();
+    assert_eq!(fitted_text.char_positions, expected);
+
+    let width = font.width_of("Rust").max(font.width_of("You")) as u32 / 2;
+    let fitted_text = font.fit_to_box("Rust\nYou", &options, width, 200);
+
+    #[cfg(feature = "parse-error")]
+    let fitted_text = fitted_text.unwrap();
+
+    let bounds = fitted_text
+        .char_positions
+        .iter()
+        .fold(Rect::default(), |rect, glyph| {
+            rect.union(&glyph.screen_rect)
+        });
+    assert!(fitted_text.scale > 0.45 && fitted_text.scale < 0.55);
+    assert!(bounds.max_y() <= 80);
+    // The second line starts a scaled line height lower.
+    let line_height = (80.0 * fitted_text.scale).round() as i32;
+    let you = &fitted_text.char_positions[4];
+    assert!(you.screen_rect.y > line_height && you.screen_rect.y < 2 * line_height);
+
+    let fitted_text = font.fit_to_box("Rust", &options, 0, 0);
+
+    #[cfg(feature = "parse-error")]
+    let fitted_text = fitted_text.unwrap();
+
+    assert!(fitted_text.scale < 0.01);
+    assert!(fitted_text
+        .char_positions
+        .iter()
+        .all(|glyph| glyph.screen_rect.is_empty()));
+}
+
+#[test]
+fn layout_size_computed_correctly() {
+    let mut layout = Layout::new(LayoutOptions::default());
+    assert_eq!(layout.size(), (0, 0));
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let font = create_bmfont(orientation);
+        layout.set_max_width(Some(100));
+        let result = layout.set_text(&font, "You\n\nRust Rust");
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+
+        assert_eq!(layout.size(), (font.width_of("Rust ") as u32, 320));
+        let result = layout.replace_range(&font, 5..14, "You");
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+
+        assert_eq!(layout.size(), (font.width_of("You") as u32, 240));
+    }
+    layout.clear();
+    assert_eq!(layout.size(), (0, 0));
+}
+
+#[test]
+fn layouts_cached_correctly() {
+    let mut font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut cache = LayoutCache::new(2);
+    let options = LayoutOptions::default();
+    let parse_cached = |cache: &mut LayoutCache, font: &BMFont, s: &str| {
+        let char_positions = cache.parse_with(font, s, &options);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        char_positions
+    };
+    let rust = parse_cached(&mut cache, &font, "Rust");
+    assert_eq!(
+        &rust[..],
+        &font.parse_lossy("Rust", &options).collect::<Vec<_>>()[..]
+    );
+    let you = parse_cached(&mut cache, &font, "You");
+    assert!(Arc::ptr_eq(&rust, &parse_cached(&mut cache, &font, "Rust")));
+
+    // "You" is the least recently used layout.
+    parse_cached(&mut cache, &font, "us");
+    assert_eq!(cache.len(), 2);
+    assert!(Arc::ptr_eq(&rust, &parse_cached(&mut cache, &font, "Rust")));
+    assert!(!Arc::ptr_eq(&you, &parse_cached(&mut cache, &font, "You")));
+
+    // Changing the font or the options lays the text out again.
+    font.set_kerning('R', 'u', -10);
+    let kerned = parse_cached(&mut cache, &font, "Rust");
+    assert_eq!(kerned[1].kerning, -10);
+    let options = LayoutOptions {
+        empty_glyph_policy: EmptyGlyphPolicy::Skip,
+        ..Default::default()
+    };
+    let char_positions = cache.parse_with(&font, "Rust you", &options);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    assert_eq!(char_positions.len(), 7);
+
+    #[cfg(feature = "parse-error")]
+    {
+        cache.clear();
+        assert!(cache.parse_with(&font, "Řust", &options).is_err());
+        assert!(cache.is_empty());
+    }
+}
+
+#[test]
+fn cached_layouts_found_without_hashing_font_again() {
+    let mut source = String::from(SIMPLE_FONT.replace("kernings count=1", "kernings count=100001"));
+    for second in 0..100_000 {
+        source.push_str(&format!(
+            "\nkerning first=100 second={} amount=-1",
+            1000 + second
+        ));
+    }
+    let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+    let start = Instant::now();
+    let fingerprint = font.fingerprint();
+    let hashing = start.elapsed();
+
+    let mut cache = LayoutCache::new(1);
+    let options = LayoutOptions::default();
+    let first = cache.parse_with(&font, "ddd", &options);
+
+    #[cfg(feature = "parse-error")]
+    let first = first.unwrap();
+
+    let start = Instant::now();
+    for _ in 0..1000 {
+        let hit = cache.parse_with(&font, "ddd", &options);
+
+        #[cfg(feature = "parse-error")]
+        let hit = hit.unwrap();
+
+        assert!(Arc::ptr_eq(&first, &hit));
+    }
+    // Hashing the font on every hit would take about a thousand times as long.
+    assert!(start.elapsed() < hashing * 100);
+    assert_eq!(font.fingerprint(), fingerprint);
+}
+
+#[test]
+fn kernings_for_character_returned_correctly() {
+    let mut font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let kernings: Vec<_> = font.kernings_for('Y').collect();
+    assert_eq!(kernings.len(), 15);
+    assert!(kernings.contains(&(' ', -1)));
+    for &(second, amount) in &kernings {
+        let expected: Vec<_> = font
+            .parse_lossy(&format!("Y{}", second), &LayoutOptions::default())
+            .collect();
+        assert_eq!(expected[1].kerning, amount);
+    }
+    font.set_kerning('Y', ' ', 0);
+    font.set_kerning('Y', 'z', 2);
+    let kernings: Vec<_> = font.kernings_for('Y').collect();
+    assert_eq!(kernings.len(), 15);
+    assert!(kernings.contains(&('z', 2)));
+    assert!(!kernings.iter().any(|&(second, _)| second == ' '));
+    assert_eq!(font.kernings_for('~').count(), 0);
+    assert_eq!(font.kernings_for('😀').count(), 0);
+}
+
+#[test]
+fn code_page_char_ids_mapped_correctly() {
+    let font_with_charset = |charset: &str, unicode: u32, first: u32, second: u32| {
+        let source = SIMPLE_FONT
+            .replace(
+                "charset= unicode=",
+                &format!("charset=\"{}\" unicode={}", charset, unicode),
+            )
+            .replace("chars count=1", "chars count=2")
+            .replace("char id=100", &format!("char id={}", first))
+            .replace(
+                "kerning first=100 second=100",
+                &format!("kerning first={} second={}", first, second),
+            )
+            + &format!(
+                "\nchar id={} x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=0 chnl=15",
+                second
+            );
+        BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap()
+    };
+    let kerning = |font: &BMFont, s: &str| font.parse_lossy(s, &LayoutOptions::default()).nth(1);
+
+    // "あい" in Shift_JIS.
+    let font = font_with_charset("SHIFTJIS", 0, 0x82A0, 0x82A2);
+    assert!(font.supports("あい"));
+    assert_eq!(kerning(&font, "あい").unwrap().kerning, -4);
+
+    // "Жd" in Windows-1251.
+    let font = font_with_charset("RUSSIAN", 0, 0xC6, 0x64);
+    assert!(font.supports("Жd"));
+    assert_eq!(kerning(&font, "Жd").unwrap().kerning, -4);
+    assert!(font.validate().is_valid());
+
+    // Unicode fonts and unknown code pages keep their char ids.
+    let font = font_with_charset("RUSSIAN", 1, 0xC6, 0x64);
+    assert!(font.supports("Æd"));
+    let font = font_with_charset("OEM", 0, 0xC6, 0x64);
+    assert!(font.supports("Æd"));
+}
+
+#[test]
+fn encoded_bytes_parsed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions::default();
+    let expected: Vec<_> = font.parse_lossy("Rust", &options).collect();
+    let char_positions = font.parse_bytes_with(b"Rust", encoding_rs::WINDOWS_1252, &options);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    assert_eq!(char_positions.collect::<Vec<_>>(), expected);
+
+    // "Жd" in Windows-1251 and "あd" in Shift_JIS, laid out with a font which has them.
+    let source = SIMPLE_FONT.replace("chars count=1", "chars count=3")
+        + "\nchar id=1046 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=0 chnl=15"
+        + "\nchar id=12354 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=2 page=0 chnl=15";
+    let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+    let char_positions = font.parse_bytes(&[0xC6, b'd'], encoding_rs::WINDOWS_1251);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    assert_eq!(char_positions.pen(), (0, 0));
+    assert_eq!(
+        char_positions.map(|p| p.xadvance).collect::<Vec<_>>(),
+        [1, 40]
+    );
+    let char_positions = font.parse_bytes(&[0x82, 0xA0, b'd'], encoding_rs::SHIFT_JIS);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    assert_eq!(
+        char_positions.map(|p| p.xadvance).collect::<Vec<_>>(),
+        [2, 40]
+    );
+
+    // A malformed sequence is a missing character.
+    let char_positions = font.parse_bytes(&[b'd', 0x82], encoding_rs::SHIFT_JIS);
+
+    #[cfg(feature = "parse-error")]
+    assert_eq!(
+        char_positions.err().unwrap().missing_characters,
+        ['\u{fffd}']
+    );
+
+    #[cfg(not(feature = "parse-error"))]
+    assert_eq!(char_positions.count(), 1);
+}
+
+#[test]
+fn char_advance_returned_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let widths = RUST_WORD.chars().map(|c| font.char_advance(c).unwrap());
+    assert_eq!(widths.sum::<i32>(), font.width_of(RUST_WORD));
+    assert_eq!(font.char_advance('Ř'), None);
+    assert_eq!(font.char_advance('\u{1F600}'), None);
+}
+
+#[test]
+fn checked_and_lossy_parsing_chosen_at_runtime_correctly() {
     let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
-    assert_eq!(bmfont.parse("𐃌").count(), 0);
+    let options = LayoutOptions::default();
+    match bmfont.parse_checked("Ř𐃌u", &options) {
+        Err(error) => {
+            assert_eq!(error.missing_characters, vec!['Ř']);
+            assert_eq!(error.unsupported_characters, vec!['𐃌']);
+        }
+        Ok(_) => panic!(),
+    }
+    let checked = bmfont.parse_checked("Rust", &options).unwrap();
+    assert_eq!(
+        checked.collect::<Vec<_>>(),
+        bmfont.parse_lossy("R𐃌uŘst", &options).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn partial_results_returned_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions::default();
+    let partial_parse = bmfont.parse_partial("RuŘst𐃌", &options);
+    assert!(!partial_parse.is_complete());
+    let error = partial_parse.error.unwrap();
+    assert_eq!(error.missing_characters, vec!['Ř']);
+    assert_eq!(error.unsupported_characters, vec!['𐃌']);
+    assert_eq!(
+        partial_parse.char_positions,
+        bmfont.parse_lossy("Rust", &options).collect::<Vec<_>>()
+    );
+
+    let partial_parse = bmfont.parse_partial("Rust", &options);
+    assert!(partial_parse.is_complete());
+    assert_eq!(partial_parse.char_positions.len(), 4);
+}
+
+#[test]
+fn missing_characters_resolved_by_fallback_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions::default();
+    let mut calls = Vec::new();
+    let char_positions = bmfont
+        .parse_with_fallback("Ř\nu𐃌st", &options, |idx, c| {
+            calls.push((idx, c));
+            match c {
+                'Ř' => MissingCharAction::Substitute('R'),
+                _ => MissingCharAction::Skip,
+            }
+        })
+        .unwrap();
+    assert_eq!(
+        char_positions.collect::<Vec<_>>(),
+        bmfont.parse_lossy("R\nust", &options).collect::<Vec<_>>()
+    );
+    assert_eq!(calls, vec![(0, 'Ř'), (3, '𐃌')]);
+
+    let error = bmfont
+        .parse_with_fallback("Ru𐃌st", &options, |_, _| MissingCharAction::Abort)
+        .err()
+        .unwrap();
+    assert!(error.missing_characters.is_empty());
+    assert_eq!(error.unsupported_characters, vec!['𐃌']);
+}
+
+#[test]
+fn coverage_checked_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert!(bmfont.supports(""));
+    assert!(bmfont.supports("Rust\r\nYou\u{2028}"));
+    assert!(!bmfont.supports("Řust"));
+    assert!(!bmfont.supports("Rust𐃌"));
+}
+
+#[test]
+fn missing_chars_counted_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert!(bmfont.missing_chars("Rust\nYou").is_empty());
+    assert_eq!(
+        bmfont.missing_chars("𐃌Řu\nŘ𐃌ŘÝ"),
+        vec![('𐃌', 2), ('Ř', 3), ('Ý', 1)]
+    );
+}
+
+#[test]
+fn decomposed_text_normalized_correctly() {
+    let text = format!(
+        "{}\n{}",
+        read_to_string("font.fnt").unwrap(),
+        "char id=233 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0"
+    );
+    let bmfont = BMFont::new(Cursor::new(text), OrdinateOrientation::TopToBottom).unwrap();
+    let parse = bmfont.parse_nfc("Caf\u{65}\u{301}\ne");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let options = LayoutOptions::default();
+    assert_eq!(
+        parse.collect::<Vec<_>>(),
+        bmfont
+            .parse_lossy("Caf\u{e9}\ne", &options)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn case_fallback_applied_correctly() {
+    let bmfont = create_simple_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions {
+        case_fallback: true,
+        ..Default::default()
+    };
+    let parse = bmfont.parse_with("dD\u{1e9e}", &options);
+
+    #[cfg(feature = "parse-error")]
+    assert!(parse.is_err());
+
+    let parse = bmfont.parse_with("dD", &options);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(char_positions.len(), 2);
+    assert_eq!(char_positions[0].page_rect, char_positions[1].page_rect);
+    assert_eq!(char_positions[1].kerning, -4);
+    assert_eq!(
+        bmfont.parse_lossy("dD", &LayoutOptions::default()).count(),
+        1
+    );
+}
+
+#[test]
+fn missing_space_synthesized_correctly() {
+    let bmfont = create_simple_bmfont(OrdinateOrientation::TopToBottom);
+    let advances = [
+        (MissingSpaceAdvance::Fixed(10), 10),
+        (MissingSpaceAdvance::Percent(50), 28),
+    ];
+    for &(missing_space_advance, advance) in advances.iter() {
+        let options = LayoutOptions {
+            missing_space_advance,
+            ..Default::default()
+        };
+        let parse = bmfont.parse_with("d d", &options);
+
+        #[cfg(feature = "parse-error")]
+        let parse = parse.unwrap();
+
+        let char_positions: Vec<_> = parse.collect();
+        assert_eq!(char_positions.len(), 3);
+        assert!(char_positions[1].page_rect.is_empty());
+        assert_eq!(char_positions[1].xadvance, advance);
+        assert_eq!(char_positions[2].screen_rect.x, 42 + advance);
+    }
+
+    let char_positions: Vec<_> = bmfont
+        .parse_lossy("d d", &LayoutOptions::default())
+        .collect();
+    assert_eq!(char_positions.len(), 2);
+    assert_eq!(char_positions[1].screen_rect.x, 38);
+}
+
+#[cfg(feature = "parse-error")]
+#[test]
+fn missing_character_handled_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    match bmfont.parse("Ř") {
+        Err(error) => assert_eq!(error.missing_characters, vec!['Ř']),
+        Ok(_) => panic!(),
+    }
+}
+
+#[cfg(not(feature = "parse-error"))]
+#[test]
+fn missing_character_handled_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert_eq!(bmfont.parse("Ř").count(), 0);
+}
+
+#[cfg(feature = "parse-error")]
+#[test]
+fn unsupported_character_handled_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    match bmfont.parse("𐃌") {
+        Err(error) => assert_eq!(error.unsupported_characters, vec!['𐃌']),
+        Ok(_) => panic!(),
+    }
+}
+
+#[test]
+fn serde() {
+    let bmfont = create_simple_bmfont(OrdinateOrientation::TopToBottom);
+    let serialized = to_string_pretty(&bmfont).unwrap().trim().to_owned();
+    let specimen = read_to_string("font.json").unwrap().trim().to_owned();
+
+    /* Useful while debugging large documents: Save it out and use a merge tool to view differences
+    if specimen != serialized {
+        std::fs::write("test-font.json", &serialized).unwrap();
+    } */
+
+    // Note: These additional assertions serve to (hopefully) make it more clear where it failed ..
+    assert_eq!(specimen.len(), serialized.len());
+    assert_eq!(specimen[..10], serialized[..10]);
+    assert_eq!(
+        specimen[specimen.len() - 10..],
+        serialized[serialized.len() - 10..]
+    );
+
+    // ... before this catch-all
+    assert_eq!(specimen, serialized);
+
+    let deserialized = from_str(&serialized).unwrap();
+    assert_eq!(bmfont, deserialized);
+}
+
+#[test]
+fn font_written_as_json_correctly() {
+    let bmfont = create_simple_bmfont(OrdinateOrientation::TopToBottom);
+    let json = bmfont.to_json().unwrap();
+    let specimen = read_to_string("font.json").unwrap();
+    assert_eq!(json.trim(), specimen.trim());
+    assert_eq!(from_str::<BMFont>(&json).unwrap(), bmfont);
+}
+
+const MSDF_FONT: &str = r#"{
+  "atlas": { "type": "msdf", "distanceRange": 4, "size": 32, "width": 64, "height": 32, "yOrigin": "top" },
+  "metrics": { "emSize": 1, "lineHeight": 1.25, "ascender": -0.75, "descender": 0.25, "underlineY": 0.1, "underlineThickness": 0.05 },
+  "glyphs": [
+    { "unicode": 32, "advance": 0.25 },
+    {
+      "unicode": 86, "advance": 0.5625,
+      "planeBounds": { "left": -0.03125, "top": -0.71875, "right": 0.59375, "bottom": 0.03125 },
+      "atlasBounds": { "left": 24.5, "top": 0.5, "right": 44.5, "bottom": 24.5 }
+    },
+    {
+      "unicode": 65, "advance": 0.625,
+      "planeBounds": { "left": 0, "top": -0.75, "right": 0.625, "bottom": 0 },
+      "atlasBounds": { "left": 0.5, "top": 0.5, "right": 20.5, "bottom": 24.5 }
+    }
+  ],
+  "kerning": [{ "unicode1": 65, "unicode2": 86, "advance": -0.0625 }]
+}"#;
+
+#[test]
+fn msdf_atlas_loaded_correctly() {
+    let bmfont =
+        BMFont::from_msdf_json(MSDF_FONT, "atlas.png", OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(bmfont.pages().collect::<Vec<_>>(), vec!["atlas.png"]);
+    assert_eq!(bmfont.line_height(), 40);
+    assert_eq!(bmfont.base_height(), 24);
+    assert_eq!(bmfont.metrics().size, Some(32));
+    assert_eq!((bmfont.page_width(), bmfont.page_height()), (64, 32));
+    assert_eq!(bmfont.width_of("AV A"), 20 - 2 + 18 + 8 + 20);
+    assert_eq!(
+        bmfont.distance_field(),
+        Some(DistanceField {
+            field_type: DistanceFieldType::Msdf,
+            distance_range: 4
+        })
+    );
+
+    let parse = bmfont.parse("AV");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(
+        char_positions[0].page_rect,
+        Rect {
+            x: 0,
+            y: 0,
+            width: 21,
+            height: 25
+        }
+    );
+    assert_eq!(
+        char_positions[0].screen_rect,
+        Rect {
+            x: 0,
+            y: 0,
+            width: 21,
+            height: 25
+        }
+    );
+    assert_eq!(char_positions[1].kerning, -2);
+    assert_eq!(
+        char_positions[1].screen_rect,
+        Rect {
+            x: 20 - 2 - 1,
+            y: 1,
+            width: 21,
+            height: 25
+        }
+    );
+    assert!(BMFont::from_msdf_json("{}", "atlas.png", OrdinateOrientation::TopToBottom).is_err());
+}
+
+#[cfg(not(feature = "parse-error"))]
+#[test]
+fn unsupported_character_handled_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert_eq!(bmfont.parse("𐃌").count(), 0);
+}
+
+#[test]
+fn valid_font_validated_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert!(bmfont.validate().is_valid());
+}
+
+#[test]
+fn font_without_page_size_loaded_correctly() {
+    let without_page_size = r#"info face=font size=72
+common lineHeight=80 base=57 pages=1 packed=0
+page id=0 file="font.png"
+chars count=1
+char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15"#;
+    let bmfont = BMFont::new(
+        Cursor::new(without_page_size),
+        OrdinateOrientation::TopToBottom,
+    )
+    .unwrap();
+    assert_eq!((bmfont.page_width(), bmfont.page_height()), (0, 0));
+    assert!(bmfont.validate().is_valid());
+}
+
+#[test]
+fn invalid_font_validated_correctly() {
+    let invalid = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+common lineHeight=80 base=57 scaleW=64 scaleH=64 pages=1 packed=0
+page id=0 file="font.png"
+chars count=2
+char id=100 x=2 y=2 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15
+char id=101 x=40 y=2 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=1 chnl=15
+kernings count=1
+kerning first=100 second=102 amount=-4"#;
+    let bmfont = BMFont::new(Cursor::new(invalid), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(
+        bmfont.validate().issues,
+        vec![
+            ValidationIssue::GlyphOutsidePage { char_id: 101 },
+            ValidationIssue::MissingPage {
+                char_id: 101,
+                page_index: 1,
+            },
+            ValidationIssue::MissingKerningCharacter {
+                first_char_id: 100,
+                second_char_id: 102,
+                missing_char_id: 102,
+            },
+        ]
+    );
+}
+
+#[test]
+fn overlapping_glyphs_validated_correctly() {
+    let overlapping = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+common lineHeight=80 base=57 scaleW=256 scaleH=256 pages=2 packed=1
+page id=0 file="font_0.png"
+page id=1 file="font_1.png"
+chars count=7
+char id=97 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=98 x=19 y=19 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=99 x=20 y=0 width=10 height=10 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=4
+char id=100 x=20 y=0 width=10 height=10 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=2
+char id=101 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=1 chnl=15
+char id=102 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=32 x=5 y=5 width=0 height=0 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+"#;
+    let bmfont = BMFont::new(Cursor::new(overlapping), OrdinateOrientation::TopToBottom).unwrap();
+    let issues = bmfont.validate().issues;
+    assert_eq!(
+        issues,
+        vec![
+            ValidationIssue::OverlappingGlyphs {
+                first_char_id: 97,
+                second_char_id: 98,
+                page_index: 0,
+            },
+            ValidationIssue::OverlappingGlyphs {
+                first_char_id: 98,
+                second_char_id: 102,
+                page_index: 0,
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "Glyphs of chars = (97, 98) overlap on page = 0"
+    );
+}
+
+#[test]
+fn overlapping_glyphs_without_channels_validated_correctly() {
+    // Some exporters write chnl=0 for glyphs drawn in all channels.
+    let overlapping = r#"info face=font size=72
+common lineHeight=80 base=57 scaleW=256 scaleH=256 pages=1 packed=0
+page id=0 file="font.png"
+chars count=3
+char id=97 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=0
+char id=98 x=19 y=19 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=0
+char id=99 x=30 y=0 width=10 height=10 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=4
+"#;
+    let bmfont = BMFont::new(Cursor::new(overlapping), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(
+        bmfont.validate().issues,
+        vec![ValidationIssue::OverlappingGlyphs {
+            first_char_id: 97,
+            second_char_id: 98,
+            page_index: 0,
+        }]
+    );
+}
+
+fn create_bmfont_with_duplicate_char(options: &LoadOptions) -> Result<BMFont, Error> {
+    let duplicate = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+page id=0 file="font.png"
+chars count=2
+char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15
+char id=100 x=40 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15"#;
+    BMFont::with_options(
+        Cursor::new(duplicate),
+        OrdinateOrientation::TopToBottom,
+        options,
+    )
+}
+
+fn page_x_of_duplicate_char(policy: DuplicateCharPolicy) -> i32 {
+    let options = LoadOptions {
+        duplicate_char_policy: policy,
+        ..Default::default()
+    };
+    let bmfont = create_bmfont_with_duplicate_char(&options).unwrap();
+    let parse = bmfont.parse("d");
+
+    #[cfg(feature = "parse-error")]
+    let mut parse = parse.unwrap();
+
+    parse.next().unwrap().page_rect.x
+}
+
+#[test]
+fn duplicate_char_kept_first_correctly() {
+    assert_eq!(page_x_of_duplicate_char(DuplicateCharPolicy::KeepFirst), 2);
+}
+
+#[test]
+fn duplicate_char_kept_last_correctly() {
+    assert_eq!(page_x_of_duplicate_char(DuplicateCharPolicy::KeepLast), 40);
+}
+
+#[test]
+fn duplicate_char_rejected_correctly() {
+    let options = LoadOptions {
+        duplicate_char_policy: DuplicateCharPolicy::Error,
+        ..Default::default()
+    };
+    match create_bmfont_with_duplicate_char(&options) {
+        Err(Error::ConfigParseError(ConfigParseError::DuplicateChar(100))) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn crlf_line_endings_and_bom_handled_correctly() {
+    let simple = read_to_string("font.fnt").unwrap();
+    let windows = format!("\u{feff}{}", simple.replace('\n', "\r\n"));
+    let bmfont = BMFont::new(Cursor::new(windows), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(bmfont.pages().next(), Some("font.png"));
+    assert_eq!(bmfont.line_height(), 80);
+
+    let parse = bmfont.parse(RUST_WORD);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.count(), RUST_WORD.len());
+}
+
+#[test]
+fn quoted_values_with_spaces_parsed_correctly() {
+    let quoted = r#"info face="Comic Sans MS" size=72 bold=0 italic=0 charset="" unicode=1 stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+page id=0 file="my font 01.png"
+chars count=1
+char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15"#;
+    let bmfont = BMFont::new(Cursor::new(quoted), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(bmfont.pages().next(), Some("my font 01.png"));
+}
+
+#[test]
+fn letter_char_fields_parsed_correctly() {
+    let with_letters = r#"info face="Arial" size=32 bold=0 italic=0 charset="" unicode=0 stretchH=100 smooth=1 aa=1 padding=1,1,1,1 spacing=-2,-2
+common lineHeight=37 base=29 scaleW=256 scaleH=256 pages=1 packed=0
+page id=0 file="arial.png"
+chars count=4
+char id=32   x=0    y=0    width=0    height=0    xoffset=-1   yoffset=28   xadvance=8    page=0  chnl=0  letter="space"
+char id=34   x=10   y=0    width=9    height=9    xoffset=0    yoffset=2    xadvance=10   page=0  chnl=0  letter="""
+char id=100  x=20   y=0    width=17   height=24   xoffset=0    yoffset=5    xadvance=17   page=0  chnl=0  letter="d"
+char id=101  x=40   y=0    width=17   height=24   xoffset=0    yoffset=5    xadvance=17   page=0  chnl=0  letter="x" extra=1"#;
+    let bmfont = BMFont::new(Cursor::new(with_letters), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(
+        bmfont.validate().issues,
+        vec![ValidationIssue::LetterMismatch { char_id: 101 }]
+    );
+}
+
+// Written by hand with the deviations of each exporter rather than exported by the tools.
+const LITTERA_FONT: &str = r#"info face="Arial" size="32" bold="0" italic="0" charset="" unicode="1" stretchH="100" smooth="1" aa="1" padding="0,0,0,0" spacing="1,1"
+common lineHeight="37" base="29" scaleW="256" scaleH="256" pages="1" packed="0"
+page id="0" file="arial.png"
+chars count="2"
+char id="100" x="20" y="0" width="17" height="24" xoffset="0" yoffset="5" xadvance="17" page="0" chnl="15"
+char id="101" x="40" y="0" width="17" height="24" xoffset="0" yoffset="5" xadvance="17" page="0" chnl="15"
+kernings count="1"
+kerning first="100" second="101" amount="-1"
+"#;
+
+const SHOEBOX_FONT: &str = r#"common lineHeight=37 base=29 scaleW=256 scaleH=256 pages=1 packed=0
+page id=0 file="arial.png"
+chars count=2
+char id="100" x=20 y=0 width=17 height=24 xoffset=0 yoffset=5 xadvance=17 page=0 chnl=0 letter="d"
+char id="101" x=40 y=0 width=17 height=24 xoffset=0 yoffset=5 xadvance=17 page=0 chnl=0 letter="e"
+kerning first="100" second="101" amount=-1
+"#;
+
+const GLYPH_DESIGNER_FONT: &str = r#"info face="Arial" size=32 bold=0 italic=0 charset="" unicode=0 stretchH=100 smooth=1 aa=1 padding=0,0,0,0 spacing=2,2
+common lineHeight=37 base=29 scaleW=256 scaleH=256 pages=1 packed=0
+page id=0 file="arial.png"
+chars count=2
+char id=100 x=20 y=0 width=17 height=24 xoffset=0.25 yoffset=4.5 xadvance=17.0 page=0 chnl=15
+char id=101 x=40 y=0 width=17 height=24 xoffset=-0.25 yoffset=5.0 xadvance=16.75 page=0 chnl=15
+kernings count=1
+kerning first=100 second=101 amount=-1.0
+"#;
+
+const BMGLYPH_FONT: &str = r#"common lineHeight=37 base=29 scaleW=256 scaleH=256 pages=1 packed=0
+page id=0 file="arial.png"
+chars count=2
+char id=100 x=20 y=0 width=17 height=24 xoffset=0 yoffset=5 xadvance=17 page=0
+char id=101 x=40 y=0 width=17 height=24 xoffset=0 yoffset=5 xadvance=16.5 page=0
+kernings count=1
+kerning first=100 second=101 amount=-1
+"#;
+
+#[test]
+fn exporter_quirks_tolerated_correctly() {
+    let fixtures = [
+        (Exporter::Littera, LITTERA_FONT),
+        (Exporter::ShoeBox, SHOEBOX_FONT),
+        (Exporter::GlyphDesigner, GLYPH_DESIGNER_FONT),
+        (Exporter::BmGlyph, BMGLYPH_FONT),
+    ];
+    for &(exporter, fixture) in fixtures.iter() {
+        let strict = BMFont::new(Cursor::new(fixture), OrdinateOrientation::TopToBottom);
+        assert!(strict.is_err(), "{:?}", exporter);
+
+        let options = LoadOptions {
+            exporter,
+            ..Default::default()
+        };
+        let bmfont = BMFont::with_options(
+            Cursor::new(fixture),
+            OrdinateOrientation::TopToBottom,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(bmfont.line_height(), 37, "{:?}", exporter);
+        assert_eq!(bmfont.width_of("de"), 33, "{:?}", exporter);
+        let parse = bmfont.parse("de");
+
+        #[cfg(feature = "parse-error")]
+        let parse = parse.unwrap();
+
+        let char_positions: Vec<_> = parse.collect();
+        assert_eq!(char_positions[0].screen_rect.y, 5, "{:?}", exporter);
+        assert_eq!(char_positions[1].screen_rect.x, 16, "{:?}", exporter);
+    }
+}
+
+#[test]
+fn interleaved_sections_parsed_correctly() {
+    let interleaved = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+
+kernings count=1
+kerning first=100 second=100 amount=-4
+
+page id=0 file="font.png"
+
+chars count=1
+char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40 page=0 chnl=15
+"#;
+    let bmfont = BMFont::new(Cursor::new(interleaved), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(
+        bmfont,
+        create_simple_bmfont(OrdinateOrientation::TopToBottom)
+    );
+}
+
+#[test]
+fn missing_optional_char_fields_parsed_correctly() {
+    let minimal = r#"info face=font size=72
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+page id=0 file="font.png"
+chars count=1
+char id=100 x=2 y=145 width=35 height=55 xoffset=2 yoffset=5 xadvance=40
+kernings count=1
+kerning first=100 second=100 amount=-4"#;
+    let bmfont = BMFont::new(Cursor::new(minimal), OrdinateOrientation::TopToBottom).unwrap();
+    assert!(bmfont.validate().is_valid());
+    assert_eq!(
+        bmfont,
+        create_simple_bmfont(OrdinateOrientation::TopToBottom)
+    );
+}
+
+#[test]
+fn font_without_chars_parsed_correctly() {
+    let empty = r#"info face=icons size=72
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+page id=0 file="icons.png"
+chars count=0
+kernings count=0"#;
+    let bmfont = BMFont::new(Cursor::new(empty), OrdinateOrientation::TopToBottom).unwrap();
+    assert!(bmfont.validate().is_valid());
+
+    let parse = bmfont.parse("");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.count(), 0);
+}
+
+#[test]
+fn font_without_char_sections_rejected_correctly() {
+    let missing = r#"info face=font size=72
+common lineHeight=80 base=57 scaleW=361 scaleH=512 pages=1 packed=0
+page id=0 file="font.png""#;
+    match BMFont::new(Cursor::new(missing), OrdinateOrientation::TopToBottom) {
+        Err(Error::ConfigParseError(ConfigParseError::MissingSection(section))) => {
+            assert_eq!(section, "char")
+        }
+        _ => panic!(),
+    }
+}
+
+fn parse_with(s: &str, options: &LayoutOptions) -> Vec<CharPosition> {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let parse = font.parse_with(s, options);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    parse.collect()
+}
+
+#[test]
+fn empty_glyphs_skipped_correctly() {
+    let emitted = parse_with("a b", &LayoutOptions::default());
+    assert_eq!(emitted.len(), 3);
+    assert!(emitted[1].page_rect.is_empty());
+
+    let options = LayoutOptions {
+        empty_glyph_policy: EmptyGlyphPolicy::Skip,
+        ..Default::default()
+    };
+    let skipped = parse_with("a b", &options);
+    assert_eq!(skipped.len(), 2);
+    assert_char_positions_equal(&skipped[0], &emitted[0]);
+    assert_char_positions_equal(&skipped[1], &emitted[2]);
+}
+
+#[test]
+fn advances_and_kerning_values_exposed_correctly() {
+    let char_positions = parse(YOU_WORD, OrdinateOrientation::TopToBottom);
+    let advances = char_positions
+        .iter()
+        .map(|char_position| (char_position.xadvance, char_position.kerning))
+        .collect::<Vec<_>>();
+    assert_eq!(advances, vec![(48, 0), (40, -7), (40, 0)]);
+}
+
+#[test]
+fn final_pen_position_reported_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::BottomToTop);
+    let parse = bmfont.parse("Rust\nYou\n");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let mut parse = parse;
+    assert_eq!(parse.pen(), (0, 0));
+    assert_eq!(parse.by_ref().count(), 7);
+    assert_eq!(parse.pen(), (0, -160));
+    assert_eq!(parse.line_count(), 3);
+}
+
+fn layout_runs_unwrapped(runs: &[TextRun], max_width: Option<u32>) -> Vec<RunCharPosition> {
+    let char_positions = layout_runs(runs, &LayoutOptions::default(), max_width);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    char_positions
+}
+
+#[test]
+fn runs_with_different_fonts_share_baseline() {
+    let regular = create_bmfont(OrdinateOrientation::TopToBottom);
+    let tall = read_to_string("font.fnt")
+        .unwrap()
+        .replace("lineHeight=80 base=57", "lineHeight=90 base=67");
+    let tall = BMFont::new(Cursor::new(tall), OrdinateOrientation::TopToBottom).unwrap();
+    let runs = [TextRun::new("d", &regular), TextRun::new("d\nd", &tall)];
+    let char_positions = layout_runs_unwrapped(&runs, None);
+    let positions = char_positions
+        .iter()
+        .map(|p| {
+            (
+                p.run_index,
+                p.char_position.screen_rect.x,
+                p.char_position.screen_rect.y,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(positions, vec![(0, 2, 15), (1, 42, 5), (1, 2, 95)]);
+}
+
+#[test]
+fn runs_wrapped_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let runs = [
+        TextRun::new("Rust ", &bmfont),
+        TextRun::new("Rust", &bmfont),
+    ];
+    let unwrapped = layout_runs_unwrapped(&runs, None);
+    assert_eq!(unwrapped[5].char_position.screen_rect.y, 5);
+
+    let wrapped = layout_runs_unwrapped(&runs, Some(200));
+    assert_eq!(wrapped.len(), 9);
+    for (rect, expected) in wrapped[5..]
+        .iter()
+        .map(|p| &p.char_position.screen_rect)
+        .zip(screen_rects_for_rust_word([85, 99, 98, 86]))
+    {
+        assert_eq!(rect, &expected);
+    }
+
+    // Widths beyond i32 fit everything on one line.
+    assert_eq!(layout_runs_unwrapped(&runs, Some(u32::MAX)), unwrapped);
+}
+
+#[test]
+fn scaled_and_shifted_runs_laid_out_correctly() {
+    for &(orientation, up) in &[
+        (OrdinateOrientation::TopToBottom, -1),
+        (OrdinateOrientation::BottomToTop, 1),
+    ] {
+        let font = create_bmfont(orientation);
+        let plain = layout_runs_unwrapped(&[TextRun::new("You", &font)], None);
+        let shifted = TextRun {
+            baseline_shift: 10,
+            ..TextRun::new("You", &font)
+        };
+        let shifted = layout_runs_unwrapped(&[shifted], None);
+        for (plain, shifted) in plain.iter().zip(&shifted) {
+            let rect = plain.char_position.screen_rect;
+            assert_eq!(
+                shifted.char_position.screen_rect,
+                rect.translate(0, up * 10)
+            );
+        }
+
+        let halved = TextRun {
+            scale: 0.5,
+            ..TextRun::new("You", &font)
+        };
+        let runs = [halved, TextRun::new("You", &font)];
+        let halved = layout_runs_unwrapped(&runs, None);
+        for (plain, halved) in plain.iter().zip(&halved) {
+            let plain = plain.char_position;
+            let halved = halved.char_position;
+            assert_eq!(halved.page_rect, plain.page_rect);
+            assert_eq!(
+                halved.xadvance,
+                (plain.xadvance as f32 / 2.0).round() as i32
+            );
+            assert_eq!(
+                halved.screen_rect.width,
+                plain.screen_rect.width.div_ceil(2)
+            );
+        }
+        let halved_width = halved[..3]
+            .iter()
+            .map(|p| p.char_position.xadvance + p.char_position.kerning)
+            .sum::<i32>();
+        let rect = plain[0].char_position.screen_rect;
+        assert_eq!(
+            halved[3].char_position.screen_rect,
+            rect.translate(halved_width, 0)
+        );
+    }
+
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let plain = layout_runs_unwrapped(&[TextRun::new("u", &font)], None);
+    let superscript = layout_runs_unwrapped(&[TextRun::superscript("u", &font)], None);
+    let subscript = layout_runs_unwrapped(&[TextRun::subscript("u", &font)], None);
+    let bottom = |p: &RunCharPosition| p.char_position.screen_rect.max_y();
+    assert!(bottom(&superscript[0]) < bottom(&plain[0]));
+    assert!(bottom(&subscript[0]) > bottom(&plain[0]));
+}
+
+#[test]
+fn fractional_positions_kept_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let plain = layout_runs_unwrapped(&[TextRun::new("You You", &font)], None);
+    let scaled = TextRun {
+        scale: 0.3,
+        ..TextRun::new("You You", &font)
+    };
+    for &positioning in &[Positioning::Snapped, Positioning::Fractional] {
+        let options = LayoutOptions {
+            positioning,
+            ..Default::default()
+        };
+        let char_positions = layout_runs(&[scaled], &options, None);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        for (plain, scaled) in plain.iter().zip(&char_positions) {
+            let plain = plain.char_position.screen_rect;
+            let scaled = scaled.char_position;
+            let x = scaled.screen_rect.x as f32 + scaled.subpixel.0 as f32 / 256.0;
+            let y = scaled.screen_rect.y as f32 + scaled.subpixel.1 as f32 / 256.0;
+            let expected_x = plain.x as f32 * 0.3;
+            let expected_y = 57.0 - (57 - plain.y) as f32 * 0.3;
+            match positioning {
+                Positioning::Snapped => {
+                    assert_eq!(scaled.subpixel, (0, 0));
+                    assert!((x - expected_x).abs() <= 0.5);
+                    assert!((y - expected_y).abs() <= 0.5);
+                }
+                Positioning::Fractional => {
+                    assert!((x - expected_x).abs() < 0.05);
+                    assert!((y - expected_y).abs() < 0.05);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn fractional_positions_kept_across_wraps() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let scaled = TextRun {
+        scale: 0.3,
+        ..TextRun::new("You You", &font)
+    };
+    let options = LayoutOptions {
+        positioning: Positioning::Fractional,
+        ..Default::default()
+    };
+    let lay_out = |max_width: Option<u32>| {
+        let char_positions = layout_runs(&[scaled], &options, max_width);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        char_positions
+    };
+    let unwrapped = lay_out(None);
+    let wrapped = lay_out(Some(unwrapped[5].char_position.screen_rect.x as u32));
+    assert_ne!(
+        wrapped[4].char_position.screen_rect.y,
+        wrapped[0].char_position.screen_rect.y
+    );
+
+    // The second line keeps the fraction of a pixel the pen had past the wrap.
+    let shift = unwrapped[4].char_position.screen_rect.x - wrapped[4].char_position.screen_rect.x;
+    for (unwrapped, wrapped) in unwrapped[4..].iter().zip(&wrapped[4..]) {
+        let (unwrapped, wrapped) = (unwrapped.char_position, wrapped.char_position);
+        assert_eq!(wrapped.screen_rect.x + shift, unwrapped.screen_rect.x);
+        assert_eq!(wrapped.subpixel, unwrapped.subpixel);
+    }
+}
+
+#[test]
+fn screen_bounds_converted_correctly() {
+    fn draw<T: Coordinate>(char_positions: &[RunCharPosition]) -> Vec<[T; 4]> {
+        char_positions
+            .iter()
+            .map(|p| p.char_position.screen_bounds())
+            .collect()
+    }
+
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let run = TextRun {
+        scale: 0.3,
+        ..TextRun::new("You", &font)
+    };
+    let options = LayoutOptions {
+        positioning: Positioning::Fractional,
+        ..Default::default()
+    };
+    let char_positions = layout_runs(&[run], &options, None);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    let pixels = draw::<i32>(&char_positions);
+    let exact = draw::<f32>(&char_positions);
+    for ((p, pixels), exact) in char_positions.iter().zip(&pixels).zip(&exact) {
+        let rect = p.char_position.screen_rect;
+        assert_eq!(*pixels, <[i32; 4]>::from(rect));
+        let (subpixel_x, subpixel_y) = p.char_position.subpixel;
+        assert_eq!(exact[0], rect.x as f32 + subpixel_x as f32 / 256.0);
+        assert_eq!(exact[1], rect.y as f32 + subpixel_y as f32 / 256.0);
+        assert_eq!(exact[2], rect.width as f32);
+    }
+    assert!(char_positions
+        .iter()
+        .any(|p| p.char_position.subpixel != (0, 0)));
+}
+
+#[test]
+fn glyphs_transformed_correctly() {
+    let text = "Rust\nYou";
+    let expected = parse(text, OrdinateOrientation::TopToBottom);
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let parse = font.parse(text);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let mut seen = Vec::new();
+    let transformed: Vec<_> = parse
+        .transformed(|idx, rect| {
+            seen.push(rect);
+            GlyphTransform {
+                offset: (0, idx as i32),
+                scale: if idx == 0 { 2.0 } else { 1.0 },
+            }
+        })
+        .collect();
+    assert_eq!(
+        seen,
+        expected.iter().map(|p| p.screen_rect).collect::<Vec<_>>()
+    );
+    for (idx, (actual, expected)) in transformed.iter().zip(&expected).enumerate() {
+        let rect = expected.screen_rect;
+        if idx == 0 {
+            assert_eq!(actual.screen_rect.width, rect.width * 2);
+            assert_eq!(actual.screen_rect.height, rect.height * 2);
+            assert_eq!(
+                actual.screen_rect.x + actual.screen_rect.max_x(),
+                rect.x + rect.max_x()
+            );
+        } else {
+            assert_eq!(actual.screen_rect, rect.translate(0, idx as i32));
+        }
+        assert_eq!(actual.page_rect, expected.page_rect);
+    }
+}
+
+#[test]
+fn inline_objects_laid_out_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let icon = InlineObject {
+        width: 40,
+        height: 30,
+        advance: 44,
+        tag: 3,
+    };
+    let runs = [
+        TextRun::new("You ", &font),
+        TextRun::object(icon, &font),
+        TextRun::new("You", &font),
+    ];
+    let char_positions = layout_runs_unwrapped(&runs, None);
+    let plain = layout_runs_unwrapped(&[TextRun::new("You You", &font)], None);
+    assert_eq!(char_positions.len(), plain.len() + 1);
+    let placeholder = char_positions[4];
+    assert_eq!(placeholder.run_index, 1);
+    assert_eq!(placeholder.object, Some(icon));
+    assert!(placeholder.char_position.page_rect.is_empty());
+    let base_height = font.base_height() as i32;
+    assert_eq!(
+        placeholder.char_position.screen_rect,
+        Rect {
+            x: font.width_of("You "),
+            y: base_height - 30,
+            width: 40,
+            height: 30
+        }
+    );
+    assert!(char_positions.iter().filter(|p| p.object.is_some()).count() == 1);
+    // Kerning does not apply across the object.
+    let dx = 44 - plain[4].char_position.kerning;
+    for (actual, expected) in char_positions[5..].iter().zip(&plain[4..]) {
+        assert_eq!(
+            actual.char_position.screen_rect,
+            expected.char_position.screen_rect.translate(dx, 0)
+        );
+    }
+
+    let wrapped = layout_runs_unwrapped(&runs, Some(150));
+    assert_eq!(wrapped[4].char_position.screen_rect.x, 0);
+    assert_eq!(
+        wrapped[4].char_position.screen_rect.y,
+        base_height - 30 + font.line_height() as i32
+    );
+}
+
+#[test]
+fn glyphs_colored_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let red = Color::rgb(255, 0, 0);
+    let runs = [
+        TextRun::new("You ", &font),
+        TextRun {
+            color: red,
+            ..TextRun::new("Rust", &font)
+        },
+    ];
+    let colors = layout_runs_unwrapped(&runs, None)
+        .iter()
+        .map(|p| p.char_position.color)
+        .collect::<Vec<_>>();
+    assert_eq!(colors[..4], [Color::WHITE; 4]);
+    assert_eq!(colors[4..], [red; 4]);
+
+    // Indices count all characters of the text, including line breaks and skipped spaces.
+    let options = LayoutOptions {
+        empty_glyph_policy: EmptyGlyphPolicy::Skip,
+        ..Default::default()
+    };
+    let char_positions = font.parse_with("You\r\n Rust", &options);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    let mut indices = Vec::new();
+    let colored = char_positions.colored(|idx| {
+        indices.push(idx);
+        Color::BLACK
+    });
+    assert!(colored.map(|p| p.color).all(|color| color == Color::BLACK));
+    assert_eq!(indices, [0, 1, 2, 6, 7, 8, 9]);
+    assert_eq!(Color::BLACK.to_f32(), [0.0, 0.0, 0.0, 1.0]);
+}
+
+fn set_layout_text(layout: &mut Layout, font: &BMFont, text: &str) {
+    let result = layout.set_text(font, text);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+}
+
+#[test]
+fn layout_reused_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut layout = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut layout, &bmfont, "Rust Rust");
+    assert_eq!(layout.char_positions().len(), 9);
+    let capacity = layout.char_positions().as_ptr();
+
+    set_layout_text(&mut layout, &bmfont, RUST_WORD);
+    assert_eq!(layout.char_positions().as_ptr(), capacity);
+    let expected = parse(RUST_WORD, OrdinateOrientation::TopToBottom);
+    assert_eq!(layout.char_positions().len(), expected.len());
+    for (actual, expected) in layout.char_positions().iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+    }
+
+    layout.set_max_width(Some(200));
+    set_layout_text(&mut layout, &bmfont, "Rust Rust");
+    assert_eq!(layout.char_positions()[5].screen_rect.y, 85);
+}
+
+#[test]
+fn layout_appended_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut whole = Layout::new(LayoutOptions::default());
+    whole.set_max_width(Some(200));
+    set_layout_text(&mut whole, &bmfont, "You\nRust Rust");
+
+    let mut appended = Layout::new(LayoutOptions::default());
+    appended.set_max_width(Some(200));
+    for text in &["Yo", "u\nRu", "st Ru", "st"] {
+        let result = appended.append_text(&bmfont, text);
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+    }
+
+    assert_eq!(appended.pen(), whole.pen());
+    assert!(appended.lines().eq(whole.lines()));
+    assert_eq!(
+        appended.char_positions().len(),
+        whole.char_positions().len()
+    );
+    for (actual, expected) in appended.char_positions().iter().zip(whole.char_positions()) {
+        assert_char_positions_equal(actual, expected);
+        assert_eq!(actual.kerning, expected.kerning);
+    }
+}
+
+#[test]
+fn layout_edited_near_coordinate_limits_correctly() {
+    let tall = SIMPLE_FONT.replace("lineHeight=80", "lineHeight=1500000000");
+    let tall = BMFont::new(Cursor::new(tall), OrdinateOrientation::TopToBottom).unwrap();
+    let mut edited = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut edited, &tall, "d\nd\nd");
+    let diff = edited.replace_range(&tall, 0..0, "d\n");
+
+    #[cfg(feature = "parse-error")]
+    diff.unwrap();
+
+    // The following lines are moved to the bottom of the coordinate space instead of overflowing.
+    let mut expected = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut expected, &tall, "d\nd\nd\nd");
+    assert!(edited.lines().eq(expected.lines()));
+    assert_eq!(edited.char_positions(), expected.char_positions());
+    assert_eq!(edited.char_positions()[3].screen_rect.y, i32::MAX);
+}
+
+#[test]
+fn layout_edited_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::BottomToTop);
+    let mut edited = Layout::new(LayoutOptions::default());
+    edited.set_max_width(Some(300));
+    set_layout_text(&mut edited, &bmfont, "Rust\nYou Rust You\nRust");
+
+    let edits = [
+        (5..8, "Me\nand you"),
+        (0..0, "You "),
+        (8..13, ""),
+        (19..27, ""),
+        (19..19, "\n\nYou"),
+    ];
+    let mut text = edited.text().to_string();
+    for (range, replace_with) in edits.iter().cloned() {
+        let old_len = edited.char_positions().len();
+        text.replace_range(range.clone(), replace_with);
+        let diff = edited.replace_range(&bmfont, range, replace_with);
+
+        #[cfg(feature = "parse-error")]
+        let diff = diff.unwrap();
+
+        assert_eq!(diff.removed.start, diff.inserted.start);
+        assert_eq!(
+            old_len - diff.removed.len() + diff.inserted.len(),
+            edited.char_positions().len()
+        );
+        assert_eq!(edited.text(), text);
+
+        let mut expected = Layout::new(LayoutOptions::default());
+        expected.set_max_width(Some(300));
+        set_layout_text(&mut expected, &bmfont, &text);
+        assert_eq!(edited.pen(), expected.pen());
+        assert!(edited.lines().eq(expected.lines()));
+        assert_eq!(
+            edited.char_positions().len(),
+            expected.char_positions().len()
+        );
+        for (actual, expected) in edited
+            .char_positions()
+            .iter()
+            .zip(expected.char_positions())
+        {
+            assert_char_positions_equal(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn layout_typed_into_correctly() {
+    let bmfont = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut edited = Layout::new(LayoutOptions::default());
+    edited.set_max_width(Some(200));
+    set_layout_text(&mut edited, &bmfont, "Rust\nRust");
+
+    let mut text = edited.text().to_string();
+    for (idx, c) in " You Rust You".char_indices() {
+        let idx = 4 + idx;
+        let diff = edited.insert(&bmfont, idx, c.encode_utf8(&mut [0; 4]));
+
+        #[cfg(feature = "parse-error")]
+        diff.unwrap();
+
+        text.insert(idx, c);
+    }
+    for _ in 0..4 {
+        let end = text.find('\n').unwrap();
+        let diff = edited.delete(&bmfont, end - 1..end);
+
+        #[cfg(feature = "parse-error")]
+        diff.unwrap();
+
+        text.remove(end - 1);
+    }
+    assert_eq!(edited.text(), "Rust You Rust\nRust");
+
+    let mut expected = Layout::new(LayoutOptions::default());
+    expected.set_max_width(Some(200));
+    set_layout_text(&mut expected, &bmfont, &text);
+    assert_eq!(edited.pen(), expected.pen());
+    assert!(edited.lines().eq(expected.lines()));
+    for (actual, expected) in edited
+        .char_positions()
+        .iter()
+        .zip(expected.char_positions())
+    {
+        assert_char_positions_equal(actual, expected);
+    }
+}
+
+#[test]
+fn lines_shaped_before_layout_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let expected = parse("Rust\nYou Rust", OrdinateOrientation::TopToBottom);
+    let mut lines = Vec::new();
+    let mut shaper = |_: &BMFont, line: &mut Vec<u32>| {
+        lines.push(line.clone());
+        for char_id in line.iter_mut() {
+            if *char_id == '~' as u32 {
+                *char_id = 'R' as u32;
+            }
+        }
+    };
+    let parse = font.parse_shaped("~ust\nYou ~ust", &LayoutOptions::default(), &mut shaper);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(char_positions.len(), expected.len());
+    for (actual, expected) in char_positions.iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+    }
+    let to_ids = |s: &str| s.chars().map(|c| c as u32).collect::<Vec<_>>();
+    assert_eq!(lines, [to_ids("~ust"), to_ids("You ~ust")]);
+
+    #[cfg(feature = "parse-error")]
+    {
+        let mut identity = |_: &BMFont, _: &mut Vec<u32>| ();
+        let parse = font.parse_shaped("~ust\u{1F980}", &LayoutOptions::default(), &mut identity);
+        let error = parse.err().unwrap();
+        assert_eq!(error.missing_characters, ['~']);
+        assert_eq!(error.unsupported_characters, ['\u{1F980}']);
+    }
+}
+
+#[test]
+fn kerning_overridden_correctly() {
+    for &kerning_lookup in &[KerningLookup::BinarySearch, KerningLookup::HashMap] {
+        let options = LoadOptions {
+            kerning_lookup,
+            ..Default::default()
+        };
+        let file = File::open("font.fnt").unwrap();
+        let mut font =
+            BMFont::with_options(file, OrdinateOrientation::TopToBottom, &options).unwrap();
+        let kernings = |font: &BMFont| {
+            let parse = font.parse("AVYoRu");
+
+            #[cfg(feature = "parse-error")]
+            let parse = parse.unwrap();
+
+            parse.map(|p| p.kerning).collect::<Vec<_>>()
+        };
+        let original = kernings(&font);
+        assert_ne!(original[1], 0);
+        let count = font.kerning_count();
+
+        font.set_kerning('A', 'V', -20);
+        font.set_kerning('R', 'u', 5);
+        font.set_kerning('Y', 'o', 0);
+        font.set_kerning('A', '\u{1F980}', 7);
+        assert_eq!(kernings(&font), [0, -20, 0, 0, 0, 5]);
+        assert_eq!(
+            font.kerning_count(),
+            count + 1 - usize::from(original[3] != 0)
+        );
+
+        let mut text = Vec::new();
+        font.write_text(&mut text).unwrap();
+        let copy = BMFont::from_bytes(&text, OrdinateOrientation::TopToBottom).unwrap();
+        assert_eq!(kernings(&copy), kernings(&font));
+    }
+}
+
+#[test]
+fn kerning_hash_map_matches_binary_search() {
+    let options = LoadOptions {
+        kerning_lookup: KerningLookup::HashMap,
+        ..Default::default()
+    };
+    let file = File::open("font.fnt").unwrap();
+    let hashed = BMFont::with_options(file, OrdinateOrientation::TopToBottom, &options).unwrap();
+    let text = "You Rust\nAVAWAY Tomorrow, You're";
+    let expected = parse(text, OrdinateOrientation::TopToBottom);
+    let parse = hashed.parse(text);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let actual = parse.collect::<Vec<_>>();
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+        assert_eq!(actual.kerning, expected.kerning);
+    }
+    assert!(expected
+        .iter()
+        .any(|char_position| char_position.kerning != 0));
+}
+
+static SIMPLE_STATIC_FONT: StaticFont = StaticFont {
+    base_height: 57,
+    line_height: 80,
+    page_width: 361,
+    page_height: 512,
+    size: Some(72),
+    distance_field: None,
+    characters: &[Glyph {
+        id: 100,
+        x: 2,
+        y: 145,
+        width: 35,
+        height: 55,
+        xoffset: 2,
+        yoffset: 5,
+        xadvance: 40,
+        page_index: 0,
+        chnl: 15,
+        letter: None,
+    }],
+    kerning_values: &[bmfont::__private::KerningValue {
+        first_char_id: 100,
+        second_char_id: 100,
+        value: -4,
+    }],
+    pages: &[bmfont::__private::Page {
+        id: 0,
+        file: std::borrow::Cow::Borrowed("font.png"),
+    }],
+};
+
+#[test]
+fn static_font_matches_loaded_font() {
+    let font = BMFont::from_static(&SIMPLE_STATIC_FONT, OrdinateOrientation::TopToBottom);
+    assert_eq!(font, create_simple_bmfont(OrdinateOrientation::TopToBottom));
+}
+
+#[test]
+fn static_font_generated_correctly() {
+    let code = bmfont::codegen::generate(Cursor::new(SIMPLE_FONT), "SIMPLE").unwrap();
+    let expected = r#"pub static SIMPLE: ::bmfont::StaticFont = ::bmfont::StaticFont {
+    base_height: 57,
+    line_height: 80,
+    page_width: 361,
+    page_height: 512,
+    size: Some(72),
+    distance_field: None,
+    characters: &[
+        ::bmfont::Glyph { id: 100, x: 2, y: 145, width: 35, height: 55, xoffset: 2, yoffset: 5, xadvance: 40, page_index: 0, chnl: 15, letter: None },
+    ],
+    kerning_values: &[
+        ::bmfont::__private::KerningValue { first_char_id: 100, second_char_id: 100, value: -4 },
+    ],
+    pages: &[
+        ::bmfont::__private::Page { id: 0, file: ::std::borrow::Cow::Borrowed("font.png") },
+    ],
+};
+"#;
+    assert_eq!(code, expected);
+}
+
+#[test]
+fn font_loaded_from_bytes_correctly() {
+    let bytes = std::fs::read("font.fnt").unwrap();
+    let font = BMFont::from_bytes(&bytes, OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(font, create_bmfont(OrdinateOrientation::TopToBottom));
+
+    let mut bytes = SIMPLE_FONT.as_bytes().to_vec();
+    bytes.push(0xff);
+    match BMFont::from_bytes(&bytes, OrdinateOrientation::TopToBottom) {
+        Err(ConfigParseError::InvalidUtf8(_)) => (),
+        result => panic!("Expected InvalidUtf8, got {:?}", result),
+    }
+}
+
+#[test]
+fn font_loaded_from_memory_map_correctly() {
+    let options = LoadOptions::default();
+    let mapped =
+        unsafe { BMFont::open_mapped("font.fnt", OrdinateOrientation::TopToBottom, &options) };
+    let mapped = mapped.unwrap();
+    let expected = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert_eq!(
+        mapped.char_ids().collect::<Vec<_>>(),
+        expected.char_ids().collect::<Vec<_>>()
+    );
+    assert_eq!(mapped.kerning_count(), expected.kerning_count());
+
+    let missing =
+        unsafe { BMFont::open_mapped("missing.fnt", OrdinateOrientation::TopToBottom, &options) };
+    assert!(matches!(missing, Err(Error::IOError(_))));
+    let image =
+        unsafe { BMFont::open_mapped("font.png", OrdinateOrientation::TopToBottom, &options) };
+    assert!(matches!(
+        image,
+        Err(Error::ConfigParseError(ConfigParseError::InvalidUtf8(_)))
+    ));
+}
+
+#[test]
+fn gzip_compressed_font_loaded_correctly() {
+    let file = File::open("font.fnt.gz").unwrap();
+    let decompressed = BMFont::new(file, OrdinateOrientation::TopToBottom).unwrap();
+    let expected = create_bmfont(OrdinateOrientation::TopToBottom);
+    assert_eq!(
+        decompressed.char_ids().collect::<Vec<_>>(),
+        expected.char_ids().collect::<Vec<_>>()
+    );
+    assert_eq!(decompressed.kerning_count(), expected.kerning_count());
+
+    let mut corrupt = read("font.fnt.gz").unwrap();
+    corrupt.truncate(corrupt.len() / 2);
+    let corrupt = BMFont::new(Cursor::new(corrupt), OrdinateOrientation::TopToBottom);
+    assert!(matches!(corrupt, Err(Error::IOError(_))));
+
+    let bytes = read("font.fnt.gz").unwrap();
+    let from_bytes = BMFont::from_bytes(&bytes, OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(from_bytes, decompressed);
+    let corrupt = BMFont::from_bytes(&bytes[..bytes.len() / 2], OrdinateOrientation::TopToBottom);
+    assert!(matches!(corrupt, Err(ConfigParseError::InvalidGzip(_))));
+}
+
+#[test]
+fn font_generated_from_vector_font_correctly() {
+    let font_data = read("font.ttf").unwrap();
+    let options = GenerateOptions {
+        size: 20,
+        page_width: 20,
+        page_height: 16,
+        ..Default::default()
+    };
+    let generated = BMFont::generate(&font_data, "AVB V\n", "font", &options).unwrap();
+    assert_eq!(generated.missing_chars, vec!['\n', 'B']);
+    assert_eq!(generated.pages.len(), 2);
+
+    let bmfont = &generated.font;
+    assert_eq!(
+        bmfont.pages().collect::<Vec<_>>(),
+        vec!["font_0.png", "font_1.png"]
+    );
+    assert_eq!(bmfont.line_height(), 20);
+    assert_eq!(bmfont.base_height(), 16);
+    assert_eq!(bmfont.char_advance(' '), Some(5));
+    assert_eq!(bmfont.width_of("AV"), 12 - 2 + 12);
+
+    let parse = bmfont.parse("AV");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    let a = &char_positions[0];
+    assert_eq!(
+        a.screen_rect,
+        Rect {
+            x: 1,
+            y: 2,
+            width: 10,
+            height: 14
+        }
+    );
+    let page = &generated.pages[a.page_index as usize];
+    let (x, y) = a.page_rect.min_corner();
+    assert_eq!(
+        page.pixels[(y as u32 * page.width + x as u32) as usize],
+        255
+    );
+    assert_ne!(a.page_index, char_positions[1].page_index);
+    assert_eq!(char_positions[1].kerning, -2);
+
+    let options = GenerateOptions {
+        page_width: 8,
+        ..options
+    };
+    match BMFont::generate(&font_data, "A", "font", &options) {
+        Err(GenerateError::GlyphTooLarge('A')) => (),
+        _ => panic!(),
+    }
+    assert!(BMFont::generate(b"font", "A", "font", &options).is_err());
+}
+
+#[test]
+fn sdf_font_generated_correctly() {
+    let font_data = read("font.ttf").unwrap();
+    let options = GenerateOptions {
+        size: 20,
+        sdf_spread: Some(4),
+        ..Default::default()
+    };
+    let generated = BMFont::generate(&font_data, "A", "font", &options).unwrap();
+    let bmfont = &generated.font;
+    let distance_field = DistanceField {
+        field_type: DistanceFieldType::Sdf,
+        distance_range: 8,
+    };
+    assert_eq!(bmfont.distance_field(), Some(distance_field));
+
+    let parse = bmfont.parse("A");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let a = parse.last().unwrap();
+    assert_eq!(
+        a.screen_rect,
+        Rect {
+            x: -3,
+            y: -2,
+            width: 18,
+            height: 22
+        }
+    );
+    let page = &generated.pages[0];
+    let pixel = |x: i32, y: i32| {
+        let (x, y) = (a.page_rect.x + x, a.page_rect.y + y);
+        page.pixels[(y as u32 * page.width + x as u32) as usize]
+    };
+    assert_eq!(pixel(0, 0), 1);
+    assert_eq!(pixel(9, 11), 255);
+    assert!(pixel(3, 11) < 128 && pixel(4, 11) > 128);
+
+    let mut text = Vec::new();
+    bmfont.write_text(&mut text).unwrap();
+    let copy = BMFont::from_bytes(&text, OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(copy.distance_field(), Some(distance_field));
+}
+
+#[test]
+fn missing_glyphs_inserted_into_dynamic_atlas_correctly() {
+    let font_data = read("font.ttf").unwrap();
+    let options = GenerateOptions {
+        size: 20,
+        page_width: 32,
+        page_height: 32,
+        ..Default::default()
+    };
+    let generated = BMFont::generate(&font_data, "A", "font", &options).unwrap();
+    let mut atlas =
+        DynamicAtlas::new(generated.font, generated.pages, font_data.clone(), options).unwrap();
+
+    let dirty_region = atlas.insert_missing("AV V").unwrap().unwrap();
+    assert_eq!(
+        dirty_region,
+        DirtyRegion {
+            page_index: 0,
+            rect: Rect {
+                x: 1,
+                y: 16,
+                width: 12,
+                height: 14
+            }
+        }
+    );
+    assert!(atlas.font().supports("AV V"));
+    assert_eq!(atlas.font().kerning_count(), 1);
+    assert_eq!(atlas.insert_missing("VA").unwrap(), None);
+
+    let parse = atlas.font().parse("AV");
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let char_positions: Vec<_> = parse.collect();
+    assert_eq!(char_positions[1].page_rect, dirty_region.rect);
+    assert_eq!(char_positions[1].screen_rect.x, 10);
+    let page = &atlas.pages()[0];
+    assert!(page.pixels[(23 * page.width + 7) as usize] > 128);
+
+    let full_options = GenerateOptions {
+        page_height: 20,
+        ..options
+    };
+    let generated = BMFont::generate(&font_data, "A", "font", &full_options).unwrap();
+    let mut atlas = DynamicAtlas::new(generated.font, generated.pages, font_data, options).unwrap();
+    assert!(matches!(
+        atlas.insert_missing("V"),
+        Err(GenerateError::AtlasFull('V'))
+    ));
+    assert!(!atlas.font().supports("V"));
+}
+
+#[test]
+fn pages_loaded_from_source_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let pages = font.load_pages(&mut DirectorySource::new(".")).unwrap();
+    assert_eq!(pages, vec![read("font.png").unwrap()]);
+
+    let mut requested = Vec::new();
+    let mut source = |name: &str| {
+        requested.push(name.to_string());
+        Ok(name.as_bytes().to_vec())
+    };
+    let pages = font.load_pages(&mut source).unwrap();
+    assert_eq!(pages, vec![b"font.png".to_vec()]);
+    assert_eq!(requested, vec!["font.png"]);
+
+    let mut missing = DirectorySource::new("missing");
+    assert!(font.load_pages(&mut missing).is_err());
+}
+
+#[test]
+fn font_written_as_text_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut text = Vec::new();
+    font.write_text(&mut text).unwrap();
+    let copy = BMFont::new(Cursor::new(text), OrdinateOrientation::TopToBottom).unwrap();
+    assert_eq!(copy.line_height(), font.line_height());
+    assert_eq!(
+        copy.pages().collect::<Vec<_>>(),
+        font.pages().collect::<Vec<_>>()
+    );
+    assert_eq!(copy.kerning_count(), font.kerning_count());
+
+    let text = font
+        .char_ids()
+        .filter_map(std::char::from_u32)
+        .collect::<String>();
+    let parse = copy.parse(&text);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    let actual = parse.collect::<Vec<_>>();
+    let expected = parse_with(&text, &LayoutOptions::default());
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(&expected) {
+        assert_char_positions_equal(actual, expected);
+        assert_eq!(actual.kerning, expected.kerning);
+    }
+}
+
+/// Linear congruential generator, so that the mangled fonts are the same on every run.
+struct Mangler(u64);
+
+impl Mangler {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) as usize % bound
+    }
+}
+
+fn load_mangled(bytes: &[u8]) {
+    if let Ok(font) = BMFont::from_bytes(bytes, OrdinateOrientation::TopToBottom) {
+        font.validate();
+    }
+}
+
+#[test]
+fn truncated_fonts_loaded_without_panic() {
+    let bytes = std::fs::read("font.fnt").unwrap();
+    for len in (0..bytes.len()).step_by(7) {
+        load_mangled(&bytes[..len]);
+    }
+}
+
+#[test]
+fn mangled_fonts_loaded_without_panic() {
+    const REPLACEMENTS: &[u8] = b"0123456789-= \"\n\rxa\xff";
+
+    let bytes = std::fs::read("font.fnt").unwrap();
+    let mut mangler = Mangler(0x5eed);
+    for _ in 0..2000 {
+        let mut mangled = bytes.clone();
+        for _ in 0..1 + mangler.next(8) {
+            let idx = mangler.next(mangled.len());
+            mangled[idx] = REPLACEMENTS[mangler.next(REPLACEMENTS.len())];
+        }
+        load_mangled(&mangled);
+    }
+
+    let text = String::from_utf8(bytes).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+    for _ in 0..500 {
+        let mut mangled = lines.clone();
+        for _ in 0..1 + mangler.next(4) {
+            let idx = mangler.next(mangled.len());
+            match mangler.next(3) {
+                0 => {
+                    mangled.remove(idx);
+                }
+                1 => mangled.insert(idx, lines[mangler.next(lines.len())]),
+                _ => mangled.swap(idx, mangler.next(lines.len())),
+            }
+        }
+        load_mangled(mangled.join("\n").as_bytes());
+    }
+}
+
+#[test]
+fn wrong_sections_rejected_correctly() {
+    assert!(Glyph::new("page id=0 file=\"font.png\"").is_err());
+    assert!(bmfont::__private::Page::new("").is_err());
+    assert!(bmfont::__private::KerningValue::new("char id=32").is_err());
+}
+
+#[test]
+fn truncated_font_recovered_correctly() {
+    let text = read_to_string("font.fnt").unwrap();
+    let cut = text.match_indices("\nchar ").nth(40).unwrap().0 + 20;
+    let truncated = &text.as_bytes()[..cut];
+
+    let options = LoadOptions {
+        truncation_policy: TruncationPolicy::Recover,
+        ..Default::default()
+    };
+    let font = BMFont::with_options(truncated, OrdinateOrientation::TopToBottom, &options).unwrap();
+    assert!(font.is_truncated());
+    assert_eq!(font.char_ids().count(), 40);
+    assert_eq!(font.kerning_count(), 0);
+    assert!(font.validate().issues.contains(&ValidationIssue::Truncated));
+
+    assert!(BMFont::from_bytes(truncated, OrdinateOrientation::TopToBottom).is_err());
+    assert!(!create_bmfont(OrdinateOrientation::TopToBottom).is_truncated());
+}
+
+fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn rect_geometry_computed_correctly() {
+    let a = rect(0, 0, 10, 10);
+    let b = rect(5, -5, 10, 10);
+    let c = rect(10, 0, 5, 5);
+
+    assert!(a.contains(0, 0));
+    assert!(a.contains(9, 9));
+    assert!(!a.contains(10, 5));
+    assert!(!a.contains(-1, 5));
+
+    assert!(a.intersects(&b));
+    assert!(!a.intersects(&c));
+    assert_eq!(&a.intersection(&b).unwrap(), &rect(5, 0, 5, 5));
+    assert!(a.intersection(&c).is_none());
+
+    assert_eq!(&a.union(&b), &rect(0, -5, 15, 15));
+    assert_eq!(&rect(0, 0, 0, 0).union(&c), &c);
+    assert_eq!(&a.union(&rect(100, 100, 0, 0)), &a);
+
+    assert_eq!(&a.translate(3, -4), &rect(3, -4, 10, 10));
+}
+
+#[test]
+fn rect_converted_correctly() {
+    let a = rect(-1, 2, 3, 4);
+    assert_eq!(a.min_corner(), (-1, 2));
+    assert_eq!(a.max_corner(), (2, 6));
+    assert_eq!(a.corners(), [(-1, 2), (2, 2), (2, 6), (-1, 6)]);
+    assert_eq!(a.to_f32(), [-1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(<[i32; 4]>::from(a), [-1, 2, 3, 4]);
+    assert_eq!(<(i32, i32, u32, u32)>::from(a), (-1, 2, 3, 4));
+}
+
+#[test]
+fn rects_scaled_to_device_correctly() {
+    let a = rect(-1, 2, 3, 4);
+    assert_eq!(a.to_device(1.0), a);
+    assert_eq!(a.to_device(2.0), rect(-2, 4, 6, 8));
+    for &scale in &[1.25, 1.5, 1.75] {
+        let row = (0..8).map(|i| rect(i * 3, 0, 3, 3).to_device(scale));
+        let row = row.collect::<Vec<_>>();
+        for pair in row.windows(2) {
+            assert_eq!(pair[0].max_x(), pair[1].x);
+        }
+        let width = row.iter().map(|rect| rect.width).sum::<u32>();
+        assert_eq!(width, (24.0 * scale).round() as u32);
+    }
+
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    for char_position in parse(YOU_WORD, OrdinateOrientation::TopToBottom) {
+        let rect = char_position.screen_rect;
+        assert_eq!(char_position.device_screen_rect(2.0), rect.to_device(2.0));
+    }
+    let scaled = TextRun {
+        scale: 0.5,
+        ..TextRun::new(YOU_WORD, &font)
+    };
+    let options = LayoutOptions {
+        positioning: Positioning::Fractional,
+        ..Default::default()
+    };
+    let halved = layout_runs(&[scaled], &options, None);
+
+    #[cfg(feature = "parse-error")]
+    let halved = halved.unwrap();
+
+    for (halved, plain) in halved
+        .iter()
+        .zip(parse(YOU_WORD, OrdinateOrientation::TopToBottom))
+    {
+        let device_rect = halved.char_position.device_screen_rect(2.0);
+        assert!((device_rect.x - plain.screen_rect.x).abs() <= 1);
+    }
+}
+
+#[test]
+fn glyphs_pushed_into_sink_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let expected = parse("You\nRust", OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions::default();
+
+    let mut char_positions = Vec::new();
+    let result = font.parse_into("You\nRust", &options, &mut char_positions);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    assert_eq!(char_positions, expected);
+
+    let mut xs = Vec::new();
+    let mut sink = |char_position: CharPosition| xs.push(char_position.screen_rect.x);
+    let sink: &mut dyn GlyphSink = &mut sink;
+    let result = font.parse_into("You\nRust", &options, sink);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    assert!(xs.iter().eq(expected.iter().map(|p| &p.screen_rect.x)));
+
+    #[cfg(feature = "parse-error")]
+    assert!(font.parse_into("Ř", &options, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn glyph_mesh_built_correctly() {
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let font = create_bmfont(orientation);
+        let expected = parse("You\nRust", orientation);
+        let mut mesh = GlyphMesh::for_font(&font);
+        let result = font.parse_into("You\nRust", &LayoutOptions::default(), &mut mesh);
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+
+        assert_eq!(mesh.len(), expected.len());
+        assert_eq!(mesh.vertices().len(), expected.len() * 4);
+        assert_eq!(mesh.indices().len(), expected.len() * 6);
+        assert!(mesh
+            .indices()
+            .iter()
+            .all(|&i| (i as usize) < mesh.vertices().len()));
+        for (i, char_position) in expected.iter().enumerate() {
+            let quad = &mesh.vertices()[i * 4..i * 4 + 4];
+            assert_eq!(quad, mesh.quad(char_position));
+            let rect = char_position.screen_rect;
+            let corners = rect.corners();
+            for (vertex, corner) in quad.iter().zip(corners.iter()) {
+                assert_eq!(vertex.position, [corner.0 as f32, corner.1 as f32]);
+            }
+            let page_rect = char_position.page_rect;
+            let top = page_rect.y as f32 / font.page_height() as f32;
+            let expected_v = match orientation {
+                OrdinateOrientation::TopToBottom => top,
+                OrdinateOrientation::BottomToTop => {
+                    page_rect.max_y() as f32 / font.page_height() as f32
+                }
+            };
+            assert_eq!(quad[0].tex_coords[1], expected_v);
+            assert_eq!(
+                quad[1].tex_coords[0],
+                page_rect.max_x() as f32 / font.page_width() as f32
+            );
+        }
+
+        let triangles = mesh.to_triangle_list();
+        assert_eq!(triangles.len(), expected.len() * 6);
+        assert_eq!(triangles[3], mesh.vertices()[0]);
+        assert_eq!(triangles[5], mesh.vertices()[3]);
+
+        mesh.clear();
+        assert!(mesh.is_empty());
+    }
+}
+
+#[test]
+fn strings_laid_out_in_batch_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let anchored = LayoutOptions {
+        anchor: Some(Anchor::Center),
+        ..Default::default()
+    };
+    let items = [
+        ("You", LayoutOptions::default()),
+        ("", LayoutOptions::default()),
+        ("Rust You", anchored),
+    ];
+    let mut batch = LayoutBatch::new();
+    let result = font.parse_batch(&items, &mut batch);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    assert_eq!(batch.len(), 3);
+    assert_eq!(batch.ranges(), [0..3, 3..3, 3..11]);
+    for (glyphs, (s, options)) in batch.iter().zip(items.iter()) {
+        let expected = font.parse_with(s, options);
+
+        #[cfg(feature = "parse-error")]
+        let expected = expected.unwrap();
+
+        assert!(glyphs.iter().copied().eq(expected));
+    }
+    assert_eq!(batch.get(3), None);
+
+    let result = font.parse_batch(&items[..1], &mut batch);
+
+    #[cfg(feature = "parse-error")]
+    result.unwrap();
+
+    assert_eq!(batch.get(0).map(<[_]>::len), Some(3));
+    assert_eq!(batch.len(), 1);
+    assert_eq!(batch.char_positions().len(), 3);
+
+    #[cfg(feature = "parse-error")]
+    {
+        let items = [
+            ("You", LayoutOptions::default()),
+            ("Ř", LayoutOptions::default()),
+        ];
+        assert!(font.parse_batch(&items, &mut batch).is_err());
+        assert_eq!(batch.get(0).map(<[_]>::len), Some(3));
+        assert_eq!(batch.len(), 1);
+    }
+
+    batch.clear();
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn font_collection_loaded_correctly() {
+    let options = LoadOptions::default();
+    let font = read_to_string("font.fnt").unwrap();
+    let sources: Vec<(String, Cursor<String>)> = (0..20)
+        .map(|i| (format!("font{}", i), Cursor::new(font.clone())))
+        .collect();
+    let fonts = FontCollection::load(sources, OrdinateOrientation::TopToBottom, &options).unwrap();
+    assert_eq!(fonts.len(), 20);
+    assert!(fonts.contains("font19"));
+    assert_eq!(fonts.get("font7").unwrap().line_height(), 80);
+    assert!(fonts.get("font20").is_none());
+
+    let sources = vec![
+        ("simple", Cursor::new(SIMPLE_FONT.to_owned())),
+        ("broken", Cursor::new("info face=font".to_owned())),
+        ("font", Cursor::new(font.clone())),
+        ("empty", Cursor::new(String::new())),
+    ];
+    let error =
+        FontCollection::load(sources, OrdinateOrientation::TopToBottom, &options).unwrap_err();
+    let names: Vec<&str> = error.errors.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["broken", "empty"]);
+    assert!(error
+        .to_string()
+        .starts_with("Failed to load fonts. broken: "));
+    let mut loaded: Vec<&str> = error.loaded.iter().map(|(name, _)| name).collect();
+    loaded.sort_unstable();
+    assert_eq!(loaded, ["font", "simple"]);
+
+    let sources = vec![
+        ("font", Cursor::new(font.clone())),
+        ("font", Cursor::new(SIMPLE_FONT.to_owned())),
+    ];
+    let mut fonts =
+        FontCollection::load(sources, OrdinateOrientation::TopToBottom, &options).unwrap();
+    assert_eq!(fonts.len(), 1);
+    assert_eq!(fonts.get("font").unwrap().kernings_for('Y').count(), 0);
+    assert!(fonts.remove("font").is_some());
+    assert!(fonts.is_empty());
+}
+
+#[test]
+fn fonts_resolved_from_registry_correctly() {
+    let mut registry = FontRegistry::new();
+    assert!(registry.is_empty());
+    assert!(registry.resolve("ui-regular").is_none());
+
+    registry.register(
+        "ui-regular",
+        create_bmfont(OrdinateOrientation::TopToBottom),
+    );
+    registry.register(
+        "mono",
+        create_simple_bmfont(OrdinateOrientation::TopToBottom),
+    );
+    registry.set_fallback("ui-bold", "ui-medium");
+    registry.set_fallback("ui-medium", "ui-regular");
+    assert_eq!(registry.len(), 2);
+    assert!(registry.get("ui-bold").is_none());
+    let bold = registry.resolve("ui-bold").unwrap();
+    assert_eq!(bold.kernings_for('Y').count(), 15);
+
+    registry.register(
+        "ui-medium",
+        create_simple_bmfont(OrdinateOrientation::TopToBottom),
+    );
+    assert_eq!(
+        registry
+            .resolve("ui-bold")
+            .unwrap()
+            .kernings_for('Y')
+            .count(),
+        0
+    );
+    assert!(registry.unregister("ui-medium").is_some());
+
+    registry.set_fallback("a", "b");
+    registry.set_fallback("b", "a");
+    assert!(registry.resolve("a").is_none());
+    registry.set_default("mono");
+    assert_eq!(registry.default_name(), Some("mono"));
+    assert_eq!(registry.resolve("a").unwrap().kernings_for('d').count(), 1);
+    assert_eq!(
+        registry
+            .resolve("ui-bold")
+            .unwrap()
+            .kernings_for('Y')
+            .count(),
+        15
+    );
+    assert_eq!(registry.remove_fallback("a"), Some("b".to_owned()));
+
+    let mut collection = FontCollection::new();
+    collection.insert(
+        "ui-regular",
+        create_bmfont(OrdinateOrientation::TopToBottom),
+    );
+    let registry = FontRegistry::from(collection);
+    assert!(registry.contains("ui-regular"));
+    assert_eq!(registry.iter().count(), 1);
+}
+
+#[test]
+fn font_hot_reloaded_correctly() {
+    let directory = std::env::temp_dir().join(format!("bmfont-hot-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("font.fnt");
+    std::fs::copy("font.fnt", &path).unwrap();
+
+    let options = LoadOptions::default();
+    let mut watcher = FontWatcher::new(&path, OrdinateOrientation::TopToBottom, &options).unwrap();
+    let font = watcher.font();
+    assert_eq!(font.kernings_for('Y').count(), 15);
+    assert!(watcher.poll().is_none());
+
+    let mut cache = LayoutCache::new(4);
+    let layout_options = LayoutOptions::default();
+    let _ = cache.parse_with(&font, "You", &layout_options);
+    let _ = cache.parse_with(&font, "Rust", &layout_options);
+
+    std::fs::write(&path, SIMPLE_FONT).unwrap();
+    let mut reload = None;
+    for _ in 0..100 {
+        if let Some(result) = watcher.poll() {
+            reload = Some(result.unwrap());
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let reload = reload.expect("the font was not reloaded");
+    assert_eq!(reload.previous_fingerprint, font.fingerprint());
+    assert_eq!(reload.font.kernings_for('d').count(), 1);
+    assert_eq!(watcher.font().fingerprint(), reload.font.fingerprint());
+    // Handles to the previous font keep it.
+    assert_eq!(font.kernings_for('Y').count(), 15);
+
+    assert_eq!(cache.invalidate(reload.previous_fingerprint), 2);
+    assert!(cache.is_empty());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert!(watcher.reload().is_err());
+    assert_eq!(watcher.font().kernings_for('d').count(), 1);
+}
+
+/// Collects the fields of tracing events as `name=value` strings.
+struct EventCollector(Arc<std::sync::Mutex<Vec<String>>>);
+
+impl tracing::Subscriber for EventCollector {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        tracing::span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        struct Visitor(Vec<String>);
+
+        impl tracing::field::Visit for Visitor {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        let mut visitor = Visitor(Vec::new());
+        event.record(&mut visitor);
+        self.0.lock().unwrap().push(visitor.0.join(" "));
+    }
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[test]
+fn tracing_events_emitted_correctly() {
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let collector = EventCollector(events.clone());
+    tracing::subscriber::with_default(collector, || {
+        let source = format!("{}\nfoo bar=1\n", SIMPLE_FONT);
+        let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+        font.parse_lossy("dxd", &LayoutOptions::default()).count();
+    });
+    let events = events.lock().unwrap();
+    assert_eq!(
+        *events,
+        [
+            "message=skipped line with unknown keyword keyword=\"foo\"",
+            "message=found font sections info=true distance_field=false pages=1 chars=1 kernings=1",
+            "message=loaded font chars=1 kernings=1 pages=1 truncated=false",
+            "message=skipped character without glyph char_id=120",
+        ]
+    );
+}
+
+#[test]
+fn font_stats_computed_correctly() {
+    let stats = create_bmfont(OrdinateOrientation::TopToBottom).stats();
+    assert_eq!(stats.glyph_count, 81);
+    assert_eq!(stats.kerning_count, 96);
+    assert_eq!(stats.page_count, 1);
+    assert_eq!(
+        stats.char_ranges,
+        [
+            ' '..='/',
+            ':'..=';',
+            '='..='=',
+            '?'..='[',
+            ']'..='_',
+            'a'..='}',
+            '№'..='№'
+        ]
+    );
+    assert_eq!(
+        stats.blocks,
+        [
+            BlockCoverage {
+                name: "Basic Latin",
+                range: '\0'..='\u{7f}',
+                glyph_count: 80,
+            },
+            BlockCoverage {
+                name: "Letterlike Symbols",
+                range: '\u{2100}'..='\u{214f}',
+                glyph_count: 1,
+            },
+        ]
+    );
+    assert_eq!(stats.blocks[0].coverage(), 80.0 / 128.0);
+    assert!((stats.fill_ratio - 0.757).abs() < 0.001);
+    assert_eq!(stats.min_glyph_size, Some((8, 7)));
+    assert_eq!(stats.max_glyph_size, Some((71, 70)));
+
+    let stats = create_simple_bmfont(OrdinateOrientation::TopToBottom).stats();
+    assert_eq!(stats.glyph_count, 1);
+    assert_eq!(stats.kerning_count, 1);
+    assert_eq!(stats.char_ranges, ['d'..='d']);
+    assert_eq!(stats.fill_ratio, (35.0 * 55.0) / (361.0 * 512.0));
+    assert_eq!(stats.min_glyph_size, Some((35, 55)));
+    assert_eq!(stats.min_glyph_size, stats.max_glyph_size);
+}
+
+#[test]
+fn very_long_lines_laid_out_exactly() {
+    let font = create_simple_bmfont(OrdinateOrientation::TopToBottom);
+    let spacer = |advance| InlineObject {
+        advance,
+        ..Default::default()
+    };
+    // Past 2^24 pixels, f32 can no longer represent every pixel.
+    let runs = [
+        TextRun::object(spacer(1 << 30), &font),
+        TextRun::new("ddd", &font),
+    ];
+    let char_positions = layout_runs_unwrapped(&runs, None);
+    let xs: Vec<i32> = char_positions
+        .iter()
+        .map(|p| p.char_position.screen_rect.x)
+        .collect();
+    assert_eq!(xs, [0, (1 << 30) + 2, (1 << 30) + 38, (1 << 30) + 74]);
+
+    // The pen saturates instead of overflowing.
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let runs = [
+        TextRun::object(spacer(i32::MAX), &font),
+        TextRun::object(spacer(i32::MAX), &font),
+        TextRun::new("You You", &font),
+    ];
+    let char_positions = layout_runs_unwrapped(&runs, None);
+    let last = char_positions.last().unwrap().char_position.screen_rect;
+    assert_eq!(last.max_x(), i32::MAX);
+    let char_positions = layout_runs_unwrapped(&runs, Some(100));
+    let last = char_positions.last().unwrap().char_position.screen_rect;
+    assert!(last.x < font.width_of("You"));
+
+    let rect = Rect {
+        x: i32::MAX - 10,
+        y: i32::MIN,
+        width: 20,
+        height: u32::MAX,
+    };
+    assert_eq!(rect.max_corner(), (i32::MAX, i32::MAX));
+    assert_eq!(rect.translate(20, -20).min_corner(), (i32::MAX, i32::MIN));
+    assert_eq!(rect.union(&Rect::default()), rect);
+    assert_eq!(rect.intersection(&rect).unwrap().height, u32::MAX);
+}
+
+#[test]
+fn text_placed_on_plane_correctly() {
+    let char_positions = parse("You", OrdinateOrientation::TopToBottom);
+    let identity = TextPlane::from_cols_4x4([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    for (quad, char_position) in identity.quads(&char_positions).zip(&char_positions) {
+        let corners = char_position.screen_rect.corners();
+        for (corner, (x, y)) in quad.iter().zip(corners.iter()) {
+            assert_eq!(*corner, [*x as f32, *y as f32, 0.0]);
+        }
+    }
+
+    // Scaled by two, swapped axes and moved.
+    let affine = TextPlane::from_cols_3x4([
+        [0.0, 2.0, 0.0],
+        [2.0, 0.0, 0.0],
+        [0.0, 0.0, 2.0],
+        [1.0, 2.0, 3.0],
+    ]);
+    assert_eq!(affine.transform_point(10.0, 20.0), [41.0, 22.0, 3.0]);
+
+    // The screen lies on the plane x = -1, facing along -x.
+    let billboard = TextPlane::from_axes([-1.0, 0.0, 0.0], [0.0, 0.0, 0.5], [0.0, -0.5, 0.0]);
+    assert_eq!(billboard.transform_point(4.0, 2.0), [-1.0, -1.0, 2.0]);
+    assert_eq!(
+        billboard,
+        TextPlane::from_cols_3x4([
+            [0.0, 0.0, 0.5],
+            [0.0, -0.5, 0.0],
+            [0.25, 0.0, 0.0],
+            [-1.0, 0.0, 0.0],
+        ])
+    );
+
+    // Projective transforms are divided by w.
+    let projective = TextPlane::from_cols_4x4([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 2.0],
+    ]);
+    assert_eq!(projective.transform_point(4.0, 6.0), [2.0, 3.0, 0.0]);
+}
+
+#[test]
+fn scaled_metrics_rounded_consistently() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "You Rust You Rust You Rust";
+    let plain = layout_runs_unwrapped(&[TextRun::new(text, &font)], None);
+    for &scale in &[0.13, 0.3, 0.45] {
+        let scaled = TextRun {
+            scale,
+            ..TextRun::new(text, &font)
+        };
+        let snapped = layout_runs_unwrapped(&[scaled], None);
+        for (plain, snapped) in plain.iter().zip(&snapped) {
+            let plain = plain.char_position.screen_rect;
+            let snapped = snapped.char_position;
+            // Every glyph lies at its exactly scaled position rounded, however far along the line.
+            let expected_x = plain.x as f32 * scale;
+            let expected_y = 57.0 - (57 - plain.y) as f32 * scale;
+            // The pen keeps 256ths of a pixel, which may tip a position lying about halfway.
+            assert!((snapped.screen_rect.x as f32 - expected_x).abs() < 0.52);
+            assert_eq!(snapped.screen_rect.y, (expected_y + 0.5).floor() as i32);
+        }
+        // The reported advances and kerning add up to the exactly scaled width, rounded.
+        let width = |char_positions: &[RunCharPosition]| {
+            char_positions
+                .iter()
+                .map(|p| p.char_position.xadvance + p.char_position.kerning)
+                .sum::<i32>()
+        };
+        let expected_width = width(&plain) as f32 * scale;
+        assert!((width(&snapped) as f32 - expected_width).abs() < 0.52);
+    }
+}
+
+#[test]
+fn glyphs_exposed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let expected = Glyph {
+        id: 100,
+        x: 2,
+        y: 145,
+        width: 35,
+        height: 55,
+        xoffset: 2,
+        yoffset: 5,
+        xadvance: 40,
+        page_index: 0,
+        chnl: 15,
+        letter: None,
+    };
+    assert_eq!(font.glyph('d'), Some(&expected));
+    assert_eq!(font.glyph('Ř'), None);
+    assert_eq!(font.glyphs().len(), 81);
+    assert!(font.glyphs().windows(2).all(|pair| pair[0].id < pair[1].id));
+    assert!(font
+        .glyphs()
+        .iter()
+        .all(|glyph| font.glyph(std::char::from_u32(glyph.id).unwrap()) == Some(glyph)));
+
+    // Generated code of older versions still compiles.
+    #[allow(deprecated)]
+    let char: bmfont::__private::Char = expected.clone();
+    assert_eq!(char, expected);
+}
+
+#[test]
+fn page_rects_flipped_correctly() {
+    let options = LayoutOptions {
+        page_origin: PageOrigin::BottomLeft,
+        ..Default::default()
+    };
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let font = create_bmfont(orientation);
+        let page_height = font.page_height() as i32;
+        let expected = parse("You\nRust", orientation);
+        let flipped = font.parse_with("You\nRust", &options);
+
+        #[cfg(feature = "parse-error")]
+        let flipped = flipped.unwrap();
+
+        let flipped: Vec<_> = flipped.collect();
+        assert_eq!(flipped.len(), expected.len());
+        for (flipped, expected) in flipped.iter().zip(&expected) {
+            let rect = expected.page_rect;
+            assert_eq!(
+                flipped.page_rect,
+                Rect {
+                    y: page_height - rect.max_y(),
+                    ..rect
+                }
+            );
+            assert_eq!(flipped.screen_rect, expected.screen_rect);
+        }
+
+        let runs = layout_runs(&[TextRun::new("You", &font)], &options, None);
+
+        #[cfg(feature = "parse-error")]
+        let runs = runs.unwrap();
+
+        for (run, expected) in runs.iter().zip(&flipped) {
+            assert_eq!(run.char_position.page_rect, expected.page_rect);
+        }
+
+        // The flipped mesh samples the same texels with v measured upwards.
+        let mesh = GlyphMesh::for_font(&font);
+        let flipped_mesh = GlyphMesh::for_font(&font).with_page_origin(PageOrigin::BottomLeft);
+        for (flipped, expected) in flipped.iter().zip(&expected) {
+            let quad = mesh.quad(expected);
+            let flipped_quad = flipped_mesh.quad(flipped);
+            for (vertex, flipped_vertex) in quad.iter().zip(&flipped_quad) {
+                assert_eq!(vertex.position, flipped_vertex.position);
+                assert_eq!(vertex.tex_coords[0], flipped_vertex.tex_coords[0]);
+                assert!((vertex.tex_coords[1] - (1.0 - flipped_vertex.tex_coords[1])).abs() < 1e-6);
+            }
+        }
+    }
+}
+
+#[test]
+fn glyphs_grouped_by_page_correctly() {
+    let two_pages = r#"info face=font size=72 bold=0 italic=0 charset= unicode= stretchH=100 smooth=1 aa=1 padding=2,2,2,2 spacing=0,0 outline=0
+common lineHeight=80 base=57 scaleW=256 scaleH=256 pages=2 packed=0
+page id=0 file="font_0.png"
+page id=1 file="font_1.png"
+chars count=3
+char id=97 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=98 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=1 chnl=15
+char id=99 x=20 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+"#;
+    let bmfont = BMFont::new(Cursor::new(two_pages), OrdinateOrientation::TopToBottom).unwrap();
+    let char_positions = bmfont.parse("abcab");
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    let char_positions: Vec<_> = char_positions.collect();
+    let mut batches: PageBatches = char_positions.iter().copied().collect();
+    assert_eq!(batches.len(), 2);
+    let xs = |char_positions: &[CharPosition]| {
+        char_positions
+            .iter()
+            .map(|p| p.screen_rect.x)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(xs(batches.get(0).unwrap()), [0, 40, 60]);
+    assert_eq!(xs(batches.get(1).unwrap()), [20, 80]);
+    assert_eq!(batches.get(2), None);
+    assert_eq!(
+        batches
+            .iter()
+            .map(|(page_index, char_positions)| (page_index, char_positions.len()))
+            .collect::<Vec<_>>(),
+        [(0, 3), (1, 2)]
+    );
+    assert_eq!(xs(batches.char_positions()), [0, 40, 60, 20, 80]);
+
+    batches.group(char_positions[1..2].iter().copied());
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches.get(0), None);
+    assert_eq!(xs(batches.get(1).unwrap()), [20]);
+    batches.clear();
+    assert!(batches.is_empty());
+}
+
+#[test]
+fn glyphs_clipped_correctly() {
+    fn clip(char_positions: &[CharPosition], clip_rect: Rect) -> Vec<CharPosition> {
+        char_positions
+            .iter()
+            .filter_map(|p| {
+                let rect = p.screen_rect;
+                if rect.is_empty() {
+                    return Some(*p).filter(|_| clip_rect.contains(rect.x, rect.y));
+                }
+                let visible = rect.intersection(&clip_rect)?;
+                Some(CharPosition {
+                    clipped: visible != rect,
+                    ..*p
+                })
+            })
+            .collect()
+    }
+
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "Rust You\nYou\nRust";
+    let unclipped = parse(text, OrdinateOrientation::TopToBottom);
+    for &clip_rect in &[
+        // The second line only.
+        rect(0, 80, 1000, 80),
+        // Across the middle of the second line.
+        rect(0, 100, 1000, 20),
+        // Off the start of the lines, so that the pen still has to advance past the left out
+        // glyphs.
+        rect(50, 0, 1000, 240),
+        rect(-100, -100, 50, 50),
+    ] {
+        let options = LayoutOptions {
+            clip_rect: Some(clip_rect),
+            ..Default::default()
+        };
+        let clipped = font.parse_with(text, &options);
+
+        #[cfg(feature = "parse-error")]
+        let clipped = clipped.unwrap();
+
+        let expected = clip(&unclipped, clip_rect);
+        assert_eq!(clipped.collect::<Vec<_>>(), expected);
+
+        let clipped = font.par_parse_with(text, &options);
+
+        #[cfg(feature = "parse-error")]
+        let clipped = clipped.unwrap();
+
+        assert_eq!(clipped, expected);
+    }
+    let options = LayoutOptions {
+        clip_rect: Some(rect(0, 100, 1000, 20)),
+        ..Default::default()
+    };
+    let clipped = font.parse_with(text, &options);
+
+    #[cfg(feature = "parse-error")]
+    let clipped = clipped.unwrap();
+
+    let clipped: Vec<_> = clipped.collect();
+    assert_eq!(clipped.len(), 3);
+    assert!(clipped.iter().all(|p| p.clipped));
+
+    // Runs are clipped where their lines were broken to.
+    let runs = [TextRun::new("Rust You", &font)];
+    let unclipped = layout_runs(&runs, &LayoutOptions::default(), Some(200));
+    let options = LayoutOptions {
+        clip_rect: Some(rect(0, 80, 1000, 80)),
+        ..Default::default()
+    };
+    let clipped = layout_runs(&runs, &options, Some(200));
+
+    #[cfg(feature = "parse-error")]
+    let (unclipped, clipped) = (unclipped.unwrap(), clipped.unwrap());
+
+    let unclipped: Vec<_> = unclipped.iter().map(|p| p.char_position).collect();
+    let clipped: Vec<_> = clipped.iter().map(|p| p.char_position).collect();
+    assert_eq!(clipped, clip(&unclipped, rect(0, 80, 1000, 80)));
+    assert_eq!(clipped.len(), 3);
+}
+
+#[test]
+fn large_advances_laid_out_exactly() {
+    let large_advances = r#"info face=font size=72
+common lineHeight=80 base=57 scaleW=64 scaleH=64 pages=1 packed=0
+page id=0 file="font.png"
+chars count=2
+char id=97 x=0 y=0 width=8 height=8 xoffset=0 yoffset=0 xadvance=16777217 page=0 chnl=15
+char id=98 x=8 y=0 width=8 height=8 xoffset=0 yoffset=0 xadvance=16777219 page=0 chnl=15
+kernings count=1
+kerning first=97 second=98 amount=3"#;
+    let font = BMFont::new(
+        Cursor::new(large_advances),
+        OrdinateOrientation::TopToBottom,
+    )
+    .unwrap();
+    for options in [
+        LayoutOptions::default(),
+        LayoutOptions {
+            fixed_advance: Some(33554437),
+            ..LayoutOptions::default()
+        },
+    ] {
+        let char_positions = font.parse_with("aab", &options);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        let char_positions: Vec<_> = char_positions.collect();
+        let mut layout = Layout::new(options);
+        let result = layout.set_text(&font, "aab");
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+
+        assert_eq!(layout.char_positions(), &char_positions[..]);
+        let xs: Vec<_> = char_positions
+            .iter()
+            .map(|char_position| char_position.screen_rect.x)
+            .collect();
+        match options.fixed_advance {
+            Some(_) => assert_eq!(xs, [8388610, 41943047, 75497483]),
+            None => assert_eq!(xs, [0, 16777217, 33554437]),
+        }
+    }
+}
+
+#[test]
+fn long_text_parsed_in_parallel_correctly() {
+    let text = "You\r\nRust You\n\nAVAWAY\u{2028}Rust\n".repeat(2000);
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let font = create_bmfont(orientation);
+        for &empty_line_advance in &[EmptyLineAdvance::Full, EmptyLineAdvance::Percent(30)] {
+            let options = LayoutOptions {
+                empty_line_advance,
+                ..Default::default()
+            };
+            let expected = font.parse_with(&text, &options);
+            let actual = font.par_parse_with(&text, &options);
+
+            #[cfg(feature = "parse-error")]
+            let (expected, actual) = (expected.unwrap(), actual.unwrap());
+
+            assert_eq!(actual, expected.collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn owned_text_parsed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = String::from("You Rust\nAVAWAY");
+    let expected = parse(&text, OrdinateOrientation::TopToBottom);
+    let parse = font.parse_owned(text);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn char_iterator_parsed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let chunks = ["You R", "ust\nAVA", "WAY"];
+    let expected = parse(&chunks.concat(), OrdinateOrientation::TopToBottom);
+    let parse = font.parse_chars(chunks.iter().flat_map(|chunk| chunk.chars()));
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.collect::<Vec<_>>(), expected);
+
+    #[cfg(feature = "parse-error")]
+    assert!(font.parse_chars("You€".chars()).is_err());
+}
+
+#[test]
+fn utf16_text_parsed_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "You Rust\nAVAWAY";
+    let units = text.encode_utf16().collect::<Vec<u16>>();
+    let expected = parse(text, OrdinateOrientation::TopToBottom);
+    let parse = font.parse_utf16(&units);
+
+    #[cfg(feature = "parse-error")]
+    let parse = parse.unwrap();
+
+    assert_eq!(parse.collect::<Vec<_>>(), expected);
+
+    #[cfg(feature = "parse-error")]
+    {
+        let units = "R🦀".encode_utf16().collect::<Vec<u16>>();
+        let error = font.parse_utf16(&units).err().unwrap();
+        assert_eq!(error.unsupported_characters, vec!['🦀']);
+    }
+
+    // Unpaired surrogates are never looked up, even if the font has glyphs with their ids.
+    let source = SIMPLE_FONT.replace("chars count=1", "chars count=3")
+        + "\nchar id=55296 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=0 chnl=15"
+        + "\nchar id=56320 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=0 chnl=15";
+    let font = BMFont::new(Cursor::new(source), OrdinateOrientation::TopToBottom).unwrap();
+    for &units in &[[0xd800, 'd' as u16], ['d' as u16, 0xdc00]] {
+        let parses = [
+            font.parse_utf16(&units),
+            font.parse_utf16_with(&units, &LayoutOptions::default()),
+        ];
+        for parse in parses {
+            #[cfg(feature = "parse-error")]
+            {
+                let error = parse.err().unwrap();
+                assert!(error.missing_characters.is_empty());
+                assert_eq!(error.unsupported_characters, ['\u{fffd}']);
+            }
+
+            #[cfg(not(feature = "parse-error"))]
+            assert_eq!(parse.count(), 1);
+        }
+    }
+}
+
+#[test]
+fn line_separators_parsed_correctly() {
+    let expected = parse(
+        "You\nRust\nRust\nYou\n\nRust",
+        OrdinateOrientation::TopToBottom,
+    );
+    let text = "You\r\nRust\u{2028}Rust\rYou\u{2029}\r\nRust";
+    assert_eq!(parse(text, OrdinateOrientation::TopToBottom), expected);
+
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut layout = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut layout, &font, text);
+    assert_eq!(layout.char_positions(), expected.as_slice());
+
+    let runs = [
+        TextRun::new("You\r", &font),
+        TextRun::new("\nRust\u{2028}Rust\rYou\u{2029}\r\nRust", &font),
+    ];
+    let char_positions = layout_runs_unwrapped(&runs, None)
+        .into_iter()
+        .map(|run_char_position| run_char_position.char_position)
+        .collect::<Vec<_>>();
+    assert_eq!(char_positions, expected);
+}
+
+#[test]
+fn layout_edited_around_carriage_returns_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut edited = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut edited, &font, "You\rRust\r\nYou");
+
+    let edits = [(4..4, "\n"), (3..4, ""), (8..9, ""), (3..3, "\r")];
+    let mut text = edited.text().to_string();
+    for (range, replace_with) in edits.iter().cloned() {
+        text.replace_range(range.clone(), replace_with);
+        let diff = edited.replace_range(&font, range, replace_with);
+
+        #[cfg(feature = "parse-error")]
+        diff.unwrap();
+
+        assert_layout_matches_text(&edited, &font, &text);
+    }
+
+    const REPLACEMENTS: &[&str] = &["\r", "\n", "\r\n", "\u{2028}", "Yo", "u ", ""];
+    let mut mangler = Mangler(0xc0de);
+    for _ in 0..300 {
+        let boundaries = text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(Some(text.len()))
+            .collect::<Vec<_>>();
+        let start = boundaries[mangler.next(boundaries.len())];
+        let end = boundaries[mangler.next(boundaries.len())];
+        let range = start.min(end)..start.max(end);
+        let replace_with = REPLACEMENTS[mangler.next(REPLACEMENTS.len())];
+        text.replace_range(range.clone(), replace_with);
+        let diff = edited.replace_range(&font, range, replace_with);
+
+        #[cfg(feature = "parse-error")]
+        diff.unwrap();
+
+        assert_layout_matches_text(&edited, &font, &text);
+    }
+
+    let mut appended = Layout::new(LayoutOptions::default());
+    for piece in &["You\r", "\nRust\r", "\r", "\n"] {
+        let result = appended.append_text(&font, piece);
+
+        #[cfg(feature = "parse-error")]
+        result.unwrap();
+    }
+    assert_layout_matches_text(&appended, &font, "You\r\nRust\r\r\n");
+}
+
+fn assert_layout_matches_text(layout: &Layout, font: &BMFont, text: &str) {
+    let mut expected = Layout::new(LayoutOptions::default());
+    set_layout_text(&mut expected, font, text);
+    assert_eq!(layout.text(), text);
+    assert_eq!(layout.pen(), expected.pen());
+    assert_eq!(layout.char_positions(), expected.char_positions());
+    assert!(layout.lines().eq(expected.lines()));
+}
+
+#[test]
+fn line_endings_reported_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let mut layout = Layout::new(LayoutOptions::default());
+    layout.set_max_width(Some(200));
+    set_layout_text(&mut layout, &font, "Rust Rust\n\nYou");
+
+    let lines = layout.lines().collect::<Vec<_>>();
+    let endings = lines.iter().map(|line| line.ending).collect::<Vec<_>>();
+    assert_eq!(
+        endings,
+        [
+            LineEnding::Soft,
+            LineEnding::Hard,
+            LineEnding::Hard,
+            LineEnding::End
+        ]
+    );
+    assert_eq!(lines[0].glyphs, 0..5);
+    assert_eq!(lines[1].glyphs, 5..9);
+    assert_eq!(lines[2].glyphs, 9..9);
+    assert_eq!(lines[3].glyphs, 9..12);
+    let ys = lines.iter().map(|line| line.y).collect::<Vec<_>>();
+    assert_eq!(ys, [0, 80, 160, 240]);
+    assert_eq!(lines[3].width, layout.pen().0);
+    let char_positions = layout.char_positions();
+    assert_eq!(
+        char_positions[lines[0].glyphs.start].screen_rect.x,
+        char_positions[lines[1].glyphs.start].screen_rect.x
+    );
+
+    // Editing the second paragraph keeps the lines of the first one.
+    let diff = layout.replace_range(&font, 11..11, "\n");
+
+    #[cfg(feature = "parse-error")]
+    diff.unwrap();
+
+    let endings = layout.lines().map(|line| line.ending).collect::<Vec<_>>();
+    assert_eq!(endings.len(), 5);
+    assert_eq!(endings[0], LineEnding::Soft);
+    assert_eq!(layout.lines().last().unwrap().y, 320);
+}
+
+#[test]
+fn fixed_advance_applied_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let options = LayoutOptions {
+        fixed_advance: Some(50),
+        ..Default::default()
+    };
+    let char_positions = font.parse_with(YOU_WORD, &options);
+
+    #[cfg(feature = "parse-error")]
+    let char_positions = char_positions.unwrap();
+
+    let char_positions = char_positions.collect::<Vec<_>>();
+    let expected = parse(YOU_WORD, OrdinateOrientation::TopToBottom);
+    let mut pen = 0;
+    for (i, (actual, expected)) in char_positions.iter().zip(&expected).enumerate() {
+        assert_eq!(actual.page_rect, expected.page_rect);
+        assert_eq!(actual.screen_rect.y, expected.screen_rect.y);
+        assert_eq!(actual.xadvance, 50);
+        assert_eq!(actual.kerning, 0);
+        let xoffset = expected.screen_rect.x - expected.kerning - pen;
+        let cell_offset = (50 - expected.xadvance) / 2;
+        assert_eq!(actual.screen_rect.x, i as i32 * 50 + cell_offset + xoffset);
+        pen += expected.kerning + expected.xadvance;
+    }
+
+    let mut layout = Layout::new(options);
+    set_layout_text(&mut layout, &font, YOU_WORD);
+    assert_eq!(layout.char_positions(), char_positions.as_slice());
+    assert_eq!(layout.pen(), (150, 0));
+}
+
+#[test]
+fn text_block_anchored_correctly() {
+    let text = "You\nRust";
+    for &orientation in &[
+        OrdinateOrientation::TopToBottom,
+        OrdinateOrientation::BottomToTop,
+    ] {
+        let font = create_bmfont(orientation);
+        let width = font.width_of(YOU_WORD).max(font.width_of(RUST_WORD));
+        let plain = parse(text, orientation);
+        let (top, bottom) = match orientation {
+            OrdinateOrientation::TopToBottom => (0, 160),
+            OrdinateOrientation::BottomToTop => (57, 57 - 160),
+        };
+        for &(anchor, dx, dy) in &[
+            (Anchor::TopLeft, 0, -top),
+            (Anchor::Center, -width / 2, -(top + bottom) / 2),
+            (Anchor::BottomRight, -width, -bottom),
+        ] {
+            let options = LayoutOptions {
+                anchor: Some(anchor),
+                anchor_point: (400, 300),
+                ..Default::default()
+            };
+            let char_positions = font.parse_with(text, &options);
+
+            #[cfg(feature = "parse-error")]
+            let char_positions = char_positions.unwrap();
+
+            let mut char_positions = char_positions;
+            let anchored = char_positions.by_ref().collect::<Vec<_>>();
+            assert_eq!(anchored.len(), plain.len());
+            for (anchored, plain) in anchored.iter().zip(&plain) {
+                let expected = plain.screen_rect.translate(400 + dx, 300 + dy);
+                assert_eq!(anchored.screen_rect, expected);
+            }
+            let line_offset = bottom - top;
+            let (x, y) = char_positions.pen();
+            assert_eq!(
+                (x - font.width_of(RUST_WORD), y),
+                (400 + dx, 300 + dy + line_offset / 2)
+            );
+        }
+    }
+}
+
+#[test]
+fn empty_lines_advanced_correctly() {
+    let font = create_bmfont(OrdinateOrientation::TopToBottom);
+    let text = "You\n\n\nRust\n";
+    for &(empty_line_advance, y) in &[
+        (EmptyLineAdvance::Full, 240),
+        (EmptyLineAdvance::Percent(50), 160),
+        (EmptyLineAdvance::None, 80),
+    ] {
+        let options = LayoutOptions {
+            empty_line_advance,
+            ..Default::default()
+        };
+        let char_positions = font.parse_with(text, &options);
+
+        #[cfg(feature = "parse-error")]
+        let char_positions = char_positions.unwrap();
+
+        let char_positions = char_positions.collect::<Vec<_>>();
+        let rust = parse("Rust", OrdinateOrientation::TopToBottom);
+        assert_eq!(char_positions[3].screen_rect.y, rust[0].screen_rect.y + y);
+
+        let mut layout = Layout::new(options);
+        set_layout_text(&mut layout, &font, text);
+        assert_eq!(layout.char_positions(), char_positions.as_slice());
+        assert_eq!(layout.pen(), (0, y + 80));
+    }
 }